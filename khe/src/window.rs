@@ -248,10 +248,12 @@ impl ApplicationHandler<AsyncInitData> for App {
             WindowEvent::CursorMoved { position, .. } => {
                 gui.cursor_pos = Some([position.x as f32, position.y as f32]);
             }
-            WindowEvent::MouseInput { state, button, .. } => {
-                if button == MouseButton::Left {
-                    gui.mouse_pressed = state == ElementState::Pressed;
-                }
+            WindowEvent::MouseInput {
+                state,
+                button: MouseButton::Left,
+                ..
+            } => {
+                gui.mouse_pressed = state == ElementState::Pressed;
             }
             WindowEvent::RedrawRequested => {
                 // Safely get components
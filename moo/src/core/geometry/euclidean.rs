@@ -1,5 +1,6 @@
+use super::lie::LieGroup;
 use super::manifold::Manifold;
-use glam::DVec3;
+use glam::{DMat2, DMat3, DVec2, DVec3};
 
 #[derive(Debug, Clone, Copy, Default)]
 pub struct Euclidean3;
@@ -20,3 +21,124 @@ impl Manifold for Euclidean3 {
         q - p
     }
 }
+
+/// The additive group of translations: abelian, so `exp`, the adjoint and the
+/// Jacobians are all identities.
+impl LieGroup for Euclidean3 {
+    type Algebra = DVec3;
+    type Matrix = DMat3;
+
+    fn identity() -> Self::Point {
+        DVec3::ZERO
+    }
+
+    fn compose(a: Self::Point, b: Self::Point) -> Self::Point {
+        a + b
+    }
+
+    fn inverse(p: Self::Point) -> Self::Point {
+        -p
+    }
+
+    fn exp(v: Self::Tangent) -> Self::Point {
+        v
+    }
+
+    fn log(p: Self::Point) -> Self::Tangent {
+        p
+    }
+
+    fn adjoint(_p: Self::Point, v: Self::Tangent) -> Self::Tangent {
+        v
+    }
+
+    fn adjoint_matrix(_p: Self::Point) -> Self::Matrix {
+        DMat3::IDENTITY
+    }
+
+    fn hat(v: Self::Tangent) -> Self::Algebra {
+        v
+    }
+
+    fn vee(m: Self::Algebra) -> Self::Tangent {
+        m
+    }
+
+    fn right_jacobian(_v: Self::Tangent) -> Self::Matrix {
+        DMat3::IDENTITY
+    }
+
+    fn right_jacobian_inv(_v: Self::Tangent) -> Self::Matrix {
+        DMat3::IDENTITY
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Euclidean2;
+
+impl Manifold for Euclidean2 {
+    type Point = DVec2;
+    type Tangent = DVec2;
+
+    fn dim() -> usize {
+        2
+    }
+
+    fn retract(p: Self::Point, v: Self::Tangent) -> Self::Point {
+        p + v
+    }
+
+    fn local(p: Self::Point, q: Self::Point) -> Self::Tangent {
+        q - p
+    }
+}
+
+/// Planar translations, the 2D analogue of the [`Euclidean3`] group.
+impl LieGroup for Euclidean2 {
+    type Algebra = DVec2;
+    type Matrix = DMat2;
+
+    fn identity() -> Self::Point {
+        DVec2::ZERO
+    }
+
+    fn compose(a: Self::Point, b: Self::Point) -> Self::Point {
+        a + b
+    }
+
+    fn inverse(p: Self::Point) -> Self::Point {
+        -p
+    }
+
+    fn exp(v: Self::Tangent) -> Self::Point {
+        v
+    }
+
+    fn log(p: Self::Point) -> Self::Tangent {
+        p
+    }
+
+    fn adjoint(_p: Self::Point, v: Self::Tangent) -> Self::Tangent {
+        v
+    }
+
+    fn adjoint_matrix(_p: Self::Point) -> Self::Matrix {
+        DMat2::IDENTITY
+    }
+
+    fn hat(v: Self::Tangent) -> Self::Algebra {
+        v
+    }
+
+    fn vee(m: Self::Algebra) -> Self::Tangent {
+        m
+    }
+
+    fn right_jacobian(_v: Self::Tangent) -> Self::Matrix {
+        DMat2::IDENTITY
+    }
+
+    fn right_jacobian_inv(_v: Self::Tangent) -> Self::Matrix {
+        DMat2::IDENTITY
+    }
+}
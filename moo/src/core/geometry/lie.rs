@@ -0,0 +1,58 @@
+use crate::core::geometry::manifold::Manifold;
+
+/// A matrix Lie group whose [`Manifold`] structure is the group exponential.
+///
+/// Conventions (shared by every implementation in this module):
+/// * Tangent vectors live in the Lie algebra and are **body** (right) increments:
+///   `retract(p, v) = p * exp(v)` and `local(p, q) = log(p⁻¹ * q)`.
+/// * `Matrix` is a linear map on the tangent space, in the coordinate order of `Tangent`.
+/// * The right Jacobian satisfies `exp(v + δ) ≈ exp(v) * exp(J_r(v) δ)` and the left
+///   Jacobian `exp(v + δ) ≈ exp(J_l(v) δ) * exp(v)`, with `J_l(v) = J_r(-v)`.
+pub trait LieGroup: Manifold {
+    /// Matrix representation of an algebra element (the image of [`LieGroup::hat`]).
+    type Algebra: Clone + Copy + std::fmt::Debug;
+
+    /// Linear map on the tangent space (adjoint matrix, Jacobians).
+    type Matrix: Clone + Copy + std::fmt::Debug;
+
+    fn identity() -> Self::Point;
+
+    /// Group product `a * b` (apply `b` first).
+    fn compose(a: Self::Point, b: Self::Point) -> Self::Point;
+
+    fn inverse(p: Self::Point) -> Self::Point;
+
+    /// Exponential map from the algebra to the group.
+    fn exp(v: Self::Tangent) -> Self::Point;
+
+    /// Logarithmic map, the inverse of [`LieGroup::exp`] near the identity.
+    fn log(p: Self::Point) -> Self::Tangent;
+
+    /// Adjoint action `Ad_p v`, so that `p * exp(v) * p⁻¹ = exp(Ad_p v)`.
+    fn adjoint(p: Self::Point, v: Self::Tangent) -> Self::Tangent;
+
+    /// Matrix of [`LieGroup::adjoint`] at `p`.
+    fn adjoint_matrix(p: Self::Point) -> Self::Matrix;
+
+    /// Tangent coordinates to algebra matrix (`v^`).
+    fn hat(v: Self::Tangent) -> Self::Algebra;
+
+    /// Algebra matrix to tangent coordinates (`m∨`), the inverse of [`LieGroup::hat`].
+    fn vee(m: Self::Algebra) -> Self::Tangent;
+
+    /// Right Jacobian of the exponential map (`dexp` in body coordinates).
+    fn right_jacobian(v: Self::Tangent) -> Self::Matrix;
+
+    /// Inverse of [`LieGroup::right_jacobian`], i.e. the derivative of `log`.
+    fn right_jacobian_inv(v: Self::Tangent) -> Self::Matrix;
+
+    /// Left Jacobian of the exponential map (`dexp` in spatial coordinates).
+    fn left_jacobian(v: Self::Tangent) -> Self::Matrix {
+        Self::right_jacobian(-v)
+    }
+
+    /// Inverse of [`LieGroup::left_jacobian`].
+    fn left_jacobian_inv(v: Self::Tangent) -> Self::Matrix {
+        Self::right_jacobian_inv(-v)
+    }
+}
@@ -1,7 +1,20 @@
 pub mod euclidean;
+pub mod lie;
 pub mod manifold;
+pub mod product;
+pub mod s2;
+pub mod se2;
+pub mod se3;
+pub mod shapes;
+pub mod so2;
 pub mod so3;
 
-pub use euclidean::Euclidean3;
+pub use euclidean::{Euclidean2, Euclidean3};
+pub use lie::LieGroup;
 pub use manifold::Manifold;
+pub use product::{Product, ProductTangent};
+pub use s2::S2;
+pub use se2::{Pose2, SE2, Twist2};
+pub use se3::{Pose, SE3, Twist};
+pub use so2::SO2;
 pub use so3::SO3;
@@ -0,0 +1,118 @@
+use crate::core::geometry::lie::LieGroup;
+use crate::core::geometry::manifold::Manifold;
+use std::marker::PhantomData;
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// The product manifold `A × B`, e.g. `Product<Euclidean3, SO3>` for a rigid body
+/// whose position and orientation are retracted independently.
+///
+/// Points are pairs `(a, b)`; tangents are [`ProductTangent`]s. Nest products for
+/// more than two factors.
+///
+/// This is a standalone combinator: [`PhaseSpace`](crate::core::state::PhaseSpace)
+/// does not store its configuration as a `Product`. Its list of
+/// [`Block`](crate::core::state::Block)s plays that role over the SoA storage, and
+/// [`PhaseSpace::drift`](crate::core::state::PhaseSpace::drift) retracts block by
+/// block just as `Product::retract` does factor by factor.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Product<A, B>(PhantomData<(A, B)>);
+
+/// Tangent vector of a [`Product`] manifold.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ProductTangent<TA, TB>(pub TA, pub TB);
+
+impl<TA: Add<Output = TA>, TB: Add<Output = TB>> Add for ProductTangent<TA, TB> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0 + rhs.0, self.1 + rhs.1)
+    }
+}
+
+impl<TA: Sub<Output = TA>, TB: Sub<Output = TB>> Sub for ProductTangent<TA, TB> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self(self.0 - rhs.0, self.1 - rhs.1)
+    }
+}
+
+impl<TA: Neg<Output = TA>, TB: Neg<Output = TB>> Neg for ProductTangent<TA, TB> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self(-self.0, -self.1)
+    }
+}
+
+impl<TA: Mul<f64, Output = TA>, TB: Mul<f64, Output = TB>> Mul<f64> for ProductTangent<TA, TB> {
+    type Output = Self;
+    fn mul(self, rhs: f64) -> Self {
+        Self(self.0 * rhs, self.1 * rhs)
+    }
+}
+
+impl<A: Manifold, B: Manifold> Manifold for Product<A, B> {
+    type Point = (A::Point, B::Point);
+    type Tangent = ProductTangent<A::Tangent, B::Tangent>;
+
+    fn dim() -> usize {
+        A::dim() + B::dim()
+    }
+
+    fn retract(p: Self::Point, v: Self::Tangent) -> Self::Point {
+        (A::retract(p.0, v.0), B::retract(p.1, v.1))
+    }
+
+    fn local(p: Self::Point, q: Self::Point) -> Self::Tangent {
+        ProductTangent(A::local(p.0, q.0), B::local(p.1, q.1))
+    }
+}
+
+/// The direct product of two Lie groups is a Lie group; its tangent maps are
+/// block diagonal, stored as the pair of diagonal blocks.
+impl<A: LieGroup, B: LieGroup> LieGroup for Product<A, B> {
+    type Algebra = (A::Algebra, B::Algebra);
+    type Matrix = (A::Matrix, B::Matrix);
+
+    fn identity() -> Self::Point {
+        (A::identity(), B::identity())
+    }
+
+    fn compose(a: Self::Point, b: Self::Point) -> Self::Point {
+        (A::compose(a.0, b.0), B::compose(a.1, b.1))
+    }
+
+    fn inverse(p: Self::Point) -> Self::Point {
+        (A::inverse(p.0), B::inverse(p.1))
+    }
+
+    fn exp(v: Self::Tangent) -> Self::Point {
+        (A::exp(v.0), B::exp(v.1))
+    }
+
+    fn log(p: Self::Point) -> Self::Tangent {
+        ProductTangent(A::log(p.0), B::log(p.1))
+    }
+
+    fn adjoint(p: Self::Point, v: Self::Tangent) -> Self::Tangent {
+        ProductTangent(A::adjoint(p.0, v.0), B::adjoint(p.1, v.1))
+    }
+
+    fn adjoint_matrix(p: Self::Point) -> Self::Matrix {
+        (A::adjoint_matrix(p.0), B::adjoint_matrix(p.1))
+    }
+
+    fn hat(v: Self::Tangent) -> Self::Algebra {
+        (A::hat(v.0), B::hat(v.1))
+    }
+
+    fn vee(m: Self::Algebra) -> Self::Tangent {
+        ProductTangent(A::vee(m.0), B::vee(m.1))
+    }
+
+    fn right_jacobian(v: Self::Tangent) -> Self::Matrix {
+        (A::right_jacobian(v.0), B::right_jacobian(v.1))
+    }
+
+    fn right_jacobian_inv(v: Self::Tangent) -> Self::Matrix {
+        (A::right_jacobian_inv(v.0), B::right_jacobian_inv(v.1))
+    }
+}
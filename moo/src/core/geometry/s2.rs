@@ -0,0 +1,93 @@
+use crate::core::geometry::manifold::Manifold;
+use glam::DVec3;
+
+/// Below this geodesic length `sin θ / θ` switches to its Taylor series.
+const SMALL_ANGLE: f64 = 1e-8;
+
+/// The unit sphere S² ⊂ R³, for directors, spins and beads on a spherical surface.
+///
+/// Points are unit vectors. A tangent vector at `p` is a vector orthogonal to `p`,
+/// stored in ambient R³ coordinates; components along `p` are ignored by
+/// [`Manifold::retract`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct S2;
+
+impl S2 {
+    /// Projects an ambient vector onto the tangent plane at `p`: `v - (p·v) p`.
+    pub fn project(p: DVec3, v: DVec3) -> DVec3 {
+        v - p * p.dot(v)
+    }
+
+    /// Exponential map at `p`: follows the great circle with initial velocity `v`
+    /// for unit time, `p cos|v| + (v/|v|) sin|v|`.
+    pub fn exp(p: DVec3, v: DVec3) -> DVec3 {
+        let v = Self::project(p, v);
+        let theta = v.length();
+        let sinc = if theta < SMALL_ANGLE {
+            1.0 - theta * theta / 6.0
+        } else {
+            theta.sin() / theta
+        };
+        (p * theta.cos() + v * sinc).normalize()
+    }
+
+    /// Logarithmic map at `p`: the tangent vector whose great circle reaches `q`
+    /// in unit time. Its length is the geodesic distance `atan2(|p×q|, p·q)`.
+    ///
+    /// For antipodal points every direction is a geodesic; an arbitrary one is returned.
+    pub fn log(p: DVec3, q: DVec3) -> DVec3 {
+        let cos = p.dot(q);
+        let perp = q - p * cos;
+        let sin = perp.length();
+        let theta = sin.atan2(cos);
+        if sin < SMALL_ANGLE {
+            if cos > 0.0 {
+                return perp;
+            }
+            return p.any_orthonormal_vector() * theta;
+        }
+        perp * (theta / sin)
+    }
+
+    /// Geodesic distance (angle) between two unit vectors.
+    pub fn distance(p: DVec3, q: DVec3) -> f64 {
+        p.cross(q).length().atan2(p.dot(q))
+    }
+
+    /// Parallel-transports the tangent vector `w` at `p` along the geodesic
+    /// `t -> exp(p, t v)` up to `t = 1`.
+    ///
+    /// Transporting `v` itself gives the velocity of the geodesic at its end point.
+    pub fn transport(p: DVec3, v: DVec3, w: DVec3) -> DVec3 {
+        let v = Self::project(p, v);
+        let theta = v.length();
+        if theta < SMALL_ANGLE {
+            return Self::project(Self::exp(p, v), w);
+        }
+        let u = v / theta;
+        let along = w.dot(u);
+        let (sin, cos) = theta.sin_cos();
+        // The component in the plane of motion turns with the geodesic; the
+        // component normal to that plane is unchanged.
+        w + u * (along * (cos - 1.0)) - p * (along * sin)
+    }
+}
+
+impl Manifold for S2 {
+    type Point = DVec3;
+    /// Ambient representation of a vector in the tangent plane.
+    type Tangent = DVec3;
+
+    fn dim() -> usize {
+        2
+    }
+
+    /// p_{new} = exp_p(v), which stays exactly on the sphere.
+    fn retract(p: Self::Point, v: Self::Tangent) -> Self::Point {
+        Self::exp(p, v)
+    }
+
+    fn local(p: Self::Point, q: Self::Point) -> Self::Tangent {
+        Self::log(p, q)
+    }
+}
@@ -0,0 +1,248 @@
+use crate::core::geometry::lie::LieGroup;
+use crate::core::geometry::manifold::Manifold;
+use crate::core::geometry::so2::SO2;
+use glam::{DMat3, DVec2, DVec3};
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// Below this rotation angle the exp/log coefficients switch to their Taylor series.
+const SMALL_ANGLE: f64 = 1e-4;
+
+/// A planar rigid-body pose: rotation by `angle` followed by translation.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Pose2 {
+    pub angle: f64,
+    pub trans: DVec2,
+}
+
+impl Pose2 {
+    pub const IDENTITY: Self = Self {
+        angle: 0.0,
+        trans: DVec2::ZERO,
+    };
+
+    pub fn new(angle: f64, trans: DVec2) -> Self {
+        Self { angle, trans }
+    }
+
+    pub fn rotate(self, p: DVec2) -> DVec2 {
+        DVec2::from_angle(self.angle).rotate(p)
+    }
+
+    /// Group composition `self * other` (apply `other` first).
+    pub fn compose(self, other: Self) -> Self {
+        Self {
+            angle: SO2::wrap(self.angle + other.angle),
+            trans: self.trans + self.rotate(other.trans),
+        }
+    }
+
+    pub fn inverse(self) -> Self {
+        let angle = -self.angle;
+        Self {
+            angle,
+            trans: -DVec2::from_angle(angle).rotate(self.trans),
+        }
+    }
+
+    pub fn transform_point(self, p: DVec2) -> DVec2 {
+        self.rotate(p) + self.trans
+    }
+}
+
+impl Mul for Pose2 {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        self.compose(rhs)
+    }
+}
+
+/// Tangent vector in the Lie Algebra se(2), in body coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Twist2 {
+    pub omega: f64,
+    pub v: DVec2,
+}
+
+impl Twist2 {
+    pub fn new(omega: f64, v: DVec2) -> Self {
+        Self { omega, v }
+    }
+}
+
+impl Add for Twist2 {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.omega + rhs.omega, self.v + rhs.v)
+    }
+}
+
+impl Sub for Twist2 {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.omega - rhs.omega, self.v - rhs.v)
+    }
+}
+
+impl Neg for Twist2 {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self::new(-self.omega, -self.v)
+    }
+}
+
+impl Mul<f64> for Twist2 {
+    type Output = Self;
+    fn mul(self, rhs: f64) -> Self {
+        Self::new(self.omega * rhs, self.v * rhs)
+    }
+}
+
+/// The Special Euclidean Group SE(2) of planar rigid-body poses.
+///
+/// Same right-trivialised convention as [`SE3`](super::SE3): `retract(T, ξ) = T * exp(ξ)`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SE2;
+
+impl SE2 {
+    /// Exponential map se(2) -> SE(2).
+    /// The translation is `V(θ) v` with `V = [[a, -b], [b, a]]`,
+    /// `a = sin θ / θ`, `b = (1 - cos θ) / θ`.
+    pub fn exp(xi: Twist2) -> Pose2 {
+        let (a, b) = Self::v_coefficients(xi.omega);
+        let v = xi.v;
+        Pose2 {
+            angle: SO2::wrap(xi.omega),
+            trans: DVec2::new(a * v.x - b * v.y, b * v.x + a * v.y),
+        }
+    }
+
+    /// Logarithmic map SE(2) -> se(2), the inverse of [`SE2::exp`].
+    pub fn log(pose: Pose2) -> Twist2 {
+        let theta = SO2::wrap(pose.angle);
+        let (a, b) = Self::v_coefficients(theta);
+        let det = a * a + b * b;
+        let t = pose.trans;
+        Twist2 {
+            omega: theta,
+            v: DVec2::new(a * t.x + b * t.y, -b * t.x + a * t.y) / det,
+        }
+    }
+
+    /// Adjoint action `Ad_T ξ`, so that `T * exp(ξ) * T⁻¹ = exp(Ad_T ξ)`.
+    pub fn adjoint(pose: Pose2, xi: Twist2) -> Twist2 {
+        let t = pose.trans;
+        Twist2 {
+            omega: xi.omega,
+            v: pose.rotate(xi.v) + DVec2::new(t.y, -t.x) * xi.omega,
+        }
+    }
+
+    fn v_coefficients(theta: f64) -> (f64, f64) {
+        if theta.abs() < SMALL_ANGLE {
+            let theta_sq = theta * theta;
+            (1.0 - theta_sq / 6.0, theta * (0.5 - theta_sq / 24.0))
+        } else {
+            (theta.sin() / theta, (1.0 - theta.cos()) / theta)
+        }
+    }
+}
+
+impl Manifold for SE2 {
+    type Point = Pose2;
+    type Tangent = Twist2;
+
+    fn dim() -> usize {
+        3
+    }
+
+    fn retract(p: Self::Point, v: Self::Tangent) -> Self::Point {
+        p.compose(Self::exp(v))
+    }
+
+    fn local(p: Self::Point, q: Self::Point) -> Self::Tangent {
+        Self::log(p.inverse().compose(q))
+    }
+}
+
+impl LieGroup for SE2 {
+    /// Homogeneous 3x3 form `[[ω]×, v; 0, 0]`.
+    type Algebra = DMat3;
+    /// Acts on tangent coordinates ordered `[omega, v.x, v.y]`.
+    type Matrix = DMat3;
+
+    fn identity() -> Self::Point {
+        Pose2::IDENTITY
+    }
+
+    fn compose(a: Self::Point, b: Self::Point) -> Self::Point {
+        a.compose(b)
+    }
+
+    fn inverse(p: Self::Point) -> Self::Point {
+        p.inverse()
+    }
+
+    fn exp(v: Self::Tangent) -> Self::Point {
+        SE2::exp(v)
+    }
+
+    fn log(p: Self::Point) -> Self::Tangent {
+        SE2::log(p)
+    }
+
+    fn adjoint(p: Self::Point, v: Self::Tangent) -> Self::Tangent {
+        SE2::adjoint(p, v)
+    }
+
+    fn adjoint_matrix(p: Self::Point) -> Self::Matrix {
+        let (s, c) = p.angle.sin_cos();
+        DMat3::from_cols(
+            DVec3::new(1.0, p.trans.y, -p.trans.x),
+            DVec3::new(0.0, c, s),
+            DVec3::new(0.0, -s, c),
+        )
+    }
+
+    fn hat(v: Self::Tangent) -> Self::Algebra {
+        DMat3::from_cols(
+            DVec3::new(0.0, v.omega, 0.0),
+            DVec3::new(-v.omega, 0.0, 0.0),
+            DVec3::new(v.v.x, v.v.y, 0.0),
+        )
+    }
+
+    fn vee(m: Self::Algebra) -> Self::Tangent {
+        Twist2::new(m.x_axis.y, DVec2::new(m.z_axis.x, m.z_axis.y))
+    }
+
+    /// Rotation block is 1, translation block is the rotation-like `[[a, b], [-b, a]]`
+    /// and the coupling column is the derivative of the `V(θ) v` term.
+    fn right_jacobian(v: Self::Tangent) -> Self::Matrix {
+        let theta = v.omega;
+        let (a, b) = Self::v_coefficients(theta);
+        // (θ - sin θ) / θ² and (1 - cos θ) / θ²
+        let (p, r) = if theta.abs() < SMALL_ANGLE {
+            let theta_sq = theta * theta;
+            (
+                theta / 6.0 - theta * theta_sq / 120.0,
+                0.5 - theta_sq / 24.0,
+            )
+        } else {
+            let theta_sq = theta * theta;
+            (
+                (theta - theta.sin()) / theta_sq,
+                (1.0 - theta.cos()) / theta_sq,
+            )
+        };
+        let (x, y) = (v.v.x, v.v.y);
+        DMat3::from_cols(
+            DVec3::new(1.0, x * p - y * r, x * r + y * p),
+            DVec3::new(0.0, a, -b),
+            DVec3::new(0.0, b, a),
+        )
+    }
+
+    fn right_jacobian_inv(v: Self::Tangent) -> Self::Matrix {
+        Self::right_jacobian(v).inverse()
+    }
+}
@@ -0,0 +1,355 @@
+use crate::core::geometry::lie::LieGroup;
+use crate::core::geometry::manifold::Manifold;
+use crate::core::geometry::so3::SO3;
+use glam::{DMat3, DMat4, DQuat, DVec3};
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// Below this rotation angle the exp/log coefficients switch to their Taylor series.
+const SMALL_ANGLE: f64 = 1e-4;
+
+/// A rigid-body pose: rotation (unit quaternion) followed by translation.
+/// Acts on points as `x -> rot * x + trans`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pose {
+    pub rot: DQuat,
+    pub trans: DVec3,
+}
+
+impl Default for Pose {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Pose {
+    pub const IDENTITY: Self = Self {
+        rot: DQuat::IDENTITY,
+        trans: DVec3::ZERO,
+    };
+
+    pub fn new(rot: DQuat, trans: DVec3) -> Self {
+        Self { rot, trans }
+    }
+
+    /// Group composition `self * other` (apply `other` first).
+    pub fn compose(self, other: Self) -> Self {
+        Self {
+            rot: (self.rot * other.rot).normalize(),
+            trans: self.trans + self.rot * other.trans,
+        }
+    }
+
+    pub fn inverse(self) -> Self {
+        let rot_inv = self.rot.inverse();
+        Self {
+            rot: rot_inv,
+            trans: -(rot_inv * self.trans),
+        }
+    }
+
+    pub fn transform_point(self, p: DVec3) -> DVec3 {
+        self.rot * p + self.trans
+    }
+}
+
+impl Mul for Pose {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        self.compose(rhs)
+    }
+}
+
+/// Tangent vector in the Lie Algebra se(3), in body coordinates.
+/// `omega` is the angular part and `v` the linear part.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Twist {
+    pub omega: DVec3,
+    pub v: DVec3,
+}
+
+impl Twist {
+    pub const ZERO: Self = Self {
+        omega: DVec3::ZERO,
+        v: DVec3::ZERO,
+    };
+
+    pub fn new(omega: DVec3, v: DVec3) -> Self {
+        Self { omega, v }
+    }
+
+    /// Packs as `[omega, v]`.
+    pub fn to_array(self) -> [f64; 6] {
+        [
+            self.omega.x,
+            self.omega.y,
+            self.omega.z,
+            self.v.x,
+            self.v.y,
+            self.v.z,
+        ]
+    }
+
+    pub fn from_array(a: [f64; 6]) -> Self {
+        Self {
+            omega: DVec3::new(a[0], a[1], a[2]),
+            v: DVec3::new(a[3], a[4], a[5]),
+        }
+    }
+}
+
+impl Add for Twist {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.omega + rhs.omega, self.v + rhs.v)
+    }
+}
+
+impl Sub for Twist {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.omega - rhs.omega, self.v - rhs.v)
+    }
+}
+
+impl Neg for Twist {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self::new(-self.omega, -self.v)
+    }
+}
+
+impl Mul<f64> for Twist {
+    type Output = Self;
+    fn mul(self, rhs: f64) -> Self {
+        Self::new(self.omega * rhs, self.v * rhs)
+    }
+}
+
+/// The Special Euclidean Group SE(3) of rigid-body poses.
+///
+/// Uses the right-trivialised convention of [`SO3`](super::SO3): `retract(T, ξ) = T * exp(ξ)`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SE3;
+
+impl SE3 {
+    /// Exponential map se(3) -> SE(3).
+    ///
+    /// The rotation is `exp(omega)` and the translation is `V(omega) v`, where
+    /// `V = I + (1 - cos θ)/θ² [ω]× + (θ - sin θ)/θ³ [ω]×²` couples the two.
+    pub fn exp(xi: Twist) -> Pose {
+        let rot = DQuat::from_scaled_axis(xi.omega);
+        Pose {
+            rot,
+            trans: Self::v_mul(xi.omega, xi.v),
+        }
+    }
+
+    /// Logarithmic map SE(3) -> se(3), the exact inverse of [`SE3::exp`]
+    /// for rotation angles below π.
+    pub fn log(pose: Pose) -> Twist {
+        // q and -q are the same rotation; pick the hemisphere with the shorter angle.
+        let rot = if pose.rot.w < 0.0 {
+            -pose.rot
+        } else {
+            pose.rot
+        };
+        let omega = rot.to_scaled_axis();
+        Twist {
+            omega,
+            v: Self::v_inv_mul(omega, pose.trans),
+        }
+    }
+
+    /// Adjoint action `Ad_T ξ`, so that `T * exp(ξ) * T⁻¹ = exp(Ad_T ξ)`.
+    pub fn adjoint(pose: Pose, xi: Twist) -> Twist {
+        let omega = pose.rot * xi.omega;
+        Twist {
+            omega,
+            v: pose.rot * xi.v + pose.trans.cross(omega),
+        }
+    }
+
+    /// The 6x6 adjoint matrix `[[R, 0], [t× R, R]]` acting on `[omega, v]`.
+    pub fn adjoint_matrix(pose: Pose) -> [[f64; 6]; 6] {
+        let mut m = [[0.0; 6]; 6];
+        for (col, e) in [DVec3::X, DVec3::Y, DVec3::Z].into_iter().enumerate() {
+            let ang = Self::adjoint(pose, Twist::new(e, DVec3::ZERO)).to_array();
+            let lin = Self::adjoint(pose, Twist::new(DVec3::ZERO, e)).to_array();
+            for row in 0..6 {
+                m[row][col] = ang[row];
+                m[row][col + 3] = lin[row];
+            }
+        }
+        m
+    }
+
+    /// Lie bracket `[a, b] = ad_a b` on se(3).
+    pub fn bracket(a: Twist, b: Twist) -> Twist {
+        Twist {
+            omega: a.omega.cross(b.omega),
+            v: a.omega.cross(b.v) - b.omega.cross(a.v),
+        }
+    }
+
+    /// Computes `V(omega) v`.
+    fn v_mul(omega: DVec3, v: DVec3) -> DVec3 {
+        let theta_sq = omega.length_squared();
+        let theta = theta_sq.sqrt();
+        let (b, c) = if theta < SMALL_ANGLE {
+            (0.5 - theta_sq / 24.0, 1.0 / 6.0 - theta_sq / 120.0)
+        } else {
+            (
+                (1.0 - theta.cos()) / theta_sq,
+                (theta - theta.sin()) / (theta_sq * theta),
+            )
+        };
+        let w_x_v = omega.cross(v);
+        v + w_x_v * b + omega.cross(w_x_v) * c
+    }
+
+    /// Computes `V(omega)⁻¹ t = t - ½ ω×t + D ω×(ω×t)`.
+    fn v_inv_mul(omega: DVec3, t: DVec3) -> DVec3 {
+        let theta_sq = omega.length_squared();
+        let theta = theta_sq.sqrt();
+        let d = if theta < SMALL_ANGLE {
+            1.0 / 12.0 + theta_sq / 720.0
+        } else {
+            let half = 0.5 * theta;
+            (1.0 - half * half.cos() / half.sin()) / theta_sq
+        };
+        let w_x_t = omega.cross(t);
+        t - w_x_t * 0.5 + omega.cross(w_x_t) * d
+    }
+}
+
+impl Manifold for SE3 {
+    type Point = Pose;
+    type Tangent = Twist;
+
+    fn dim() -> usize {
+        6
+    }
+
+    /// T_{new} = T * exp(ξ)
+    fn retract(p: Self::Point, v: Self::Tangent) -> Self::Point {
+        p.compose(Self::exp(v))
+    }
+
+    /// ξ = log(T_p^{-1} * T_q)
+    fn local(p: Self::Point, q: Self::Point) -> Self::Tangent {
+        Self::log(p.inverse().compose(q))
+    }
+}
+
+impl LieGroup for SE3 {
+    /// Homogeneous 4x4 form `[[ω]×, v; 0, 0]`.
+    type Algebra = DMat4;
+    /// Row-major 6x6 map on tangent coordinates ordered `[omega, v]`.
+    type Matrix = [[f64; 6]; 6];
+
+    fn identity() -> Self::Point {
+        Pose::IDENTITY
+    }
+
+    fn compose(a: Self::Point, b: Self::Point) -> Self::Point {
+        a.compose(b)
+    }
+
+    fn inverse(p: Self::Point) -> Self::Point {
+        p.inverse()
+    }
+
+    fn exp(v: Self::Tangent) -> Self::Point {
+        SE3::exp(v)
+    }
+
+    fn log(p: Self::Point) -> Self::Tangent {
+        SE3::log(p)
+    }
+
+    fn adjoint(p: Self::Point, v: Self::Tangent) -> Self::Tangent {
+        SE3::adjoint(p, v)
+    }
+
+    fn adjoint_matrix(p: Self::Point) -> Self::Matrix {
+        SE3::adjoint_matrix(p)
+    }
+
+    fn hat(v: Self::Tangent) -> Self::Algebra {
+        let w = SO3::hat(v.omega);
+        DMat4::from_cols(
+            w.x_axis.extend(0.0),
+            w.y_axis.extend(0.0),
+            w.z_axis.extend(0.0),
+            v.v.extend(0.0),
+        )
+    }
+
+    fn vee(m: Self::Algebra) -> Self::Tangent {
+        let w = DMat3::from_mat4(m);
+        Twist::new(SO3::vee(w), m.w_axis.truncate())
+    }
+
+    /// `J_r(ξ) = J_l(-ξ) = [[J, 0], [Q, J]]` evaluated at `-ξ`.
+    fn right_jacobian(v: Self::Tangent) -> Self::Matrix {
+        let (j, q) = Self::left_jacobian_blocks(-v);
+        block_matrix(j, DMat3::ZERO, q, j)
+    }
+
+    /// Block-triangular inverse `[[J⁻¹, 0], [-J⁻¹ Q J⁻¹, J⁻¹]]`.
+    fn right_jacobian_inv(v: Self::Tangent) -> Self::Matrix {
+        let (_, q) = Self::left_jacobian_blocks(-v);
+        let j_inv = SO3::left_jacobian_inv(-v.omega);
+        block_matrix(j_inv, DMat3::ZERO, -(j_inv * q * j_inv), j_inv)
+    }
+}
+
+impl SE3 {
+    /// The SO(3) left Jacobian `J_l(ω)` and the coupling block `Q(ω, v)` of the SE(3)
+    /// left Jacobian (Barfoot, *State Estimation for Robotics*, eq. 7.86).
+    fn left_jacobian_blocks(xi: Twist) -> (DMat3, DMat3) {
+        let theta_sq = xi.omega.length_squared();
+        let theta = theta_sq.sqrt();
+        let (c1, c2, c3) = if theta < SMALL_ANGLE {
+            (
+                1.0 / 6.0 - theta_sq / 120.0,
+                1.0 / 24.0 - theta_sq / 720.0,
+                1.0 / 120.0 - theta_sq / 2520.0,
+            )
+        } else {
+            let (s, c) = theta.sin_cos();
+            let theta_4 = theta_sq * theta_sq;
+            (
+                (theta - s) / (theta_sq * theta),
+                (theta_sq + 2.0 * c - 2.0) / (2.0 * theta_4),
+                (2.0 * theta - 3.0 * s + theta * c) / (2.0 * theta_4 * theta),
+            )
+        };
+
+        let w = SO3::hat(xi.omega);
+        let r = SO3::hat(xi.v);
+        let wr = w * r;
+        let rw = r * w;
+        let wrw = wr * w;
+        let q = r * 0.5
+            + (wr + rw + wrw) * c1
+            + (w * wr + rw * w - wrw * 3.0) * c2
+            + (wrw * w + w * wrw) * c3;
+
+        (SO3::left_jacobian(xi.omega), q)
+    }
+}
+
+/// Assembles `[[a, b], [c, d]]` into a row-major 6x6 matrix.
+fn block_matrix(a: DMat3, b: DMat3, c: DMat3, d: DMat3) -> [[f64; 6]; 6] {
+    let mut m = [[0.0; 6]; 6];
+    for row in 0..3 {
+        for col in 0..3 {
+            m[row][col] = a.col(col)[row];
+            m[row][col + 3] = b.col(col)[row];
+            m[row + 3][col] = c.col(col)[row];
+            m[row + 3][col + 3] = d.col(col)[row];
+        }
+    }
+    m
+}
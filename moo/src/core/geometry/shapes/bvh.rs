@@ -0,0 +1,167 @@
+use glam::DVec3;
+
+/// Primitives per leaf; splitting further costs more in traversal than it saves.
+const MAX_LEAF_SIZE: usize = 4;
+
+/// An axis-aligned bounding box.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Aabb {
+    pub min: DVec3,
+    pub max: DVec3,
+}
+
+impl Aabb {
+    /// The empty box: the identity for [`Aabb::union`].
+    pub const EMPTY: Self = Self {
+        min: DVec3::INFINITY,
+        max: DVec3::NEG_INFINITY,
+    };
+
+    pub fn new(min: DVec3, max: DVec3) -> Self {
+        Self { min, max }
+    }
+
+    /// The box around a sphere.
+    pub fn around(center: DVec3, radius: f64) -> Self {
+        Self::new(center - DVec3::splat(radius), center + DVec3::splat(radius))
+    }
+
+    pub fn from_points(points: impl IntoIterator<Item = DVec3>) -> Self {
+        points.into_iter().fold(Self::EMPTY, |b, p| b.grow(p))
+    }
+
+    pub fn grow(self, p: DVec3) -> Self {
+        Self::new(self.min.min(p), self.max.max(p))
+    }
+
+    pub fn union(self, other: Self) -> Self {
+        Self::new(self.min.min(other.min), self.max.max(other.max))
+    }
+
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.min.cmple(other.max).all() && other.min.cmple(self.max).all()
+    }
+
+    pub fn center(&self) -> DVec3 {
+        (self.min + self.max) * 0.5
+    }
+
+    pub fn extent(&self) -> DVec3 {
+        self.max - self.min
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Node {
+    /// Primitives `order[start..start + count]`.
+    Leaf {
+        bounds: Aabb,
+        start: usize,
+        count: usize,
+    },
+    /// The left child always directly follows its parent.
+    Internal { bounds: Aabb, right: usize },
+}
+
+impl Node {
+    fn bounds(&self) -> &Aabb {
+        match self {
+            Node::Leaf { bounds, .. } | Node::Internal { bounds, .. } => bounds,
+        }
+    }
+}
+
+/// A bounding-volume hierarchy over a fixed set of primitives, each given by its box.
+///
+/// Built top-down by splitting at the median centroid along the longest axis, and
+/// stored as a flat array in depth-first order.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Bvh {
+    nodes: Vec<Node>,
+    /// Primitive indices, permuted so that every leaf covers a contiguous range.
+    order: Vec<usize>,
+    /// Primitive boxes, in their original order.
+    boxes: Vec<Aabb>,
+}
+
+impl Bvh {
+    pub fn build(boxes: &[Aabb]) -> Self {
+        let mut bvh = Self {
+            nodes: Vec::with_capacity(2 * boxes.len() / MAX_LEAF_SIZE + 1),
+            order: (0..boxes.len()).collect(),
+            boxes: boxes.to_vec(),
+        };
+        if !boxes.is_empty() {
+            bvh.build_node(boxes, 0, boxes.len());
+        }
+        bvh
+    }
+
+    fn build_node(&mut self, boxes: &[Aabb], start: usize, end: usize) -> usize {
+        let range = &mut self.order[start..end];
+        let bounds = range.iter().fold(Aabb::EMPTY, |b, &i| b.union(boxes[i]));
+        let index = self.nodes.len();
+
+        if range.len() <= MAX_LEAF_SIZE {
+            self.nodes.push(Node::Leaf {
+                bounds,
+                start,
+                count: range.len(),
+            });
+            return index;
+        }
+
+        let centroids = Aabb::from_points(range.iter().map(|&i| boxes[i].center()));
+        let extent = centroids.extent();
+        let axis = if extent.x >= extent.y && extent.x >= extent.z {
+            0
+        } else if extent.y >= extent.z {
+            1
+        } else {
+            2
+        };
+        let mid = range.len() / 2;
+        range.select_nth_unstable_by(mid, |&a, &b| {
+            boxes[a].center()[axis].total_cmp(&boxes[b].center()[axis])
+        });
+
+        // Placeholder until the right child's index is known.
+        self.nodes.push(Node::Internal { bounds, right: 0 });
+        self.build_node(boxes, start, start + mid);
+        let right = self.build_node(boxes, start + mid, end);
+        self.nodes[index] = Node::Internal { bounds, right };
+        index
+    }
+
+    /// Box around everything, or `None` for an empty hierarchy.
+    pub fn bounds(&self) -> Option<Aabb> {
+        self.nodes.first().map(|n| *n.bounds())
+    }
+
+    /// Calls `visit` with every primitive whose box overlaps `query`.
+    pub fn query(&self, query: &Aabb, mut visit: impl FnMut(usize)) {
+        if self.nodes.is_empty() {
+            return;
+        }
+        let mut stack = vec![0];
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if !node.bounds().intersects(query) {
+                continue;
+            }
+            match *node {
+                Node::Leaf { start, count, .. } => {
+                    for &i in &self.order[start..start + count] {
+                        if self.boxes[i].intersects(query) {
+                            visit(i);
+                        }
+                    }
+                }
+                Node::Internal { right, .. } => {
+                    stack.push(right);
+                    stack.push(index + 1);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,179 @@
+use super::Contact;
+use super::gjk::SupportPoint;
+use glam::DVec3;
+
+const MAX_ITERATIONS: usize = 64;
+/// Converged when the support in the closest face's normal gains less than this.
+const TOLERANCE: f64 = 1e-9;
+
+#[derive(Debug, Clone, Copy)]
+struct Face {
+    vertices: [usize; 3],
+    /// Outward unit normal.
+    normal: DVec3,
+    /// Distance of the face plane from the origin.
+    distance: f64,
+}
+
+/// Expanding Polytope Algorithm: penetration depth and normal of two overlapping
+/// convex shapes, starting from the GJK simplex that encloses the origin.
+///
+/// The polytope inside `A - B` is grown towards the boundary face closest to the
+/// origin until the support mapping cannot push that face any further. Returns
+/// `None` if the Minkowski difference is flat (e.g. zero-thickness shapes).
+pub fn penetration(
+    support: impl Fn(DVec3) -> SupportPoint,
+    simplex: Vec<SupportPoint>,
+) -> Option<Contact> {
+    let mut vertices = blow_up(&support, simplex)?;
+    let interior = vertices.iter().map(|v| v.w).sum::<DVec3>() / vertices.len() as f64;
+
+    let mut faces: Vec<Face> = [[0, 1, 2], [0, 3, 1], [0, 2, 3], [1, 3, 2]]
+        .into_iter()
+        .filter_map(|f| make_face(&vertices, f, interior))
+        .collect();
+
+    for _ in 0..MAX_ITERATIONS {
+        let closest = *faces
+            .iter()
+            .min_by(|a, b| a.distance.total_cmp(&b.distance))?;
+
+        let s = support(closest.normal);
+        if s.w.dot(closest.normal) - closest.distance < TOLERANCE {
+            return Some(contact_from_face(&vertices, &closest));
+        }
+
+        // Remove every face the new vertex can see and re-triangulate the hole.
+        let new_index = vertices.len();
+        vertices.push(s);
+        let mut horizon: Vec<(usize, usize)> = Vec::new();
+        faces.retain(|face| {
+            let visible = face.normal.dot(s.w - vertices[face.vertices[0]].w) > 0.0;
+            if visible {
+                let [a, b, c] = face.vertices;
+                for edge in [(a, b), (b, c), (c, a)] {
+                    // An edge shared by two visible faces is interior to the hole.
+                    if let Some(i) = horizon.iter().position(|&e| e == (edge.1, edge.0)) {
+                        horizon.swap_remove(i);
+                    } else {
+                        horizon.push(edge);
+                    }
+                }
+            }
+            !visible
+        });
+        for (a, b) in horizon {
+            if let Some(face) = make_face(&vertices, [a, b, new_index], interior) {
+                faces.push(face);
+            }
+        }
+        if faces.is_empty() {
+            return None;
+        }
+    }
+
+    // Out of iterations: the closest face is still a good approximation.
+    let closest = faces
+        .iter()
+        .min_by(|a, b| a.distance.total_cmp(&b.distance))?;
+    Some(contact_from_face(&vertices, closest))
+}
+
+/// Builds a face oriented away from `interior`, or `None` if it is degenerate.
+fn make_face(vertices: &[SupportPoint], indices: [usize; 3], interior: DVec3) -> Option<Face> {
+    let [a, b, c] = indices.map(|i| vertices[i].w);
+    let n = (b - a).cross(c - a);
+    let len = n.length();
+    if len < 1e-14 {
+        return None;
+    }
+    let mut normal = n / len;
+    let mut vertices = indices;
+    if normal.dot(a - interior) < 0.0 {
+        normal = -normal;
+        vertices.swap(1, 2);
+    }
+    Some(Face {
+        vertices,
+        normal,
+        distance: normal.dot(a),
+    })
+}
+
+fn contact_from_face(vertices: &[SupportPoint], face: &Face) -> Contact {
+    let [a, b, c] = face.vertices.map(|i| vertices[i]);
+    let p = face.normal * face.distance;
+
+    // Barycentric coordinates of the origin's projection onto the face.
+    let (v0, v1, v2) = (b.w - a.w, c.w - a.w, p - a.w);
+    let (d00, d01, d11) = (v0.dot(v0), v0.dot(v1), v1.dot(v1));
+    let (d20, d21) = (v2.dot(v0), v2.dot(v1));
+    let denom = d00 * d11 - d01 * d01;
+    let (u, v, w) = if denom.abs() > 0.0 {
+        let v = (d11 * d20 - d01 * d21) / denom;
+        let w = (d00 * d21 - d01 * d20) / denom;
+        (1.0 - v - w, v, w)
+    } else {
+        (1.0, 0.0, 0.0)
+    };
+
+    Contact {
+        normal: face.normal,
+        depth: face.distance.max(0.0),
+        point_a: a.a * u + b.a * v + c.a * w,
+        point_b: a.b * u + b.b * v + c.b * w,
+    }
+}
+
+/// Grows a GJK simplex with fewer than four vertices into a tetrahedron that still
+/// contains the origin (the origin was on a vertex, edge or face of `A - B`).
+fn blow_up(
+    support: &impl Fn(DVec3) -> SupportPoint,
+    mut simplex: Vec<SupportPoint>,
+) -> Option<Vec<SupportPoint>> {
+    const EPS: f64 = 1e-10;
+    let axes = [DVec3::X, DVec3::Y, DVec3::Z];
+
+    if simplex.is_empty() {
+        simplex.push(support(DVec3::X));
+    }
+
+    if simplex.len() == 1 {
+        let a = simplex[0].w;
+        let next = axes
+            .iter()
+            .flat_map(|&d| [d, -d])
+            .map(support)
+            .find(|s| (s.w - a).length() > EPS)?;
+        simplex.push(next);
+    }
+
+    if simplex.len() == 2 {
+        let (a, b) = (simplex[0].w, simplex[1].w);
+        let axis = (b - a).normalize();
+        let mut dir = axis.any_orthonormal_vector();
+        let rotation = glam::DQuat::from_axis_angle(axis, std::f64::consts::FRAC_PI_3);
+        let mut found = None;
+        for _ in 0..6 {
+            let s = support(dir);
+            if (s.w - a).cross(axis).length() > EPS {
+                found = Some(s);
+                break;
+            }
+            dir = rotation * dir;
+        }
+        simplex.push(found?);
+    }
+
+    if simplex.len() == 3 {
+        let (a, b, c) = (simplex[0].w, simplex[1].w, simplex[2].w);
+        let n = (b - a).cross(c - a).normalize_or_zero();
+        let s = [n, -n]
+            .into_iter()
+            .map(support)
+            .find(|s| (s.w - a).dot(n).abs() > EPS)?;
+        simplex.push(s);
+    }
+
+    Some(simplex)
+}
@@ -0,0 +1,321 @@
+use super::SupportMap;
+use crate::core::geometry::se3::Pose;
+use glam::DVec3;
+
+const MAX_ITERATIONS: usize = 64;
+/// Stop when an iteration improves the squared distance by less than this fraction.
+const RELATIVE_TOLERANCE: f64 = 1e-12;
+/// Squared distance below which the origin is considered inside `A - B`.
+const INTERSECTION_EPSILON_SQ: f64 = 1e-20;
+
+/// A vertex of the Minkowski difference together with the points of A and B it came from.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SupportPoint {
+    /// `a - b`
+    pub w: DVec3,
+    pub a: DVec3,
+    pub b: DVec3,
+}
+
+impl SupportPoint {
+    /// Support point of `A - B` in world direction `dir`, using either the cores
+    /// (`with_margin == false`) or the full shapes.
+    pub fn new(
+        a: &dyn SupportMap,
+        pose_a: Pose,
+        b: &dyn SupportMap,
+        pose_b: Pose,
+        dir: DVec3,
+        with_margin: bool,
+    ) -> Self {
+        let local_a = pose_a.rot.inverse() * dir;
+        let local_b = pose_b.rot.inverse() * -dir;
+        let (sa, sb) = if with_margin {
+            (a.support(local_a), b.support(local_b))
+        } else {
+            (a.support_core(local_a), b.support_core(local_b))
+        };
+        let a = pose_a.transform_point(sa);
+        let b = pose_b.transform_point(sb);
+        Self { w: a - b, a, b }
+    }
+}
+
+/// Outcome of a GJK run.
+#[derive(Debug, Clone)]
+pub struct GjkResult {
+    /// Whether the origin is inside `A - B` (the shapes overlap).
+    pub intersecting: bool,
+    /// Point of `A - B` closest to the origin (zero when intersecting).
+    pub closest: DVec3,
+    /// Witness points on A and B with `point_a - point_b = closest`.
+    pub point_a: DVec3,
+    pub point_b: DVec3,
+    /// Final simplex; a tetrahedron enclosing the origin when intersecting in 3D.
+    pub simplex: Vec<SupportPoint>,
+}
+
+/// Separation between two convex shapes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Proximity {
+    /// Euclidean distance between the shapes, zero if they overlap.
+    pub distance: f64,
+    /// Closest point on A (world frame).
+    pub point_a: DVec3,
+    /// Closest point on B (world frame).
+    pub point_b: DVec3,
+}
+
+/// Closest points between two posed convex shapes (including their margins).
+pub fn closest_points(
+    a: &dyn SupportMap,
+    pose_a: Pose,
+    b: &dyn SupportMap,
+    pose_b: Pose,
+) -> Proximity {
+    let core = gjk(
+        |dir| SupportPoint::new(a, pose_a, b, pose_b, dir, false),
+        pose_b.trans - pose_a.trans,
+    );
+    let (margin_a, margin_b) = (a.margin(), b.margin());
+    let core_distance = core.closest.length();
+    if core.intersecting || core_distance <= margin_a + margin_b {
+        let mid = (core.point_a + core.point_b) * 0.5;
+        return Proximity {
+            distance: 0.0,
+            point_a: mid,
+            point_b: mid,
+        };
+    }
+    let dir = -core.closest / core_distance; // From A to B
+    Proximity {
+        distance: core_distance - margin_a - margin_b,
+        point_a: core.point_a + dir * margin_a,
+        point_b: core.point_b - dir * margin_b,
+    }
+}
+
+/// Gilbert–Johnson–Keerthi distance on the Minkowski difference `A - B` described by `support`.
+///
+/// Grows a simplex (point, segment, triangle, tetrahedron) of support points towards
+/// the origin; the shapes intersect iff the origin lies inside `A - B`.
+/// `initial_dir` is a hint (e.g. the offset between the shape centers).
+pub fn gjk(support: impl Fn(DVec3) -> SupportPoint, initial_dir: DVec3) -> GjkResult {
+    let dir = if initial_dir.length_squared() > 0.0 {
+        initial_dir
+    } else {
+        DVec3::X
+    };
+
+    let first = support(dir);
+    let mut simplex = vec![(first, 1.0)];
+    let mut v = first.w;
+
+    for _ in 0..MAX_ITERATIONS {
+        let v_sq = v.length_squared();
+        if v_sq <= INTERSECTION_EPSILON_SQ {
+            return finish(simplex, true);
+        }
+
+        let s = support(-v);
+        // No more progress towards the origin: v is the closest point.
+        if v_sq - v.dot(s.w) <= RELATIVE_TOLERANCE * v_sq {
+            break;
+        }
+        if simplex.iter().any(|(p, _)| p.w == s.w) {
+            break;
+        }
+
+        let mut points: Vec<SupportPoint> = simplex.iter().map(|(p, _)| *p).collect();
+        points.push(s);
+        let (closest, reduced) = closest_on_simplex(&points);
+        v = closest;
+        simplex = reduced;
+
+        if simplex.len() == 4 {
+            return finish(simplex, true);
+        }
+    }
+
+    finish(simplex, false)
+}
+
+fn finish(simplex: Vec<(SupportPoint, f64)>, intersecting: bool) -> GjkResult {
+    let mut point_a = DVec3::ZERO;
+    let mut point_b = DVec3::ZERO;
+    for (p, weight) in &simplex {
+        point_a += p.a * *weight;
+        point_b += p.b * *weight;
+    }
+    GjkResult {
+        intersecting,
+        closest: if intersecting {
+            DVec3::ZERO
+        } else {
+            point_a - point_b
+        },
+        point_a,
+        point_b,
+        simplex: simplex.into_iter().map(|(p, _)| p).collect(),
+    }
+}
+
+/// Closest point to the origin on a simplex, and the smallest sub-simplex that
+/// contains it with its barycentric weights.
+fn closest_on_simplex(points: &[SupportPoint]) -> (DVec3, Vec<(SupportPoint, f64)>) {
+    match points {
+        [a] => (a.w, vec![(*a, 1.0)]),
+        [a, b] => closest_on_segment(*a, *b),
+        [a, b, c] => closest_on_face(*a, *b, *c),
+        [a, b, c, d] => closest_on_tetrahedron(*a, *b, *c, *d),
+        _ => unreachable!("GJK simplex has at most 4 vertices"),
+    }
+}
+
+fn closest_on_segment(a: SupportPoint, b: SupportPoint) -> (DVec3, Vec<(SupportPoint, f64)>) {
+    let ab = b.w - a.w;
+    let denom = ab.length_squared();
+    let t = if denom > 0.0 {
+        (-a.w.dot(ab) / denom).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    if t <= 0.0 {
+        (a.w, vec![(a, 1.0)])
+    } else if t >= 1.0 {
+        (b.w, vec![(b, 1.0)])
+    } else {
+        (a.w + ab * t, vec![(a, 1.0 - t), (b, t)])
+    }
+}
+
+/// Closest point to the origin on the triangle of three support points.
+fn closest_on_face(
+    a: SupportPoint,
+    b: SupportPoint,
+    c: SupportPoint,
+) -> (DVec3, Vec<(SupportPoint, f64)>) {
+    let (point, weights) = closest_on_triangle(DVec3::ZERO, a.w, b.w, c.w);
+    let simplex = [a, b, c]
+        .into_iter()
+        .zip(weights)
+        .filter_map(|(p, w)| Some((p, w?)))
+        .collect();
+    (point, simplex)
+}
+
+/// Closest point to `p` on triangle `abc` by Voronoi-region search (Ericson,
+/// *Real-Time Collision Detection*, 5.1.5), with the barycentric weight of each
+/// vertex of the feature it lies on (`None` for the other vertices).
+pub(crate) fn closest_on_triangle(
+    p: DVec3,
+    a: DVec3,
+    b: DVec3,
+    c: DVec3,
+) -> (DVec3, [Option<f64>; 3]) {
+    let ab = b - a;
+    let ac = c - a;
+
+    let ap = p - a;
+    let d1 = ab.dot(ap);
+    let d2 = ac.dot(ap);
+    if d1 <= 0.0 && d2 <= 0.0 {
+        return (a, [Some(1.0), None, None]);
+    }
+
+    let bp = p - b;
+    let d3 = ab.dot(bp);
+    let d4 = ac.dot(bp);
+    if d3 >= 0.0 && d4 <= d3 {
+        return (b, [None, Some(1.0), None]);
+    }
+
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+        let t = d1 / (d1 - d3);
+        return (a + ab * t, [Some(1.0 - t), Some(t), None]);
+    }
+
+    let cp = p - c;
+    let d5 = ab.dot(cp);
+    let d6 = ac.dot(cp);
+    if d6 >= 0.0 && d5 <= d6 {
+        return (c, [None, None, Some(1.0)]);
+    }
+
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+        let t = d2 / (d2 - d6);
+        return (a + ac * t, [Some(1.0 - t), None, Some(t)]);
+    }
+
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0.0 && (d4 - d3) >= 0.0 && (d5 - d6) >= 0.0 {
+        let t = (d4 - d3) / ((d4 - d3) + (d5 - d6));
+        return (b + (c - b) * t, [None, Some(1.0 - t), Some(t)]);
+    }
+
+    let denom = 1.0 / (va + vb + vc);
+    let v = vb * denom;
+    let w = vc * denom;
+    (a + ab * v + ac * w, [Some(1.0 - v - w), Some(v), Some(w)])
+}
+
+fn closest_on_tetrahedron(
+    a: SupportPoint,
+    b: SupportPoint,
+    c: SupportPoint,
+    d: SupportPoint,
+) -> (DVec3, Vec<(SupportPoint, f64)>) {
+    let faces = [(a, b, c, d), (a, c, d, b), (a, d, b, c), (b, d, c, a)];
+
+    let mut best: Option<(DVec3, Vec<(SupportPoint, f64)>)> = None;
+    for (p, q, r, opposite) in faces {
+        if origin_outside_face(p.w, q.w, r.w, opposite.w) {
+            let candidate = closest_on_face(p, q, r);
+            let better = best
+                .as_ref()
+                .is_none_or(|(x, _)| candidate.0.length_squared() < x.length_squared());
+            if better {
+                best = Some(candidate);
+            }
+        }
+    }
+
+    best.unwrap_or_else(|| {
+        // Origin enclosed: barycentric weights from signed sub-volumes.
+        let volume = signed_volume(a.w, b.w, c.w, d.w);
+        let weights = [
+            signed_volume(DVec3::ZERO, b.w, c.w, d.w) / volume,
+            signed_volume(a.w, DVec3::ZERO, c.w, d.w) / volume,
+            signed_volume(a.w, b.w, DVec3::ZERO, d.w) / volume,
+            signed_volume(a.w, b.w, c.w, DVec3::ZERO) / volume,
+        ];
+        (
+            DVec3::ZERO,
+            vec![
+                (a, weights[0]),
+                (b, weights[1]),
+                (c, weights[2]),
+                (d, weights[3]),
+            ],
+        )
+    })
+}
+
+/// Whether the origin and `opposite` lie on different sides of the plane `(a, b, c)`.
+/// A degenerate (flat) tetrahedron counts as "outside" so the face is still searched.
+fn origin_outside_face(a: DVec3, b: DVec3, c: DVec3, opposite: DVec3) -> bool {
+    let n = (b - a).cross(c - a);
+    let side_origin = (-a).dot(n);
+    let side_opposite = (opposite - a).dot(n);
+    let scale = n.length() * (opposite - a).length();
+    if side_opposite.abs() <= 1e-12 * scale {
+        return true;
+    }
+    side_origin * side_opposite < 0.0
+}
+
+fn signed_volume(a: DVec3, b: DVec3, c: DVec3, d: DVec3) -> f64 {
+    (b - a).dot((c - a).cross(d - a))
+}
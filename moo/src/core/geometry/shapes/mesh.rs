@@ -0,0 +1,230 @@
+use super::Contact;
+use super::bvh::{Aabb, Bvh};
+use super::gjk::closest_on_triangle;
+use crate::core::geometry::se3::Pose;
+use glam::DVec3;
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+
+/// A static triangle soup with a BVH over its triangles.
+///
+/// Meshes are treated as two-sided shells: they need not be closed or consistently
+/// wound, and a sphere is pushed out on whichever side its center lies.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TriMesh {
+    pub vertices: Vec<DVec3>,
+    pub triangles: Vec<[usize; 3]>,
+    bvh: Bvh,
+}
+
+impl TriMesh {
+    /// Builds the hierarchy. Panics if a triangle refers to a missing vertex.
+    pub fn new(vertices: Vec<DVec3>, triangles: Vec<[usize; 3]>) -> Self {
+        let boxes: Vec<Aabb> = triangles
+            .iter()
+            .map(|t| Aabb::from_points(t.map(|i| vertices[i])))
+            .collect();
+        let bvh = Bvh::build(&boxes);
+        Self {
+            vertices,
+            triangles,
+            bvh,
+        }
+    }
+
+    /// Loads an `.obj` or `.stl` file, chosen by extension.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_ascii_lowercase);
+        match extension.as_deref() {
+            Some("obj") => Self::from_obj(&std::fs::read_to_string(path)?),
+            Some("stl") => Self::from_stl(&std::fs::read(path)?),
+            _ => Err(invalid(format!(
+                "unsupported mesh format: {}",
+                path.display()
+            ))),
+        }
+    }
+
+    /// Parses Wavefront OBJ text. Only `v` and `f` records are used; polygons are
+    /// fan-triangulated and negative (relative) indices are supported.
+    pub fn from_obj(text: &str) -> io::Result<Self> {
+        let mut vertices = Vec::new();
+        let mut triangles = Vec::new();
+
+        for (line_no, line) in text.lines().enumerate() {
+            let mut tokens = line.split_whitespace();
+            match tokens.next() {
+                Some("v") => {
+                    let mut coords = [0.0; 3];
+                    for c in &mut coords {
+                        *c = tokens
+                            .next()
+                            .and_then(|t| t.parse().ok())
+                            .ok_or_else(|| invalid(format!("line {}: bad vertex", line_no + 1)))?;
+                    }
+                    vertices.push(DVec3::from_array(coords));
+                }
+                Some("f") => {
+                    let face = tokens
+                        .map(|t| obj_index(t, vertices.len()))
+                        .collect::<Option<Vec<usize>>>()
+                        .filter(|f| f.len() >= 3)
+                        .ok_or_else(|| invalid(format!("line {}: bad face", line_no + 1)))?;
+                    for k in 1..face.len() - 1 {
+                        triangles.push([face[0], face[k], face[k + 1]]);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(Self::new(vertices, triangles))
+    }
+
+    /// Parses binary or ASCII STL. Coincident vertices are welded.
+    pub fn from_stl(bytes: &[u8]) -> io::Result<Self> {
+        // Binary files may also start with "solid", so trust the size check first.
+        let corners = if is_binary_stl(bytes) {
+            binary_stl_corners(bytes)
+        } else {
+            let text = std::str::from_utf8(bytes)
+                .map_err(|_| invalid("STL is neither binary nor text"))?;
+            ascii_stl_corners(text)?
+        };
+
+        let mut welded: HashMap<[u64; 3], usize> = HashMap::new();
+        let mut vertices = Vec::new();
+        let indices: Vec<usize> = corners
+            .iter()
+            .map(|p| {
+                *welded
+                    .entry(p.to_array().map(f64::to_bits))
+                    .or_insert_with(|| {
+                        vertices.push(*p);
+                        vertices.len() - 1
+                    })
+            })
+            .collect();
+        let triangles = indices
+            .chunks_exact(3)
+            .map(|t| [t[0], t[1], t[2]])
+            .collect();
+
+        Ok(Self::new(vertices, triangles))
+    }
+
+    /// Box around the whole mesh (local frame).
+    pub fn bounds(&self) -> Option<Aabb> {
+        self.bvh.bounds()
+    }
+
+    /// Closest point on the mesh to `p` among triangles whose boxes overlap
+    /// `within`, with the index of its triangle.
+    pub fn closest_point(&self, p: DVec3, within: &Aabb) -> Option<(DVec3, usize)> {
+        let mut best: Option<(DVec3, usize, f64)> = None;
+        self.bvh.query(within, |t| {
+            let [a, b, c] = self.triangles[t].map(|i| self.vertices[i]);
+            let (q, _) = closest_on_triangle(p, a, b, c);
+            let d = q.distance_squared(p);
+            if best.is_none_or(|(_, _, bd)| d < bd) {
+                best = Some((q, t, d));
+            }
+        });
+        best.map(|(q, t, _)| (q, t))
+    }
+
+    /// Contact between the posed mesh (A) and a sphere (B), or `None` if they are separated.
+    pub fn sphere_contact(&self, pose: Pose, center: DVec3, radius: f64) -> Option<Contact> {
+        let local = pose.inverse().transform_point(center);
+        let (closest, triangle) = self.closest_point(local, &Aabb::around(local, radius))?;
+
+        let offset = local - closest;
+        let dist = offset.length();
+        if dist > radius {
+            return None;
+        }
+        let normal = if dist > 1e-12 {
+            offset / dist
+        } else {
+            // Center on the surface: fall back to the face normal.
+            let [a, b, c] = self.triangles[triangle].map(|i| self.vertices[i]);
+            (b - a).cross(c - a).normalize_or(DVec3::Y)
+        };
+
+        let normal = pose.rot * normal;
+        let point_a = pose.transform_point(closest);
+        Some(Contact {
+            normal,
+            depth: radius - dist,
+            point_a,
+            point_b: point_a - normal * (radius - dist),
+        })
+    }
+}
+
+fn invalid(message: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.into())
+}
+
+/// Resolves an OBJ index token (`7`, `7/1`, `7//3`, `-1`) to a zero-based vertex index.
+fn obj_index(token: &str, vertex_count: usize) -> Option<usize> {
+    let index: i64 = token.split('/').next()?.parse().ok()?;
+    let resolved = match index {
+        i if i > 0 => i as usize - 1,
+        i if i < 0 => vertex_count.checked_sub(i.unsigned_abs() as usize)?,
+        _ => return None,
+    };
+    (resolved < vertex_count).then_some(resolved)
+}
+
+const STL_HEADER: usize = 80;
+const STL_RECORD: usize = 50;
+
+fn is_binary_stl(bytes: &[u8]) -> bool {
+    if bytes.len() < STL_HEADER + 4 {
+        return false;
+    }
+    let count = u32::from_le_bytes(bytes[STL_HEADER..STL_HEADER + 4].try_into().unwrap());
+    bytes.len() == STL_HEADER + 4 + count as usize * STL_RECORD
+}
+
+fn binary_stl_corners(bytes: &[u8]) -> Vec<DVec3> {
+    let read = |r: &[u8], at: usize| f32::from_le_bytes(r[at..at + 4].try_into().unwrap()) as f64;
+    bytes[STL_HEADER + 4..]
+        .chunks_exact(STL_RECORD)
+        .flat_map(|record| {
+            // Skip the 12-byte facet normal; normals are recomputed when needed.
+            (1..4).map(move |k| {
+                let at = k * 12;
+                DVec3::new(read(record, at), read(record, at + 4), read(record, at + 8))
+            })
+        })
+        .collect()
+}
+
+fn ascii_stl_corners(text: &str) -> io::Result<Vec<DVec3>> {
+    let mut corners = Vec::new();
+    let mut tokens = text.split_whitespace();
+    while let Some(token) = tokens.next() {
+        if token != "vertex" {
+            continue;
+        }
+        let mut coords = [0.0; 3];
+        for c in &mut coords {
+            *c = tokens
+                .next()
+                .and_then(|t| t.parse().ok())
+                .ok_or_else(|| invalid("bad STL vertex"))?;
+        }
+        corners.push(DVec3::from_array(coords));
+    }
+    if corners.len() % 3 != 0 {
+        return Err(invalid("STL facet without three vertices"));
+    }
+    Ok(corners)
+}
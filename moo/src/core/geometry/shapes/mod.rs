@@ -0,0 +1,298 @@
+use crate::core::geometry::se3::Pose;
+use glam::DVec3;
+
+pub mod bvh;
+pub mod epa;
+pub mod gjk;
+pub mod mesh;
+pub mod sdf;
+
+pub use bvh::{Aabb, Bvh};
+pub use gjk::{Proximity, closest_points};
+pub use mesh::TriMesh;
+pub use sdf::{Sdf, VoxelSdf};
+
+/// A convex shape described by its support mapping, in its local frame.
+///
+/// Shapes are split into a *core* and a *margin*: the shape is the set of points
+/// within `margin` of the core. Spheres (a point) and capsules (a segment) are
+/// represented exactly that way, so narrow-phase queries stay exact for them.
+pub trait SupportMap {
+    /// Furthest point of the core in direction `dir`.
+    fn support_core(&self, dir: DVec3) -> DVec3;
+
+    /// Radius swept around the core. Zero for polytopes and cylinders.
+    fn margin(&self) -> f64 {
+        0.0
+    }
+
+    /// Furthest point of the full shape (core plus margin) in direction `dir`.
+    fn support(&self, dir: DVec3) -> DVec3 {
+        self.support_core(dir) + dir.normalize_or_zero() * self.margin()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sphere {
+    pub radius: f64,
+}
+
+impl Sphere {
+    pub fn new(radius: f64) -> Self {
+        Self { radius }
+    }
+}
+
+impl SupportMap for Sphere {
+    fn support_core(&self, _dir: DVec3) -> DVec3 {
+        DVec3::ZERO
+    }
+
+    fn margin(&self) -> f64 {
+        self.radius
+    }
+}
+
+/// An axis-aligned box in its local frame, centered at the origin.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cuboid {
+    pub half_extents: DVec3,
+}
+
+impl Cuboid {
+    pub fn new(half_extents: DVec3) -> Self {
+        Self { half_extents }
+    }
+}
+
+impl SupportMap for Cuboid {
+    fn support_core(&self, dir: DVec3) -> DVec3 {
+        let h = self.half_extents;
+        DVec3::new(
+            if dir.x >= 0.0 { h.x } else { -h.x },
+            if dir.y >= 0.0 { h.y } else { -h.y },
+            if dir.z >= 0.0 { h.z } else { -h.z },
+        )
+    }
+}
+
+/// A segment from `-half_height` to `+half_height` along local Y, swept by `radius`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Capsule {
+    pub half_height: f64,
+    pub radius: f64,
+}
+
+impl Capsule {
+    pub fn new(half_height: f64, radius: f64) -> Self {
+        Self {
+            half_height,
+            radius,
+        }
+    }
+}
+
+impl SupportMap for Capsule {
+    fn support_core(&self, dir: DVec3) -> DVec3 {
+        let y = if dir.y >= 0.0 {
+            self.half_height
+        } else {
+            -self.half_height
+        };
+        DVec3::new(0.0, y, 0.0)
+    }
+
+    fn margin(&self) -> f64 {
+        self.radius
+    }
+}
+
+/// A solid cylinder with its axis along local Y.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cylinder {
+    pub half_height: f64,
+    pub radius: f64,
+}
+
+impl Cylinder {
+    pub fn new(half_height: f64, radius: f64) -> Self {
+        Self {
+            half_height,
+            radius,
+        }
+    }
+}
+
+impl SupportMap for Cylinder {
+    fn support_core(&self, dir: DVec3) -> DVec3 {
+        let y = if dir.y >= 0.0 {
+            self.half_height
+        } else {
+            -self.half_height
+        };
+        let radial = DVec3::new(dir.x, 0.0, dir.z).normalize_or_zero() * self.radius;
+        radial + DVec3::new(0.0, y, 0.0)
+    }
+}
+
+/// The convex hull of a point cloud. Points need not be hull vertices.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ConvexHull {
+    pub points: Vec<DVec3>,
+}
+
+impl ConvexHull {
+    pub fn new(points: Vec<DVec3>) -> Self {
+        Self { points }
+    }
+}
+
+impl SupportMap for ConvexHull {
+    fn support_core(&self, dir: DVec3) -> DVec3 {
+        self.points
+            .iter()
+            .copied()
+            .max_by(|a, b| a.dot(dir).total_cmp(&b.dot(dir)))
+            .unwrap_or(DVec3::ZERO)
+    }
+}
+
+/// The solid half-space `normal · x <= offset` (the plane is its boundary).
+/// Not bounded, so it has no support mapping and gets its own contact routine.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Plane {
+    /// Unit outward normal.
+    pub normal: DVec3,
+    pub offset: f64,
+}
+
+impl Plane {
+    pub fn new(normal: DVec3, offset: f64) -> Self {
+        Self {
+            normal: normal.normalize(),
+            offset,
+        }
+    }
+}
+
+/// Any collision shape.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Shape {
+    Sphere(Sphere),
+    Cuboid(Cuboid),
+    Capsule(Capsule),
+    Cylinder(Cylinder),
+    ConvexHull(ConvexHull),
+    Plane(Plane),
+}
+
+impl Shape {
+    /// The support mapping, for every shape except [`Plane`].
+    pub fn as_convex(&self) -> Option<&dyn SupportMap> {
+        match self {
+            Shape::Sphere(s) => Some(s),
+            Shape::Cuboid(s) => Some(s),
+            Shape::Capsule(s) => Some(s),
+            Shape::Cylinder(s) => Some(s),
+            Shape::ConvexHull(s) => Some(s),
+            Shape::Plane(_) => None,
+        }
+    }
+}
+
+/// A contact between shape A and shape B.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Contact {
+    /// Unit normal pointing from A to B: moving B by `normal * depth` separates them.
+    pub normal: DVec3,
+    /// Penetration depth (zero when just touching).
+    pub depth: f64,
+    /// Witness point on the surface of A (world frame).
+    pub point_a: DVec3,
+    /// Witness point on the surface of B, with `point_a - point_b = normal * depth`.
+    pub point_b: DVec3,
+}
+
+impl Contact {
+    /// The same contact seen from B.
+    pub fn flipped(self) -> Self {
+        Self {
+            normal: -self.normal,
+            depth: self.depth,
+            point_a: self.point_b,
+            point_b: self.point_a,
+        }
+    }
+}
+
+/// Narrow-phase contact between two posed shapes, or `None` if they are separated.
+///
+/// Convex pairs use GJK on the cores; only when the cores overlap does EPA run on
+/// the full shapes. Planes are tested against the support point of the other shape.
+pub fn contact(a: &Shape, pose_a: Pose, b: &Shape, pose_b: Pose) -> Option<Contact> {
+    match (a, b) {
+        (Shape::Plane(_), Shape::Plane(_)) => None,
+        (Shape::Plane(plane), _) => plane_contact(plane, pose_a, b.as_convex()?, pose_b),
+        (_, Shape::Plane(plane)) => {
+            plane_contact(plane, pose_b, a.as_convex()?, pose_a).map(Contact::flipped)
+        }
+        _ => convex_contact(a.as_convex()?, pose_a, b.as_convex()?, pose_b),
+    }
+}
+
+/// Contact between two convex shapes.
+pub fn convex_contact(
+    a: &dyn SupportMap,
+    pose_a: Pose,
+    b: &dyn SupportMap,
+    pose_b: Pose,
+) -> Option<Contact> {
+    let (margin_a, margin_b) = (a.margin(), b.margin());
+    let margins = margin_a + margin_b;
+
+    let core = gjk::gjk(
+        |dir| gjk::SupportPoint::new(a, pose_a, b, pose_b, dir, false),
+        pose_b.trans - pose_a.trans,
+    );
+
+    if !core.intersecting {
+        let dist = core.closest.length();
+        if dist > margins {
+            return None;
+        }
+        let normal = -core.closest / dist;
+        return Some(Contact {
+            normal,
+            depth: margins - dist,
+            point_a: core.point_a + normal * margin_a,
+            point_b: core.point_b - normal * margin_b,
+        });
+    }
+
+    epa::penetration(
+        |dir| gjk::SupportPoint::new(a, pose_a, b, pose_b, dir, true),
+        core.simplex,
+    )
+}
+
+fn plane_contact(
+    plane: &Plane,
+    plane_pose: Pose,
+    shape: &dyn SupportMap,
+    pose: Pose,
+) -> Option<Contact> {
+    let normal = plane_pose.rot * plane.normal;
+    let offset = plane.offset + normal.dot(plane_pose.trans);
+
+    let deepest = pose.transform_point(shape.support(pose.rot.inverse() * -normal));
+    let depth = offset - normal.dot(deepest);
+    if depth < 0.0 {
+        return None;
+    }
+    Some(Contact {
+        normal,
+        depth,
+        point_a: deepest + normal * depth,
+        point_b: deepest,
+    })
+}
@@ -0,0 +1,305 @@
+use super::bvh::Aabb;
+use super::mesh::TriMesh;
+use crate::core::geometry::se3::Pose;
+use glam::{DVec3, IVec3};
+use std::f64::consts::PI;
+
+/// Step of the central differences in [`Sdf::gradient`].
+const GRADIENT_STEP: f64 = 1e-6;
+
+/// A signed distance field: negative inside the solid, positive outside.
+///
+/// Primitives are centered at the origin of their local frame (like [`super::Shape`])
+/// and placed with [`Sdf::transformed`]. CSG combinations are exact outside the
+/// solid but only a bound on the distance inside, which is all collision needs.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Sdf {
+    Sphere {
+        radius: f64,
+    },
+    Cuboid {
+        half_extents: DVec3,
+    },
+    /// Segment along local Y, swept by `radius`.
+    Capsule {
+        half_height: f64,
+        radius: f64,
+    },
+    /// Axis along local Y.
+    Cylinder {
+        half_height: f64,
+        radius: f64,
+    },
+    /// The solid half-space `normal · x <= offset`.
+    HalfSpace {
+        normal: DVec3,
+        offset: f64,
+    },
+    Union(Box<Sdf>, Box<Sdf>),
+    Intersection(Box<Sdf>, Box<Sdf>),
+    /// The first solid minus the second.
+    Difference(Box<Sdf>, Box<Sdf>),
+    /// Union blended over a distance `k` (polynomial smooth minimum).
+    SmoothUnion(Box<Sdf>, Box<Sdf>, f64),
+    /// Swaps inside and outside: turns a solid into a container.
+    Inverted(Box<Sdf>),
+    Transformed(Box<Sdf>, Pose),
+    Grid(VoxelSdf),
+}
+
+impl Sdf {
+    pub fn sphere(radius: f64) -> Self {
+        Sdf::Sphere { radius }
+    }
+
+    pub fn cuboid(half_extents: DVec3) -> Self {
+        Sdf::Cuboid { half_extents }
+    }
+
+    pub fn capsule(half_height: f64, radius: f64) -> Self {
+        Sdf::Capsule {
+            half_height,
+            radius,
+        }
+    }
+
+    pub fn cylinder(half_height: f64, radius: f64) -> Self {
+        Sdf::Cylinder {
+            half_height,
+            radius,
+        }
+    }
+
+    pub fn half_space(normal: DVec3, offset: f64) -> Self {
+        Sdf::HalfSpace {
+            normal: normal.normalize(),
+            offset,
+        }
+    }
+
+    pub fn union(self, other: Sdf) -> Self {
+        Sdf::Union(Box::new(self), Box::new(other))
+    }
+
+    pub fn intersection(self, other: Sdf) -> Self {
+        Sdf::Intersection(Box::new(self), Box::new(other))
+    }
+
+    pub fn difference(self, other: Sdf) -> Self {
+        Sdf::Difference(Box::new(self), Box::new(other))
+    }
+
+    pub fn smooth_union(self, other: Sdf, k: f64) -> Self {
+        Sdf::SmoothUnion(Box::new(self), Box::new(other), k)
+    }
+
+    pub fn inverted(self) -> Self {
+        Sdf::Inverted(Box::new(self))
+    }
+
+    pub fn transformed(self, pose: Pose) -> Self {
+        Sdf::Transformed(Box::new(self), pose)
+    }
+
+    /// Signed distance from `p` to the surface.
+    pub fn distance(&self, p: DVec3) -> f64 {
+        match self {
+            Sdf::Sphere { radius } => p.length() - radius,
+            Sdf::Cuboid { half_extents } => {
+                let q = p.abs() - *half_extents;
+                q.max(DVec3::ZERO).length() + q.max_element().min(0.0)
+            }
+            Sdf::Capsule {
+                half_height,
+                radius,
+            } => {
+                let y = p.y.clamp(-half_height, *half_height);
+                (p - DVec3::new(0.0, y, 0.0)).length() - radius
+            }
+            Sdf::Cylinder {
+                half_height,
+                radius,
+            } => {
+                let d = glam::DVec2::new(p.x.hypot(p.z) - radius, p.y.abs() - half_height);
+                d.max(glam::DVec2::ZERO).length() + d.max_element().min(0.0)
+            }
+            Sdf::HalfSpace { normal, offset } => normal.dot(p) - offset,
+            Sdf::Union(a, b) => a.distance(p).min(b.distance(p)),
+            Sdf::Intersection(a, b) => a.distance(p).max(b.distance(p)),
+            Sdf::Difference(a, b) => a.distance(p).max(-b.distance(p)),
+            Sdf::SmoothUnion(a, b, k) => {
+                let (da, db) = (a.distance(p), b.distance(p));
+                let h = (0.5 + 0.5 * (db - da) / k).clamp(0.0, 1.0);
+                db + (da - db) * h - k * h * (1.0 - h)
+            }
+            Sdf::Inverted(a) => -a.distance(p),
+            Sdf::Transformed(a, pose) => a.distance(pose.inverse().transform_point(p)),
+            Sdf::Grid(grid) => grid.distance(p),
+        }
+    }
+
+    /// Gradient of the distance by central differences; the outward normal on the surface.
+    pub fn gradient(&self, p: DVec3) -> DVec3 {
+        let h = GRADIENT_STEP;
+        let d = |e: DVec3| (self.distance(p + e * h) - self.distance(p - e * h)) / (2.0 * h);
+        DVec3::new(d(DVec3::X), d(DVec3::Y), d(DVec3::Z))
+    }
+}
+
+/// A signed distance field sampled on a regular grid and interpolated trilinearly.
+///
+/// Values are stored as `f32` in x-fastest order so they can be uploaded to the GPU
+/// unchanged. Outside the grid the distance is extrapolated from the grid box (see
+/// [`distance`](Self::distance)).
+#[derive(Debug, Clone, PartialEq)]
+pub struct VoxelSdf {
+    /// Position of sample `(0, 0, 0)`.
+    pub origin: DVec3,
+    pub spacing: f64,
+    /// Samples per axis, at least two each.
+    pub dims: [usize; 3],
+    pub values: Vec<f32>,
+}
+
+impl VoxelSdf {
+    /// Samples `sdf` over `bounds` every `spacing`.
+    pub fn bake(sdf: &Sdf, bounds: Aabb, spacing: f64) -> Self {
+        Self::sample(bounds, spacing, |p| sdf.distance(p))
+    }
+
+    /// Bakes a closed triangle mesh with `padding` of empty space around it.
+    ///
+    /// The sign comes from the generalized winding number, so the mesh may have
+    /// either orientation and small cracks do not flip whole regions.
+    pub fn from_mesh(mesh: &TriMesh, spacing: f64, padding: f64) -> Self {
+        let bounds = mesh.bounds().unwrap_or(Aabb::around(DVec3::ZERO, 0.0));
+        let bounds = Aabb::new(
+            bounds.min - DVec3::splat(padding),
+            bounds.max + DVec3::splat(padding),
+        );
+        Self::sample(bounds, spacing, |p| {
+            let distance = unsigned_distance(mesh, p, spacing);
+            if winding_number(mesh, p).abs() > 0.5 {
+                -distance
+            } else {
+                distance
+            }
+        })
+    }
+
+    fn sample(bounds: Aabb, spacing: f64, f: impl Fn(DVec3) -> f64) -> Self {
+        let extent = bounds.extent();
+        let dims =
+            [extent.x, extent.y, extent.z].map(|e| ((e / spacing).ceil() as usize + 1).max(2));
+        let mut values = Vec::with_capacity(dims[0] * dims[1] * dims[2]);
+        for z in 0..dims[2] {
+            for y in 0..dims[1] {
+                for x in 0..dims[0] {
+                    let p = bounds.min + DVec3::new(x as f64, y as f64, z as f64) * spacing;
+                    values.push(f(p) as f32);
+                }
+            }
+        }
+        Self {
+            origin: bounds.min,
+            spacing,
+            dims,
+            values,
+        }
+    }
+
+    /// Box covered by the samples.
+    pub fn bounds(&self) -> Aabb {
+        let last = DVec3::new(
+            (self.dims[0] - 1) as f64,
+            (self.dims[1] - 1) as f64,
+            (self.dims[2] - 1) as f64,
+        );
+        Aabb::new(self.origin, self.origin + last * self.spacing)
+    }
+
+    fn value(&self, c: IVec3) -> f64 {
+        let [nx, ny, _] = self.dims;
+        self.values[c.x as usize + nx * (c.y as usize + ny * c.z as usize)] as f64
+    }
+
+    /// Trilinearly interpolated distance at `p`.
+    ///
+    /// Outside the grid the sample at the nearest point of the grid box is extended:
+    /// by the distance to the box if that point is outside the shape, and along the
+    /// interpolated gradient if it is inside, so solids cut off by the box (a floor)
+    /// continue beyond it.
+    pub fn distance(&self, p: DVec3) -> f64 {
+        let bounds = self.bounds();
+        let q = p.clamp(bounds.min, bounds.max);
+        let (d, gradient) = self.interpolate(q);
+        if d >= 0.0 {
+            d + (p - q).length()
+        } else {
+            d + gradient.dot(p - q)
+        }
+    }
+
+    /// Trilinear interpolant and its gradient at `q`, which must lie in the grid box.
+    fn interpolate(&self, q: DVec3) -> (f64, DVec3) {
+        let g = (q - self.origin) / self.spacing;
+        let max_base = IVec3::new(
+            self.dims[0] as i32 - 2,
+            self.dims[1] as i32 - 2,
+            self.dims[2] as i32 - 2,
+        );
+        let base = g.floor().as_ivec3().min(max_base);
+        let f = g - base.as_dvec3();
+
+        let lerp = |a: f64, b: f64, t: f64| a + (b - a) * t;
+        let corner = |dx, dy, dz| self.value(base + IVec3::new(dx, dy, dz));
+        let (c000, c100) = (corner(0, 0, 0), corner(1, 0, 0));
+        let (c010, c110) = (corner(0, 1, 0), corner(1, 1, 0));
+        let (c001, c101) = (corner(0, 0, 1), corner(1, 0, 1));
+        let (c011, c111) = (corner(0, 1, 1), corner(1, 1, 1));
+        let x00 = lerp(c000, c100, f.x);
+        let x10 = lerp(c010, c110, f.x);
+        let x01 = lerp(c001, c101, f.x);
+        let x11 = lerp(c011, c111, f.x);
+        let (y0, y1) = (lerp(x00, x10, f.y), lerp(x01, x11, f.y));
+
+        let gradient = DVec3::new(
+            lerp(
+                lerp(c100 - c000, c110 - c010, f.y),
+                lerp(c101 - c001, c111 - c011, f.y),
+                f.z,
+            ),
+            lerp(x10 - x00, x11 - x01, f.z),
+            y1 - y0,
+        ) / self.spacing;
+        (lerp(y0, y1, f.z), gradient)
+    }
+}
+
+/// Distance from `p` to the nearest triangle, searching outwards from `hint`.
+fn unsigned_distance(mesh: &TriMesh, p: DVec3, hint: f64) -> f64 {
+    let mut radius = hint.max(f64::EPSILON);
+    loop {
+        match mesh.closest_point(p, &Aabb::around(p, radius)) {
+            // A triangle further than the box half-width could hide a closer one.
+            Some((q, _)) if q.distance(p) <= radius => return q.distance(p),
+            Some((q, _)) => radius = q.distance(p),
+            None if mesh.triangles.is_empty() => return f64::INFINITY,
+            None => radius *= 2.0,
+        }
+    }
+}
+
+/// Sum of the solid angles of all triangles seen from `p`, over 4π
+/// (Van Oosterom–Strackee): ±1 inside a closed mesh, 0 outside.
+fn winding_number(mesh: &TriMesh, p: DVec3) -> f64 {
+    let mut total = 0.0;
+    for t in &mesh.triangles {
+        let [a, b, c] = t.map(|i| mesh.vertices[i] - p);
+        let (la, lb, lc) = (a.length(), b.length(), c.length());
+        let numerator = a.dot(b.cross(c));
+        let denominator = la * lb * lc + a.dot(b) * lc + b.dot(c) * la + c.dot(a) * lb;
+        total += 2.0 * numerator.atan2(denominator);
+    }
+    total / (4.0 * PI)
+}
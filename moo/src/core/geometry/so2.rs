@@ -0,0 +1,88 @@
+use crate::core::geometry::lie::LieGroup;
+use crate::core::geometry::manifold::Manifold;
+use glam::{DMat2, DVec2};
+use std::f64::consts::{PI, TAU};
+
+/// The Special Orthogonal Group SO(2) of planar rotations.
+/// Points are angles kept in `(-π, π]`; the tangent is the angular increment.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SO2;
+
+impl SO2 {
+    /// Wraps an angle into `(-π, π]`.
+    pub fn wrap(angle: f64) -> f64 {
+        let a = angle.rem_euclid(TAU);
+        if a > PI { a - TAU } else { a }
+    }
+}
+
+impl Manifold for SO2 {
+    type Point = f64;
+    /// Angular increment (scalar angular velocity times dt).
+    type Tangent = f64;
+
+    fn dim() -> usize {
+        1
+    }
+
+    /// θ_{new} = θ + ω, wrapped.
+    fn retract(p: Self::Point, v: Self::Tangent) -> Self::Point {
+        Self::wrap(p + v)
+    }
+
+    /// Shortest signed angle from `p` to `q`.
+    fn local(p: Self::Point, q: Self::Point) -> Self::Tangent {
+        Self::wrap(q - p)
+    }
+}
+
+impl LieGroup for SO2 {
+    type Algebra = DMat2;
+    /// SO(2) is one-dimensional, so tangent maps are scalars.
+    type Matrix = f64;
+
+    fn identity() -> Self::Point {
+        0.0
+    }
+
+    fn compose(a: Self::Point, b: Self::Point) -> Self::Point {
+        Self::wrap(a + b)
+    }
+
+    fn inverse(p: Self::Point) -> Self::Point {
+        Self::wrap(-p)
+    }
+
+    fn exp(v: Self::Tangent) -> Self::Point {
+        Self::wrap(v)
+    }
+
+    fn log(p: Self::Point) -> Self::Tangent {
+        Self::wrap(p)
+    }
+
+    /// SO(2) is abelian, so the adjoint is the identity.
+    fn adjoint(_p: Self::Point, v: Self::Tangent) -> Self::Tangent {
+        v
+    }
+
+    fn adjoint_matrix(_p: Self::Point) -> Self::Matrix {
+        1.0
+    }
+
+    fn hat(v: Self::Tangent) -> Self::Algebra {
+        DMat2::from_cols(DVec2::new(0.0, v), DVec2::new(-v, 0.0))
+    }
+
+    fn vee(m: Self::Algebra) -> Self::Tangent {
+        m.x_axis.y
+    }
+
+    fn right_jacobian(_v: Self::Tangent) -> Self::Matrix {
+        1.0
+    }
+
+    fn right_jacobian_inv(_v: Self::Tangent) -> Self::Matrix {
+        1.0
+    }
+}
@@ -1,11 +1,40 @@
+use crate::core::geometry::lie::LieGroup;
 use crate::core::geometry::manifold::Manifold;
-use glam::{DQuat, DVec3};
+use glam::{DMat3, DQuat, DVec3};
+
+/// Below this rotation angle the Jacobian coefficients switch to their Taylor series.
+const SMALL_ANGLE: f64 = 1e-4;
 
 /// The Special Orthogonal Group SO(3) representing 3D rotations.
 /// We use Unit Quaternions for implementation.
 #[derive(Debug, Clone, Copy, Default)]
 pub struct SO3;
 
+impl SO3 {
+    /// Coefficients `((1 - cos θ) / θ², (θ - sin θ) / θ³)` of the exp Jacobians.
+    fn jacobian_coefficients(theta_sq: f64) -> (f64, f64) {
+        let theta = theta_sq.sqrt();
+        if theta < SMALL_ANGLE {
+            (0.5 - theta_sq / 24.0, 1.0 / 6.0 - theta_sq / 120.0)
+        } else {
+            (
+                (1.0 - theta.cos()) / theta_sq,
+                (theta - theta.sin()) / (theta_sq * theta),
+            )
+        }
+    }
+
+    /// Coefficient `1/θ² - (1 + cos θ) / (2 θ sin θ)` of the inverse Jacobians.
+    fn jacobian_inv_coefficient(theta_sq: f64) -> f64 {
+        let theta = theta_sq.sqrt();
+        if theta < SMALL_ANGLE {
+            1.0 / 12.0 + theta_sq / 720.0
+        } else {
+            1.0 / theta_sq - (1.0 + theta.cos()) / (2.0 * theta * theta.sin())
+        }
+    }
+}
+
 impl Manifold for SO3 {
     type Point = DQuat;
     /// Tangent vector in the Lie Algebra so(3).
@@ -17,27 +46,83 @@ impl Manifold for SO3 {
     }
 
     /// Retraction map: exponential map on SO(3).
-    /// Updates rotation `q` by angular velocity `v` over time 1.
+    /// Updates rotation `q` by the body-frame rotation vector `v` (e.g. `ω dt`).
     /// q_{new} = q * exp(v)
     fn retract(q: Self::Point, v: Self::Tangent) -> Self::Point {
-        // Formally on Lie Groups: exp: bundle -> manifold
-        // For Quaternions: q_new = q + 0.5 * w * q * dt (linearized)
-        // Or exact: q_new = q * [cos(|w|/2), sin(|w|/2) * w/|w|]
-
-        // glam's `DQuat::from_scaled_axis(v)` computes exp(v/2) efficiently.
-        // Note: The factor of 1/2 usually comes from the definition of the algebra.
-        // If v is angular velocity vector w, the update is typically exp(w * dt / 2) in quaternion space?
-        // Let's assume v is the "scaled axis" angle vector.
-
-        let delta = DQuat::from_scaled_axis(v);
-        (q * delta).normalize()
+        // `v` is the full rotation vector (angle |v| about v/|v|). Its unit quaternion is
+        // [cos(|v|/2), sin(|v|/2) v/|v|]; the half angle comes from the quaternion double
+        // cover, not from the algebra, and `from_scaled_axis` already applies it.
+        Self::compose(q, Self::exp(v))
     }
 
     /// Logarithmic map (Inverse Retraction).
-    /// Finds v such that retract(p, v) = q (approx).
+    /// Finds v such that retract(p, v) = q.
     /// v = log(p^{-1} * q)
     fn local(p: Self::Point, q: Self::Point) -> Self::Tangent {
-        let delta = p.inverse() * q;
-        delta.to_scaled_axis()
+        Self::log(p.inverse() * q)
+    }
+}
+
+impl LieGroup for SO3 {
+    type Algebra = DMat3;
+    type Matrix = DMat3;
+
+    fn identity() -> Self::Point {
+        DQuat::IDENTITY
+    }
+
+    fn compose(a: Self::Point, b: Self::Point) -> Self::Point {
+        (a * b).normalize()
+    }
+
+    fn inverse(p: Self::Point) -> Self::Point {
+        p.inverse()
+    }
+
+    fn exp(v: Self::Tangent) -> Self::Point {
+        DQuat::from_scaled_axis(v)
+    }
+
+    /// Rotation vector with angle in `[0, π]`.
+    fn log(p: Self::Point) -> Self::Tangent {
+        // q and -q are the same rotation; pick the hemisphere with the shorter angle.
+        let p = if p.w < 0.0 { -p } else { p };
+        p.to_scaled_axis()
+    }
+
+    /// `Ad_R ω = R ω`
+    fn adjoint(p: Self::Point, v: Self::Tangent) -> Self::Tangent {
+        p * v
+    }
+
+    fn adjoint_matrix(p: Self::Point) -> Self::Matrix {
+        DMat3::from_quat(p)
+    }
+
+    /// The skew-symmetric cross-product matrix `[v]×`.
+    fn hat(v: Self::Tangent) -> Self::Algebra {
+        DMat3::from_cols(
+            DVec3::new(0.0, v.z, -v.y),
+            DVec3::new(-v.z, 0.0, v.x),
+            DVec3::new(v.y, -v.x, 0.0),
+        )
+    }
+
+    fn vee(m: Self::Algebra) -> Self::Tangent {
+        DVec3::new(m.y_axis.z, m.z_axis.x, m.x_axis.y)
+    }
+
+    /// `J_r = I - (1 - cos θ)/θ² [v]× + (θ - sin θ)/θ³ [v]×²`
+    fn right_jacobian(v: Self::Tangent) -> Self::Matrix {
+        let (a, b) = Self::jacobian_coefficients(v.length_squared());
+        let w = Self::hat(v);
+        DMat3::IDENTITY - w * a + w * w * b
+    }
+
+    /// `J_r⁻¹ = I + ½ [v]× + (1/θ² - (1 + cos θ)/(2θ sin θ)) [v]×²`
+    fn right_jacobian_inv(v: Self::Tangent) -> Self::Matrix {
+        let c = Self::jacobian_inv_coefficient(v.length_squared());
+        let w = Self::hat(v);
+        DMat3::IDENTITY + w * 0.5 + w * w * c
     }
 }
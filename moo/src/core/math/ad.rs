@@ -1,3 +1,4 @@
+use super::real::first_order_real;
 use std::ops::{Add, Div, Mul, Neg, Sub};
 
 /// A Dual number for Forward-Mode Automatic Differentiation.
@@ -65,4 +66,47 @@ impl Neg for Dual {
     }
 }
 
-// TODO: Add Sin, Cos, Exp, Log via a trait like `RealField`
+// Mixed arithmetic with plain constants (no derivative contribution).
+
+impl Add<f64> for Dual {
+    type Output = Self;
+    fn add(self, rhs: f64) -> Self {
+        Self::new(self.val + rhs, self.der)
+    }
+}
+
+impl Sub<f64> for Dual {
+    type Output = Self;
+    fn sub(self, rhs: f64) -> Self {
+        Self::new(self.val - rhs, self.der)
+    }
+}
+
+impl Mul<f64> for Dual {
+    type Output = Self;
+    fn mul(self, rhs: f64) -> Self {
+        Self::new(self.val * rhs, self.der * rhs)
+    }
+}
+
+impl Div<f64> for Dual {
+    type Output = Self;
+    fn div(self, rhs: f64) -> Self {
+        Self::new(self.val / rhs, self.der / rhs)
+    }
+}
+
+impl Dual {
+    /// Chain rule: given f(a) and f'(a), returns f(a + bε) = f(a) + f'(a)bε.
+    #[inline]
+    fn chain(self, val: f64, slope: f64) -> Self {
+        Self::new(val, slope * self.der)
+    }
+
+    #[inline]
+    fn zip(self, rhs: Self, f: impl Fn(f64, f64) -> f64) -> f64 {
+        f(self.der, rhs.der)
+    }
+}
+
+first_order_real!(impl [] Dual);
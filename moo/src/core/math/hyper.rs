@@ -0,0 +1,257 @@
+use super::real::Real;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// A Hyper-Dual number for exact second derivatives.
+/// Represents `a + b ε₁ + c ε₂ + d ε₁ε₂` where `ε₁² = ε₂² = 0` (but `ε₁ε₂ ≠ 0`).
+///
+/// Seeding `x_i` along `ε₁` and `x_j` along `ε₂` gives `∂f/∂x_i` in `e1`,
+/// `∂f/∂x_j` in `e2` and `∂²f/∂x_i∂x_j` in `e12`, free of truncation error.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct HyperDual {
+    /// The primal value (f(x))
+    pub val: f64,
+    /// First derivative along the ε₁ direction.
+    pub e1: f64,
+    /// First derivative along the ε₂ direction.
+    pub e2: f64,
+    /// Mixed second derivative (ε₁ε₂ part).
+    pub e12: f64,
+}
+
+impl HyperDual {
+    pub const fn new(val: f64, e1: f64, e2: f64, e12: f64) -> Self {
+        Self { val, e1, e2, e12 }
+    }
+
+    /// Creates a constant value (all derivative parts zero).
+    pub const fn constant(val: f64) -> Self {
+        Self::new(val, 0.0, 0.0, 0.0)
+    }
+
+    /// Chain rule to second order, given f(a), f'(a) and f''(a).
+    #[inline]
+    fn chain(self, f0: f64, f1: f64, f2: f64) -> Self {
+        Self::new(
+            f0,
+            f1 * self.e1,
+            f1 * self.e2,
+            f1 * self.e12 + f2 * self.e1 * self.e2,
+        )
+    }
+}
+
+impl Add for HyperDual {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self::new(
+            self.val + rhs.val,
+            self.e1 + rhs.e1,
+            self.e2 + rhs.e2,
+            self.e12 + rhs.e12,
+        )
+    }
+}
+
+impl Sub for HyperDual {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(
+            self.val - rhs.val,
+            self.e1 - rhs.e1,
+            self.e2 - rhs.e2,
+            self.e12 - rhs.e12,
+        )
+    }
+}
+
+impl Mul for HyperDual {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Self::new(
+            self.val * rhs.val,
+            self.val * rhs.e1 + self.e1 * rhs.val,
+            self.val * rhs.e2 + self.e2 * rhs.val,
+            self.val * rhs.e12 + self.e1 * rhs.e2 + self.e2 * rhs.e1 + self.e12 * rhs.val,
+        )
+    }
+}
+
+impl Div for HyperDual {
+    type Output = Self;
+    #[allow(clippy::suspicious_arithmetic_impl)] // a / b = a * (1 / b)
+    fn div(self, rhs: Self) -> Self {
+        self * rhs.recip()
+    }
+}
+
+impl Neg for HyperDual {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self::new(-self.val, -self.e1, -self.e2, -self.e12)
+    }
+}
+
+impl Add<f64> for HyperDual {
+    type Output = Self;
+    fn add(self, rhs: f64) -> Self {
+        Self::new(self.val + rhs, self.e1, self.e2, self.e12)
+    }
+}
+
+impl Sub<f64> for HyperDual {
+    type Output = Self;
+    fn sub(self, rhs: f64) -> Self {
+        Self::new(self.val - rhs, self.e1, self.e2, self.e12)
+    }
+}
+
+impl Mul<f64> for HyperDual {
+    type Output = Self;
+    fn mul(self, rhs: f64) -> Self {
+        Self::new(self.val * rhs, self.e1 * rhs, self.e2 * rhs, self.e12 * rhs)
+    }
+}
+
+impl Div<f64> for HyperDual {
+    type Output = Self;
+    fn div(self, rhs: f64) -> Self {
+        Self::new(self.val / rhs, self.e1 / rhs, self.e2 / rhs, self.e12 / rhs)
+    }
+}
+
+impl Real for HyperDual {
+    fn constant(val: f64) -> Self {
+        HyperDual::constant(val)
+    }
+
+    fn value(self) -> f64 {
+        self.val
+    }
+
+    fn sqrt(self) -> Self {
+        let s = self.val.sqrt();
+        self.chain(s, 0.5 / s, -0.25 / (s * self.val))
+    }
+
+    fn powi(self, n: i32) -> Self {
+        match n {
+            0 => Self::constant(1.0),
+            1 => self,
+            _ => {
+                let nf = n as f64;
+                self.chain(
+                    self.val.powi(n),
+                    nf * self.val.powi(n - 1),
+                    nf * (nf - 1.0) * self.val.powi(n - 2),
+                )
+            }
+        }
+    }
+
+    fn powf(self, n: f64) -> Self {
+        if n == 0.0 {
+            return Self::constant(1.0);
+        }
+        self.chain(
+            self.val.powf(n),
+            n * self.val.powf(n - 1.0),
+            n * (n - 1.0) * self.val.powf(n - 2.0),
+        )
+    }
+
+    fn exp(self) -> Self {
+        let e = self.val.exp();
+        self.chain(e, e, e)
+    }
+
+    fn ln(self) -> Self {
+        let inv = 1.0 / self.val;
+        self.chain(self.val.ln(), inv, -inv * inv)
+    }
+
+    fn sin(self) -> Self {
+        let (s, c) = self.val.sin_cos();
+        self.chain(s, c, -s)
+    }
+
+    fn cos(self) -> Self {
+        let (s, c) = self.val.sin_cos();
+        self.chain(c, -s, -c)
+    }
+
+    fn tan(self) -> Self {
+        let t = self.val.tan();
+        let sec_sq = 1.0 + t * t;
+        self.chain(t, sec_sq, 2.0 * t * sec_sq)
+    }
+
+    fn atan2(self, x: Self) -> Self {
+        let y = self;
+        let r_sq = x.val * x.val + y.val * y.val;
+        if r_sq == 0.0 {
+            return Self::constant(y.val.atan2(x.val));
+        }
+        let r4 = r_sq * r_sq;
+
+        // First partials
+        let fy = x.val / r_sq;
+        let fx = -y.val / r_sq;
+        // Second partials
+        let fyy = -2.0 * x.val * y.val / r4;
+        let fxx = 2.0 * x.val * y.val / r4;
+        let fxy = (y.val * y.val - x.val * x.val) / r4;
+
+        Self::new(
+            y.val.atan2(x.val),
+            fy * y.e1 + fx * x.e1,
+            fy * y.e2 + fx * x.e2,
+            fy * y.e12
+                + fx * x.e12
+                + fyy * y.e1 * y.e2
+                + fxx * x.e1 * x.e2
+                + fxy * (y.e1 * x.e2 + x.e1 * y.e2),
+        )
+    }
+
+    fn tanh(self) -> Self {
+        let t = self.val.tanh();
+        let sech_sq = 1.0 - t * t;
+        self.chain(t, sech_sq, -2.0 * t * sech_sq)
+    }
+
+    fn abs(self) -> Self {
+        let slope = if self.val > 0.0 {
+            1.0
+        } else if self.val < 0.0 {
+            -1.0
+        } else {
+            0.0
+        };
+        self.chain(self.val.abs(), slope, 0.0)
+    }
+
+    fn min(self, other: Self) -> Self {
+        if self.val < other.val {
+            self
+        } else if other.val < self.val {
+            other
+        } else {
+            (self + other) * 0.5
+        }
+    }
+
+    fn max(self, other: Self) -> Self {
+        if self.val > other.val {
+            self
+        } else if other.val > self.val {
+            other
+        } else {
+            (self + other) * 0.5
+        }
+    }
+
+    fn recip(self) -> Self {
+        let r = 1.0 / self.val;
+        self.chain(r, -r * r, 2.0 * r * r * r)
+    }
+}
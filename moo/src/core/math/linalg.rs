@@ -0,0 +1,125 @@
+/// `max |x_i|`, the infinity norm.
+pub fn max_abs(x: impl IntoIterator<Item = f64>) -> f64 {
+    x.into_iter().fold(0.0, |m, x| m.max(x.abs()))
+}
+
+/// Solves `a x = b` in place (`b <- x`) by Gaussian elimination with partial
+/// pivoting. `a` is row-major and destroyed. Returns `false` if `a` is singular.
+pub fn solve_dense(a: &mut [f64], b: &mut [f64]) -> bool {
+    let n = b.len();
+    for k in 0..n {
+        let pivot = (k..n)
+            .max_by(|&i, &j| a[i * n + k].abs().total_cmp(&a[j * n + k].abs()))
+            .unwrap();
+        if a[pivot * n + k] == 0.0 || !a[pivot * n + k].is_finite() {
+            return false;
+        }
+        if pivot != k {
+            for j in 0..n {
+                a.swap(k * n + j, pivot * n + j);
+            }
+            b.swap(k, pivot);
+        }
+        for i in k + 1..n {
+            let factor = a[i * n + k] / a[k * n + k];
+            for j in k..n {
+                a[i * n + j] -= factor * a[k * n + j];
+            }
+            b[i] -= factor * b[k];
+        }
+    }
+    for k in (0..n).rev() {
+        let mut x = b[k];
+        for j in k + 1..n {
+            x -= a[k * n + j] * b[j];
+        }
+        b[k] = x / a[k * n + k];
+    }
+    true
+}
+
+/// Euclidean norm.
+pub fn norm(x: &[f64]) -> f64 {
+    x.iter().map(|x| x * x).sum::<f64>().sqrt()
+}
+
+/// Euclidean inner product.
+pub fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
+
+/// GMRES from a zero initial guess: minimises `|a(x) - b|` over the Krylov space
+/// of `a` and `b`, using modified Gram–Schmidt and Givens rotations. Stops when the
+/// residual has dropped by `tolerance` or the basis has `max_iterations` vectors.
+pub fn gmres(
+    mut a: impl FnMut(&[f64], &mut [f64]),
+    b: &[f64],
+    tolerance: f64,
+    max_iterations: usize,
+) -> Vec<f64> {
+    let n = b.len();
+    let mut x = vec![0.0; n];
+    let beta = norm(b);
+    if beta == 0.0 || n == 0 {
+        return x;
+    }
+    let m = max_iterations.min(n).max(1);
+
+    let mut basis: Vec<Vec<f64>> = vec![b.iter().map(|b| b / beta).collect()];
+    // Column j of the Hessenberg matrix, already rotated.
+    let mut h: Vec<Vec<f64>> = Vec::with_capacity(m);
+    let mut rotations: Vec<(f64, f64)> = Vec::with_capacity(m);
+    let mut g = vec![beta];
+
+    for j in 0..m {
+        let mut w = vec![0.0; n];
+        a(&basis[j], &mut w);
+        let mut column = Vec::with_capacity(j + 2);
+        for v in &basis {
+            let hij = dot(&w, v);
+            for (w, v) in w.iter_mut().zip(v) {
+                *w -= hij * v;
+            }
+            column.push(hij);
+        }
+        let norm_w = norm(&w);
+        column.push(norm_w);
+
+        for (i, &(c, s)) in rotations.iter().enumerate() {
+            let (a, b) = (column[i], column[i + 1]);
+            column[i] = c * a + s * b;
+            column[i + 1] = -s * a + c * b;
+        }
+        let (a, b) = (column[j], column[j + 1]);
+        let r = a.hypot(b);
+        let (c, s) = if r == 0.0 { (1.0, 0.0) } else { (a / r, b / r) };
+        column[j] = r;
+        column[j + 1] = 0.0;
+        rotations.push((c, s));
+        g.push(-s * g[j]);
+        g[j] *= c;
+        h.push(column);
+
+        if g[j + 1].abs() <= tolerance * beta || norm_w == 0.0 {
+            break;
+        }
+        basis.push(w.iter().map(|w| w / norm_w).collect());
+    }
+
+    // Back substitution on the triangular system, then x = V y.
+    let k = h.len();
+    let mut y = vec![0.0; k];
+    for i in (0..k).rev() {
+        let mut sum = g[i];
+        for (j, yj) in y.iter().enumerate().skip(i + 1) {
+            sum -= h[j][i] * yj;
+        }
+        y[i] = if h[i][i] == 0.0 { 0.0 } else { sum / h[i][i] };
+    }
+    for (v, y) in basis.iter().zip(&y) {
+        for (x, v) in x.iter_mut().zip(v) {
+            *x += y * v;
+        }
+    }
+    x
+}
@@ -1,3 +1,12 @@
 pub mod ad;
+pub mod hyper;
+pub mod linalg;
+pub mod multi;
+pub mod real;
+pub mod tape;
 
 pub use ad::Dual;
+pub use hyper::HyperDual;
+pub use multi::{ChunkDual, MultiDual};
+pub use real::Real;
+pub use tape::{Tape, Var};
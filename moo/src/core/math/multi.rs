@@ -0,0 +1,125 @@
+use super::real::first_order_real;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Number of tangent directions carried by the [`ChunkDual`] used for gradients.
+pub const GRADIENT_CHUNK: usize = 8;
+
+/// The multi-tangent dual the engine uses to sweep gradients in chunks.
+pub type ChunkDual = MultiDual<GRADIENT_CHUNK>;
+
+/// A vector-mode Dual number: one primal value with `N` independent tangents.
+/// Represents `a + Σ b_k ε_k` where `ε_j ε_k = 0`.
+///
+/// Seeding `N` coordinates with unit tangents yields `N` partial derivatives from a
+/// single evaluation, so the primal work is shared across the whole chunk.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MultiDual<const N: usize> {
+    /// The primal value (f(x))
+    pub val: f64,
+    /// The directional derivatives, one per tangent slot.
+    pub der: [f64; N],
+}
+
+impl<const N: usize> Default for MultiDual<N> {
+    fn default() -> Self {
+        Self::constant(0.0)
+    }
+}
+
+impl<const N: usize> MultiDual<N> {
+    pub const fn new(val: f64, der: [f64; N]) -> Self {
+        Self { val, der }
+    }
+
+    /// Creates a constant value (all tangents zero).
+    pub const fn constant(val: f64) -> Self {
+        Self { val, der: [0.0; N] }
+    }
+
+    /// Creates a variable seeded along tangent slot `slot`.
+    pub fn variable(val: f64, slot: usize) -> Self {
+        let mut der = [0.0; N];
+        der[slot] = 1.0;
+        Self { val, der }
+    }
+
+    /// Chain rule applied to every tangent slot.
+    #[inline]
+    fn chain(self, val: f64, slope: f64) -> Self {
+        Self::new(val, self.der.map(|d| slope * d))
+    }
+
+    #[inline]
+    fn zip(self, rhs: Self, f: impl Fn(f64, f64) -> f64) -> [f64; N] {
+        std::array::from_fn(|k| f(self.der[k], rhs.der[k]))
+    }
+}
+
+impl<const N: usize> Add for MultiDual<N> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.val + rhs.val, self.zip(rhs, |a, b| a + b))
+    }
+}
+
+impl<const N: usize> Sub for MultiDual<N> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.val - rhs.val, self.zip(rhs, |a, b| a - b))
+    }
+}
+
+impl<const N: usize> Mul for MultiDual<N> {
+    type Output = Self;
+    #[allow(clippy::suspicious_arithmetic_impl)] // Product rule
+    fn mul(self, rhs: Self) -> Self {
+        let (a, c) = (self.val, rhs.val);
+        Self::new(a * c, self.zip(rhs, |b, d| a * d + b * c))
+    }
+}
+
+impl<const N: usize> Div for MultiDual<N> {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        let (a, c) = (self.val, rhs.val);
+        let c_sq = c * c;
+        Self::new(a / c, self.zip(rhs, |b, d| (b * c - a * d) / c_sq))
+    }
+}
+
+impl<const N: usize> Neg for MultiDual<N> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self::new(-self.val, self.der.map(|d| -d))
+    }
+}
+
+impl<const N: usize> Add<f64> for MultiDual<N> {
+    type Output = Self;
+    fn add(self, rhs: f64) -> Self {
+        Self::new(self.val + rhs, self.der)
+    }
+}
+
+impl<const N: usize> Sub<f64> for MultiDual<N> {
+    type Output = Self;
+    fn sub(self, rhs: f64) -> Self {
+        Self::new(self.val - rhs, self.der)
+    }
+}
+
+impl<const N: usize> Mul<f64> for MultiDual<N> {
+    type Output = Self;
+    fn mul(self, rhs: f64) -> Self {
+        Self::new(self.val * rhs, self.der.map(|d| d * rhs))
+    }
+}
+
+impl<const N: usize> Div<f64> for MultiDual<N> {
+    type Output = Self;
+    fn div(self, rhs: f64) -> Self {
+        Self::new(self.val / rhs, self.der.map(|d| d / rhs))
+    }
+}
+
+first_order_real!(impl [const N: usize] MultiDual<N>);
@@ -0,0 +1,233 @@
+use std::fmt::Debug;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// A real scalar field that potentials can be written against.
+///
+/// Implemented for plain `f64` (value only) and for the AD number types, so a
+/// law written once in terms of `Real` is differentiated correctly no matter which
+/// scalar the engine evaluates it with.
+///
+/// Non-smooth functions (`abs`, `min`, `max`) propagate a valid subgradient at
+/// their kinks: zero for `abs(0)` and the mean of both branches on ties.
+pub trait Real:
+    Copy
+    + Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + Add<f64, Output = Self>
+    + Sub<f64, Output = Self>
+    + Mul<f64, Output = Self>
+    + Div<f64, Output = Self>
+{
+    /// Lifts a constant into the scalar type (zero derivative).
+    fn constant(val: f64) -> Self;
+
+    /// The primal value, discarding any derivative information.
+    fn value(self) -> f64;
+
+    fn sqrt(self) -> Self;
+    fn powi(self, n: i32) -> Self;
+    /// Power with a constant real exponent.
+    fn powf(self, n: f64) -> Self;
+    fn exp(self) -> Self;
+    /// Natural logarithm.
+    fn ln(self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn tan(self) -> Self;
+    /// Four-quadrant arctangent of `self / x` (`self` is the y coordinate).
+    fn atan2(self, x: Self) -> Self;
+    fn tanh(self) -> Self;
+    fn abs(self) -> Self;
+    fn min(self, other: Self) -> Self;
+    fn max(self, other: Self) -> Self;
+
+    /// Reciprocal `1 / self`.
+    fn recip(self) -> Self {
+        Self::constant(1.0) / self
+    }
+}
+
+impl Real for f64 {
+    fn constant(val: f64) -> Self {
+        val
+    }
+
+    fn value(self) -> f64 {
+        self
+    }
+
+    fn sqrt(self) -> Self {
+        f64::sqrt(self)
+    }
+
+    fn powi(self, n: i32) -> Self {
+        f64::powi(self, n)
+    }
+
+    fn powf(self, n: f64) -> Self {
+        f64::powf(self, n)
+    }
+
+    fn exp(self) -> Self {
+        f64::exp(self)
+    }
+
+    fn ln(self) -> Self {
+        f64::ln(self)
+    }
+
+    fn sin(self) -> Self {
+        f64::sin(self)
+    }
+
+    fn cos(self) -> Self {
+        f64::cos(self)
+    }
+
+    fn tan(self) -> Self {
+        f64::tan(self)
+    }
+
+    fn atan2(self, x: Self) -> Self {
+        f64::atan2(self, x)
+    }
+
+    fn tanh(self) -> Self {
+        f64::tanh(self)
+    }
+
+    fn abs(self) -> Self {
+        f64::abs(self)
+    }
+
+    fn min(self, other: Self) -> Self {
+        f64::min(self, other)
+    }
+
+    fn max(self, other: Self) -> Self {
+        f64::max(self, other)
+    }
+
+    fn recip(self) -> Self {
+        f64::recip(self)
+    }
+}
+
+/// Implements [`Real`] for a first-order dual number type from its inherent
+/// `new(val, der)`, `constant(val)`, `chain(val, slope)` (the chain rule applied to
+/// every tangent) and `zip(rhs, f)` (tangents combined component-wise), so the scalar
+/// and vector-mode duals share one set of derivative rules.
+macro_rules! first_order_real {
+    (impl [$($generics:tt)*] $ty:ty) => {
+        impl<$($generics)*> $crate::core::math::real::Real for $ty {
+            fn constant(val: f64) -> Self {
+                Self::constant(val)
+            }
+
+            fn value(self) -> f64 {
+                self.val
+            }
+
+            fn sqrt(self) -> Self {
+                let s = self.val.sqrt();
+                self.chain(s, 0.5 / s)
+            }
+
+            fn powi(self, n: i32) -> Self {
+                if n == 0 {
+                    return Self::constant(1.0);
+                }
+                self.chain(self.val.powi(n), n as f64 * self.val.powi(n - 1))
+            }
+
+            fn powf(self, n: f64) -> Self {
+                if n == 0.0 {
+                    return Self::constant(1.0);
+                }
+                self.chain(self.val.powf(n), n * self.val.powf(n - 1.0))
+            }
+
+            fn exp(self) -> Self {
+                let e = self.val.exp();
+                self.chain(e, e)
+            }
+
+            fn ln(self) -> Self {
+                self.chain(self.val.ln(), 1.0 / self.val)
+            }
+
+            fn sin(self) -> Self {
+                self.chain(self.val.sin(), self.val.cos())
+            }
+
+            fn cos(self) -> Self {
+                self.chain(self.val.cos(), -self.val.sin())
+            }
+
+            fn tan(self) -> Self {
+                let t = self.val.tan();
+                self.chain(t, 1.0 + t * t)
+            }
+
+            fn atan2(self, x: Self) -> Self {
+                // d atan2(y, x) = (x dy - y dx) / (x^2 + y^2)
+                let r_sq = x.val * x.val + self.val * self.val;
+                let der = if r_sq > 0.0 {
+                    self.zip(x, |dy, dx| (x.val * dy - self.val * dx) / r_sq)
+                } else {
+                    Self::constant(0.0).der
+                };
+                Self::new(self.val.atan2(x.val), der)
+            }
+
+            fn tanh(self) -> Self {
+                let t = self.val.tanh();
+                self.chain(t, 1.0 - t * t)
+            }
+
+            fn abs(self) -> Self {
+                // Subgradient 0 at the kink.
+                let slope = if self.val > 0.0 {
+                    1.0
+                } else if self.val < 0.0 {
+                    -1.0
+                } else {
+                    0.0
+                };
+                self.chain(self.val.abs(), slope)
+            }
+
+            fn min(self, other: Self) -> Self {
+                if self.val < other.val {
+                    self
+                } else if other.val < self.val {
+                    other
+                } else {
+                    Self::new(self.val, self.zip(other, |a, b| 0.5 * (a + b)))
+                }
+            }
+
+            fn max(self, other: Self) -> Self {
+                if self.val > other.val {
+                    self
+                } else if other.val > self.val {
+                    other
+                } else {
+                    Self::new(self.val, self.zip(other, |a, b| 0.5 * (a + b)))
+                }
+            }
+
+            fn recip(self) -> Self {
+                // 1/x -> -1/x^2
+                let r = 1.0 / self.val;
+                self.chain(r, -r * r)
+            }
+        }
+    };
+}
+
+pub(crate) use first_order_real;
@@ -0,0 +1,366 @@
+use super::real::Real;
+use std::cell::RefCell;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Parent slot marker for operands that are constants (not recorded on the tape).
+const NONE: usize = usize::MAX;
+
+/// One recorded operation: up to two parents and the local partial derivative
+/// of the result w.r.t. each of them.
+#[derive(Debug, Clone, Copy)]
+struct Node<S> {
+    parents: [usize; 2],
+    partials: [S; 2],
+}
+
+/// A Wengert list for Reverse-Mode Automatic Differentiation.
+///
+/// Every arithmetic operation on a [`Var`] appends a node to the tape. A single
+/// backward sweep over the recorded nodes then yields the derivative of one output
+/// w.r.t. every input, at a cost proportional to one evaluation, independent of
+/// the number of inputs.
+///
+/// The recorded values and partials are of the scalar type `S`, `f64` by default.
+/// Recording over [`Dual`](super::Dual) numbers instead differentiates the whole
+/// gradient along the dual direction (forward over reverse), which gives an exact
+/// Hessian-vector product from one evaluation and one sweep.
+#[derive(Debug)]
+pub struct Tape<S = f64> {
+    nodes: RefCell<Vec<Node<S>>>,
+}
+
+impl<S> Default for Tape<S> {
+    fn default() -> Self {
+        Self {
+            nodes: RefCell::new(Vec::new()),
+        }
+    }
+}
+
+impl<S: Real + PartialEq> Tape<S> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            nodes: RefCell::new(Vec::with_capacity(capacity)),
+        }
+    }
+
+    /// Records an independent input variable.
+    pub fn var(&self, val: S) -> Var<'_, S> {
+        let idx = self.push(Node {
+            parents: [NONE, NONE],
+            partials: [S::constant(0.0); 2],
+        });
+        Var {
+            val,
+            idx,
+            tape: Some(self),
+        }
+    }
+
+    /// Number of recorded nodes.
+    pub fn len(&self) -> usize {
+        self.nodes.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Forgets all recorded nodes so the allocation can be reused.
+    /// Requires `&mut self`, so no `Var` from the previous recording can be alive.
+    pub fn clear(&mut self) {
+        self.nodes.get_mut().clear();
+    }
+
+    fn push(&self, node: Node<S>) -> usize {
+        let mut nodes = self.nodes.borrow_mut();
+        nodes.push(node);
+        nodes.len() - 1
+    }
+
+    /// Back-propagates from `output`, returning the adjoint of every recorded node.
+    fn backward(&self, output: usize) -> Vec<S> {
+        let nodes = self.nodes.borrow();
+        let zero = S::constant(0.0);
+        let mut adjoints = vec![zero; nodes.len()];
+        adjoints[output] = S::constant(1.0);
+
+        for i in (0..=output).rev() {
+            let adj = adjoints[i];
+            if adj == zero {
+                continue;
+            }
+            let node = nodes[i];
+            for (&parent, &partial) in node.parents.iter().zip(&node.partials) {
+                if parent != NONE {
+                    adjoints[parent] = adjoints[parent] + partial * adj;
+                }
+            }
+        }
+
+        adjoints
+    }
+}
+
+/// The adjoints produced by [`Var::backward`].
+#[derive(Debug, Clone)]
+pub struct Adjoints<S = f64> {
+    values: Vec<S>,
+}
+
+impl<S: Real> Adjoints<S> {
+    /// Derivative of the output w.r.t. the variable `x`.
+    /// Constants (and variables recorded after the output) have zero derivative.
+    pub fn wrt(&self, x: Var<'_, S>) -> S {
+        if x.tape.is_none() {
+            return S::constant(0.0);
+        }
+        self.values.get(x.idx).copied().unwrap_or(S::constant(0.0))
+    }
+}
+
+/// A reverse-mode scalar recorded on a [`Tape`].
+///
+/// Constants carry no tape reference, so laws can freely mix `Real::constant`
+/// values with recorded variables.
+#[derive(Debug, Clone, Copy)]
+pub struct Var<'t, S = f64> {
+    /// The primal value (f(x))
+    pub val: S,
+    idx: usize,
+    tape: Option<&'t Tape<S>>,
+}
+
+impl Var<'_> {
+    /// Creates a constant value (not recorded, zero derivative).
+    pub const fn constant(val: f64) -> Self {
+        Self {
+            val,
+            idx: NONE,
+            tape: None,
+        }
+    }
+}
+
+impl<'t, S: Real + PartialEq> Var<'t, S> {
+    /// A value that is not recorded.
+    fn untaped(val: S) -> Self {
+        Self {
+            val,
+            idx: NONE,
+            tape: None,
+        }
+    }
+
+    /// Back-propagates from this value to every variable on its tape.
+    pub fn backward(self) -> Adjoints<S> {
+        let values = match self.tape {
+            Some(tape) => tape.backward(self.idx),
+            None => Vec::new(),
+        };
+        Adjoints { values }
+    }
+
+    /// Records a unary operation with local derivative `slope`.
+    #[inline]
+    fn unary(self, val: S, slope: S) -> Self {
+        match self.tape {
+            Some(tape) => Self {
+                val,
+                idx: tape.push(Node {
+                    parents: [self.idx, NONE],
+                    partials: [slope, S::constant(0.0)],
+                }),
+                tape: Some(tape),
+            },
+            None => Self::untaped(val),
+        }
+    }
+
+    /// Records a binary operation with local derivatives `da` and `db`.
+    #[inline]
+    fn binary(self, rhs: Self, val: S, da: S, db: S) -> Self {
+        match self.tape.or(rhs.tape) {
+            Some(tape) => Self {
+                val,
+                idx: tape.push(Node {
+                    parents: [self.idx, rhs.idx],
+                    partials: [da, db],
+                }),
+                tape: Some(tape),
+            },
+            None => Self::untaped(val),
+        }
+    }
+}
+
+impl<S: Real + PartialEq> Add for Var<'_, S> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        self.binary(rhs, self.val + rhs.val, S::constant(1.0), S::constant(1.0))
+    }
+}
+
+impl<S: Real + PartialEq> Sub for Var<'_, S> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        self.binary(rhs, self.val - rhs.val, S::constant(1.0), S::constant(-1.0))
+    }
+}
+
+impl<S: Real + PartialEq> Mul for Var<'_, S> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        self.binary(rhs, self.val * rhs.val, rhs.val, self.val)
+    }
+}
+
+impl<S: Real + PartialEq> Div for Var<'_, S> {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        let inv = rhs.val.recip();
+        let val = self.val * inv;
+        self.binary(rhs, val, inv, -val * inv)
+    }
+}
+
+impl<S: Real + PartialEq> Neg for Var<'_, S> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        self.unary(-self.val, S::constant(-1.0))
+    }
+}
+
+impl<S: Real + PartialEq> Add<f64> for Var<'_, S> {
+    type Output = Self;
+    fn add(self, rhs: f64) -> Self {
+        self.unary(self.val + rhs, S::constant(1.0))
+    }
+}
+
+impl<S: Real + PartialEq> Sub<f64> for Var<'_, S> {
+    type Output = Self;
+    fn sub(self, rhs: f64) -> Self {
+        self.unary(self.val - rhs, S::constant(1.0))
+    }
+}
+
+impl<S: Real + PartialEq> Mul<f64> for Var<'_, S> {
+    type Output = Self;
+    fn mul(self, rhs: f64) -> Self {
+        self.unary(self.val * rhs, S::constant(rhs))
+    }
+}
+
+impl<S: Real + PartialEq> Div<f64> for Var<'_, S> {
+    type Output = Self;
+    fn div(self, rhs: f64) -> Self {
+        self.unary(self.val / rhs, S::constant(1.0 / rhs))
+    }
+}
+
+impl<S: Real + PartialEq> Real for Var<'_, S> {
+    fn constant(val: f64) -> Self {
+        Self::untaped(S::constant(val))
+    }
+
+    fn value(self) -> f64 {
+        self.val.value()
+    }
+
+    fn sqrt(self) -> Self {
+        let s = self.val.sqrt();
+        self.unary(s, s.recip() * 0.5)
+    }
+
+    fn powi(self, n: i32) -> Self {
+        if n == 0 {
+            return Self::constant(1.0);
+        }
+        self.unary(self.val.powi(n), self.val.powi(n - 1) * n as f64)
+    }
+
+    fn powf(self, n: f64) -> Self {
+        if n == 0.0 {
+            return Self::constant(1.0);
+        }
+        self.unary(self.val.powf(n), self.val.powf(n - 1.0) * n)
+    }
+
+    fn exp(self) -> Self {
+        let e = self.val.exp();
+        self.unary(e, e)
+    }
+
+    fn ln(self) -> Self {
+        self.unary(self.val.ln(), self.val.recip())
+    }
+
+    fn sin(self) -> Self {
+        self.unary(self.val.sin(), self.val.cos())
+    }
+
+    fn cos(self) -> Self {
+        self.unary(self.val.cos(), -self.val.sin())
+    }
+
+    fn tan(self) -> Self {
+        let t = self.val.tan();
+        self.unary(t, t * t + 1.0)
+    }
+
+    fn atan2(self, x: Self) -> Self {
+        let r_sq = x.val * x.val + self.val * self.val;
+        let (dy, dx) = if r_sq.value() > 0.0 {
+            (x.val / r_sq, -self.val / r_sq)
+        } else {
+            (S::constant(0.0), S::constant(0.0))
+        };
+        self.binary(x, self.val.atan2(x.val), dy, dx)
+    }
+
+    fn tanh(self) -> Self {
+        let t = self.val.tanh();
+        self.unary(t, -(t * t) + 1.0)
+    }
+
+    fn abs(self) -> Self {
+        let slope = if self.val.value() > 0.0 {
+            1.0
+        } else if self.val.value() < 0.0 {
+            -1.0
+        } else {
+            0.0
+        };
+        self.unary(self.val.abs(), S::constant(slope))
+    }
+
+    fn min(self, other: Self) -> Self {
+        if self.val.value() < other.val.value() {
+            self
+        } else if other.val.value() < self.val.value() {
+            other
+        } else {
+            self.binary(other, self.val, S::constant(0.5), S::constant(0.5))
+        }
+    }
+
+    fn max(self, other: Self) -> Self {
+        if self.val.value() > other.val.value() {
+            self
+        } else if other.val.value() > self.val.value() {
+            other
+        } else {
+            self.binary(other, self.val, S::constant(0.5), S::constant(0.5))
+        }
+    }
+
+    fn recip(self) -> Self {
+        let r = self.val.recip();
+        self.unary(r, -(r * r))
+    }
+}
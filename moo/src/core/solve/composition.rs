@@ -0,0 +1,206 @@
+use super::constraints::Constraint;
+use super::{Integrator, compute_forces, kick_rotations};
+use crate::core::state::PhaseSpace;
+use crate::laws::registry::LawRegistry;
+
+/// A symplectic splitting method: alternating kicks `v += b_i h F / m` and drifts
+/// `x <- retract(x, a_i h v)`,
+///
+/// `K(b_0) D(a_0) K(b_1) D(a_1) ... D(a_{m-1}) K(b_m)`.
+///
+/// Every substep is the exact flow of a Hamiltonian piece, so any coefficient table
+/// gives a symplectic map; the published tables below are chosen to cancel the
+/// error terms up to the stated order. Kicks with a zero coefficient are skipped,
+/// and adjacent half kicks of composed Verlet steps are merged, so the cost of a
+/// step is [`force_evaluations`](Self::force_evaluations) gradient evaluations.
+///
+/// Constraints are projected once, at the end of the step.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Composition {
+    kicks: Vec<f64>,
+    drifts: Vec<f64>,
+    order: u32,
+}
+
+impl Composition {
+    /// A scheme from its kick and drift coefficients (`kicks.len() == drifts.len() + 1`).
+    /// Both must sum to one for the step to be consistent.
+    pub fn new(kicks: Vec<f64>, drifts: Vec<f64>, order: u32) -> Self {
+        assert_eq!(
+            kicks.len(),
+            drifts.len() + 1,
+            "a composition alternates kick, drift, ..., kick"
+        );
+        debug_assert!((kicks.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        debug_assert!((drifts.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        Self {
+            kicks,
+            drifts,
+            order,
+        }
+    }
+
+    /// Drift-first (position Verlet style) form `D(a_0) K(b_0) D(a_1) ... K(b_{m-1}) D(a_m)`.
+    pub fn drift_first(drifts: &[f64], kicks: &[f64], order: u32) -> Self {
+        let mut all_kicks = Vec::with_capacity(kicks.len() + 2);
+        all_kicks.push(0.0);
+        all_kicks.extend_from_slice(kicks);
+        all_kicks.push(0.0);
+        Self::new(all_kicks, drifts.to_vec(), order)
+    }
+
+    /// Consecutive velocity Verlet steps of sizes `w_i h`, with the touching half
+    /// kicks merged into one.
+    pub fn from_verlet(weights: &[f64], order: u32) -> Self {
+        let mut kicks = vec![0.0; weights.len() + 1];
+        for (i, w) in weights.iter().enumerate() {
+            kicks[i] += 0.5 * w;
+            kicks[i + 1] += 0.5 * w;
+        }
+        Self::new(kicks, weights.to_vec(), order)
+    }
+
+    /// Plain velocity Verlet (order 2), for comparison.
+    pub fn verlet() -> Self {
+        Self::from_verlet(&[1.0], 2)
+    }
+
+    /// Order 4: Yoshida's (1990) triple jump, three Verlet steps with weights
+    /// `w1, w0, w1`, `w1 = 1 / (2 - 2^(1/3))`, `w0 = 1 - 2 w1`.
+    pub fn yoshida4() -> Self {
+        let w1 = 1.0 / (2.0 - 2f64.cbrt());
+        Self::from_verlet(&[w1, 1.0 - 2.0 * w1, w1], 4)
+    }
+
+    /// Order 4: Forest and Ruth (1990), the triple jump built from position Verlet
+    /// (drift first), with `θ = 1 / (2 - 2^(1/3))`.
+    pub fn forest_ruth() -> Self {
+        let theta = 1.0 / (2.0 - 2f64.cbrt());
+        Self::drift_first(
+            &[
+                0.5 * theta,
+                0.5 * (1.0 - theta),
+                0.5 * (1.0 - theta),
+                0.5 * theta,
+            ],
+            &[theta, 1.0 - 2.0 * theta, theta],
+            4,
+        )
+    }
+
+    /// Order 6: Yoshida's (1990) seven-step composition, solution A.
+    pub fn yoshida6() -> Self {
+        Self::symmetric_verlet(
+            &[0.784513610477560, 0.235573213359357, -1.17767998417887],
+            6,
+        )
+    }
+
+    /// Order 8: Yoshida's (1990) fifteen-step composition, solution D.
+    pub fn yoshida8() -> Self {
+        Self::symmetric_verlet(
+            &[
+                0.914844246229740,
+                0.253693336566229,
+                -1.44485223686048,
+                -0.158240635368243,
+                1.93813913762276,
+                -1.96061023297549,
+                0.102799849391985,
+            ],
+            8,
+        )
+    }
+
+    /// Order 4: Blanes and Moan (2002), SRKN₆ᵇ. Seven kicks per step, with error
+    /// constants far below the triple jump's.
+    pub fn blanes_moan4() -> Self {
+        let a = [0.0792036964311957, 0.353172906049774, -0.0420650803577195];
+        let b = [0.209515106613362, -0.143851773179818];
+        Self::new(palindrome(&a, true), palindrome(&b, false), 4)
+    }
+
+    /// Order 6: Blanes and Moan (2002), SRKN₁₁ᵇ. Twelve kicks per step.
+    pub fn blanes_moan6() -> Self {
+        let a = [
+            0.0414649985182624,
+            0.198128671918067,
+            -0.0400061921041533,
+            0.0752539843015807,
+            -0.0115113874206879,
+        ];
+        let b = [
+            0.123229775946271,
+            0.290553797799558,
+            -0.127049212625417,
+            -0.246331761062075,
+            0.357208872795928,
+        ];
+        Self::new(palindrome(&a, false), palindrome(&b, true), 6)
+    }
+
+    /// Symmetric Verlet composition `w_k .. w_1, w_0, w_1 .. w_k` from the outer
+    /// weights `[w_k, .., w_1]`; `w_0` makes them sum to one.
+    fn symmetric_verlet(outer: &[f64], order: u32) -> Self {
+        Self::from_verlet(&palindrome(outer, true), order)
+    }
+
+    pub fn order(&self) -> u32 {
+        self.order
+    }
+
+    /// Gradient evaluations per step.
+    pub fn force_evaluations(&self) -> usize {
+        self.kicks.iter().filter(|&&b| b != 0.0).count()
+    }
+
+    fn kick(state: &mut PhaseSpace, laws: &LawRegistry, forces: &mut [f64], h: f64) {
+        compute_forces(state, laws, forces);
+        for ((v, f), m) in state.v.iter_mut().zip(forces.iter()).zip(&state.mass) {
+            *v += f / m * h;
+        }
+        kick_rotations(state, laws, h);
+    }
+}
+
+impl Integrator for Composition {
+    fn step(
+        &mut self,
+        state: &mut PhaseSpace,
+        laws: &LawRegistry,
+        constraints: &[Box<dyn Constraint>],
+        dt: f64,
+    ) {
+        laws.sync(state);
+        let mut forces = vec![0.0; state.dof];
+        for (i, &b) in self.kicks.iter().enumerate() {
+            if b != 0.0 {
+                Self::kick(state, laws, &mut forces, b * dt);
+            }
+            if let Some(&a) = self.drifts.get(i) {
+                state.drift(a * dt);
+            }
+        }
+
+        for c in constraints {
+            c.project(state);
+        }
+
+        state.t += dt;
+    }
+}
+
+/// Mirrors the leading half of a symmetric coefficient table, as the tables are
+/// published: either with one middle coefficient or with the last one doubled,
+/// which is chosen so that the whole sequence sums to one.
+fn palindrome(half: &[f64], middle: bool) -> Vec<f64> {
+    let sum: f64 = half.iter().sum();
+    let mut all = half.to_vec();
+    if middle {
+        all.push(1.0 - 2.0 * sum);
+    } else {
+        all.extend([0.5 - sum; 2]);
+    }
+    all.extend(half.iter().rev());
+    all
+}
@@ -1,10 +1,18 @@
-use crate::core::state::PhaseSpace;
+use crate::core::geometry::se3::Pose;
+use crate::core::geometry::shapes::{self, Contact, Sdf, Shape, Sphere, TriMesh};
+use crate::core::state::{PhaseSpace, Reindex};
 
 /// A geometric constraint that enforces non-penetration or joints.
 pub trait Constraint {
     /// Projects the state to satisfy the constraint.
     /// Modifies position (q) and velocity (v).
     fn project(&self, state: &mut PhaseSpace);
+
+    /// Follows particles to their new indices after particles were removed.
+    /// Returns `false` if the constraint lost a particle it needs and should be dropped.
+    fn reindex(&mut self, _map: &Reindex) -> bool {
+        true
+    }
 }
 
 pub struct FloorConstraint {
@@ -23,9 +31,10 @@ impl FloorConstraint {
 
 impl Constraint for FloorConstraint {
     fn project(&self, state: &mut PhaseSpace) {
-        let n = state.dof / 3;
+        let dim = state.dim;
+        let n = state.particle_count();
         for i in 0..n {
-            let idx = i * 3;
+            let idx = i * dim;
             let y = state.q[idx + 1];
 
             // Check penetration
@@ -41,7 +50,9 @@ impl Constraint for FloorConstraint {
                     // Friction (Simple)
                     let friction = 0.9;
                     state.v[idx] *= friction;
-                    state.v[idx + 2] *= friction;
+                    if dim == 3 {
+                        state.v[idx + 2] *= friction;
+                    }
                 }
             }
         }
@@ -74,22 +85,20 @@ const DEFAULT_MIN_SEPARATION: f64 = 1e-6;
 
 impl Constraint for SphereConstraint {
     fn project(&self, state: &mut PhaseSpace) {
-        let n = state.dof / 3;
+        let dim = state.dim;
+        let n = state.particle_count();
         for i in 0..n {
             for j in (i + 1)..n {
-                let idx_i = i * 3;
-                let idx_j = j * 3;
-
-                let p1 = glam::DVec3::from_slice(&state.q[idx_i..idx_i + 3]);
-                let p2 = glam::DVec3::from_slice(&state.q[idx_j..idx_j + 3]);
+                let p1 = state.position(i);
+                let p2 = state.position(j);
 
                 let diff = p1 - p2;
                 let dist_sq = diff.length_squared();
                 let r_sum = state.radius[i] + state.radius[j];
 
                 if dist_sq < r_sum * r_sum {
-                    let v1 = glam::DVec3::from_slice(&state.v[idx_i..idx_i + 3]);
-                    let v2 = glam::DVec3::from_slice(&state.v[idx_j..idx_j + 3]);
+                    let v1 = state.velocity(i);
+                    let v2 = state.velocity(j);
                     let rel_vel = v1 - v2;
 
                     let mut dist = dist_sq.sqrt();
@@ -110,13 +119,8 @@ impl Constraint for SphereConstraint {
                     // Split overlap based on inverse mass? (Simplification: 0.5 each for now)
                     let correction = normal * (overlap * 0.5);
 
-                    state.q[idx_i] += correction.x;
-                    state.q[idx_i + 1] += correction.y;
-                    state.q[idx_i + 2] += correction.z;
-
-                    state.q[idx_j] -= correction.x;
-                    state.q[idx_j + 1] -= correction.y;
-                    state.q[idx_j + 2] -= correction.z;
+                    state.set_position(i, p1 + correction);
+                    state.set_position(j, p2 - correction);
 
                     // 2. Velocity Response
                     let vel_along_normal = rel_vel.dot(normal);
@@ -126,23 +130,144 @@ impl Constraint for SphereConstraint {
                         // Assuming equal mass for impulse distribution simplicity in this constraint
                         // Ideally: j / (1/m1 + 1/m2).
                         // Let's do it properly if we can access mass.
-                        let inv_mass1 = 1.0 / state.mass[i * 3]; // Mass is duplicated per DOF
-                        let inv_mass2 = 1.0 / state.mass[j * 3];
+                        let inv_mass1 = 1.0 / state.mass[i * dim]; // Mass is duplicated per DOF
+                        let inv_mass2 = 1.0 / state.mass[j * dim];
                         let impulse_mag = j_impulse / (inv_mass1 + inv_mass2);
 
                         let impulse = normal * impulse_mag;
 
                         // Apply Impulse
-                        state.v[idx_i] += impulse.x * inv_mass1;
-                        state.v[idx_i + 1] += impulse.y * inv_mass1;
-                        state.v[idx_i + 2] += impulse.z * inv_mass1;
-
-                        state.v[idx_j] -= impulse.x * inv_mass2;
-                        state.v[idx_j + 1] -= impulse.y * inv_mass2;
-                        state.v[idx_j + 2] -= impulse.z * inv_mass2;
+                        state.set_velocity(i, v1 + impulse * inv_mass1);
+                        state.set_velocity(j, v2 - impulse * inv_mass2);
                     }
                 }
             }
         }
     }
 }
+
+/// A static collision shape that particles (spheres of `state.radius`) cannot enter.
+pub struct ColliderConstraint {
+    pub shape: Shape,
+    pub pose: Pose,
+    pub restitution: f64,
+    /// Fraction of the tangential velocity removed on impact (0 = frictionless).
+    pub friction: f64,
+}
+
+impl ColliderConstraint {
+    pub fn new(shape: Shape, pose: Pose, restitution: f64, friction: f64) -> Self {
+        Self {
+            shape,
+            pose,
+            restitution,
+            friction,
+        }
+    }
+}
+
+impl Constraint for ColliderConstraint {
+    fn project(&self, state: &mut PhaseSpace) {
+        for i in 0..state.particle_count() {
+            let p = state.position(i);
+            let particle = Shape::Sphere(Sphere::new(state.radius[i]));
+            let particle_pose = Pose::new(glam::DQuat::IDENTITY, p);
+
+            let Some(contact) = shapes::contact(&self.shape, self.pose, &particle, particle_pose)
+            else {
+                continue;
+            };
+
+            push_out(state, i, contact, self.restitution, self.friction);
+        }
+    }
+}
+
+/// A static triangle mesh (containers, funnels, terrain) that particles cannot pass through.
+pub struct MeshConstraint {
+    pub mesh: TriMesh,
+    pub pose: Pose,
+    pub restitution: f64,
+    /// Fraction of the tangential velocity removed on impact (0 = frictionless).
+    pub friction: f64,
+}
+
+impl MeshConstraint {
+    pub fn new(mesh: TriMesh, pose: Pose, restitution: f64, friction: f64) -> Self {
+        Self {
+            mesh,
+            pose,
+            restitution,
+            friction,
+        }
+    }
+}
+
+impl Constraint for MeshConstraint {
+    fn project(&self, state: &mut PhaseSpace) {
+        for i in 0..state.particle_count() {
+            let p = state.position(i);
+            if let Some(contact) = self.mesh.sphere_contact(self.pose, p, state.radius[i]) {
+                push_out(state, i, contact, self.restitution, self.friction);
+            }
+        }
+    }
+}
+
+/// Keeps particles on the outside (positive side) of a signed distance field.
+pub struct SdfConstraint {
+    pub sdf: Sdf,
+    pub restitution: f64,
+    /// Fraction of the tangential velocity removed on impact (0 = frictionless).
+    pub friction: f64,
+}
+
+impl SdfConstraint {
+    pub fn new(sdf: Sdf, restitution: f64, friction: f64) -> Self {
+        Self {
+            sdf,
+            restitution,
+            friction,
+        }
+    }
+}
+
+impl Constraint for SdfConstraint {
+    fn project(&self, state: &mut PhaseSpace) {
+        for i in 0..state.particle_count() {
+            let p = state.position(i);
+            let radius = state.radius[i];
+            let d = self.sdf.distance(p);
+            if d >= radius {
+                continue;
+            }
+            let Some(n) = self.sdf.gradient(p).try_normalize() else {
+                continue;
+            };
+            let surface = p - n * d;
+            let contact = Contact {
+                normal: n,
+                depth: radius - d,
+                point_a: surface,
+                point_b: p - n * radius,
+            };
+            push_out(state, i, contact, self.restitution, self.friction);
+        }
+    }
+}
+
+/// Moves particle `i` (shape B of `contact`) out along the contact normal and
+/// applies restitution and friction to the approaching part of its velocity.
+fn push_out(state: &mut PhaseSpace, i: usize, contact: Contact, restitution: f64, friction: f64) {
+    // Positional Projection along the contact normal
+    let n = contact.normal;
+    state.set_position(i, state.position(i) + n * contact.depth);
+
+    // Velocity Response
+    let v = state.velocity(i);
+    let vn = v.dot(n);
+    if vn < 0.0 {
+        let tangential = (v - n * vn) * (1.0 - friction);
+        state.set_velocity(i, tangential - n * (vn * restitution));
+    }
+}
@@ -0,0 +1,238 @@
+use super::constraints::Constraint;
+use super::{Integrator, advance_rotations};
+use crate::core::math::linalg::{gmres, max_abs, norm};
+use crate::core::state::PhaseSpace;
+use crate::laws::registry::LawRegistry;
+
+/// A step whose Newton iteration missed the tolerance. The step still completes
+/// with the last iterate.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConvergenceFailure {
+    /// Time at the start of the step.
+    pub t: f64,
+    pub dt: f64,
+    pub iterations: usize,
+    /// Final residual, in velocity units (see [`NewtonKrylov::tolerance`]).
+    pub residual: f64,
+}
+
+/// Inexact Newton solver shared by the implicit integrators.
+///
+/// Both methods reduce the step to one nonlinear system for a velocity `x`,
+///
+/// `r(x) = x - v0 + θ h M⁻¹ ∇V(q0 + θ h x) = 0`,
+///
+/// whose Jacobian `I + θ² h² M⁻¹ H` is never formed: each Newton correction is
+/// found by GMRES with [`LawRegistry::hessian_vector_product`] for the products, so
+/// a Krylov iteration costs one forward-over-reverse pass, independent of the DOF
+/// count. A backtracking line search halves corrections that would increase `|r|`;
+/// if no halving helps, the iteration stops there and the step counts as a failure.
+#[derive(Debug, Clone)]
+pub struct NewtonKrylov {
+    /// Newton stops once `max |r_i| <= tolerance * (1 + max |x_i|)`.
+    pub tolerance: f64,
+    pub max_iterations: usize,
+    /// GMRES stops once the linear residual has dropped by this factor.
+    pub krylov_tolerance: f64,
+    /// Krylov basis size per Newton iteration (GMRES is not restarted).
+    pub max_krylov_iterations: usize,
+    /// Newton iterations used by the last step.
+    pub iterations: usize,
+    /// Whether the last step met the tolerance.
+    pub converged: bool,
+    /// Every step that did not converge so far.
+    pub failures: Vec<ConvergenceFailure>,
+}
+
+impl Default for NewtonKrylov {
+    fn default() -> Self {
+        Self::new(1e-10, 20)
+    }
+}
+
+impl NewtonKrylov {
+    const MAX_BACKTRACKS: usize = 10;
+
+    pub fn new(tolerance: f64, max_iterations: usize) -> Self {
+        Self {
+            tolerance,
+            max_iterations,
+            krylov_tolerance: 1e-8,
+            max_krylov_iterations: 50,
+            iterations: 0,
+            converged: true,
+            failures: Vec::new(),
+        }
+    }
+
+    /// `q0 + θ h x`.
+    fn stage(q0: &[f64], x: &[f64], theta_h: f64) -> Vec<f64> {
+        q0.iter().zip(x).map(|(q, x)| q + theta_h * x).collect()
+    }
+
+    fn residual(state: &PhaseSpace, laws: &LawRegistry, x: &[f64], theta_h: f64, out: &mut [f64]) {
+        let q = Self::stage(&state.q, x, theta_h);
+        laws.gradient(&q, &state.mass, out);
+        for (((r, x), v0), m) in out.iter_mut().zip(x).zip(&state.v).zip(&state.mass) {
+            *r = x - v0 + theta_h * *r / m;
+        }
+    }
+
+    /// Solves `r(x) = 0` for the state's translational DOFs, starting from `x = v0`.
+    fn solve(&mut self, state: &PhaseSpace, laws: &LawRegistry, dt: f64, theta: f64) -> Vec<f64> {
+        let n = state.dof;
+        let theta_h = theta * dt;
+        let mut x = state.v.clone();
+        let mut r = vec![0.0; n];
+        let mut trial = vec![0.0; n];
+        let mut trial_r = vec![0.0; n];
+        Self::residual(state, laws, &x, theta_h, &mut r);
+
+        self.iterations = 0;
+        self.converged = false;
+        loop {
+            if max_abs(r.iter().copied()) <= self.tolerance * (1.0 + max_abs(x.iter().copied())) {
+                self.converged = true;
+                break;
+            }
+            if self.iterations == self.max_iterations {
+                break;
+            }
+            self.iterations += 1;
+
+            // J d = -r with J y = y + θ² h² M⁻¹ H(q) y, one taped pass per product.
+            let q = Self::stage(&state.q, &x, theta_h);
+            let mut hv = vec![0.0; n];
+            let jacobian = |y: &[f64], out: &mut [f64]| {
+                laws.hessian_vector_product(&q, &state.mass, y, &mut hv);
+                for (((o, y), hv), m) in out.iter_mut().zip(y).zip(&hv).zip(&state.mass) {
+                    *o = y + theta_h * theta_h * hv / m;
+                }
+            };
+            let rhs: Vec<f64> = r.iter().map(|r| -r).collect();
+            let d = gmres(
+                jacobian,
+                &rhs,
+                self.krylov_tolerance,
+                self.max_krylov_iterations,
+            );
+
+            let current = norm(&r);
+            let mut step = 1.0;
+            let mut improved = false;
+            for _ in 0..=Self::MAX_BACKTRACKS {
+                for ((t, x), d) in trial.iter_mut().zip(&x).zip(&d) {
+                    *t = x + step * d;
+                }
+                Self::residual(state, laws, &trial, theta_h, &mut trial_r);
+                if norm(&trial_r) < current {
+                    improved = true;
+                    break;
+                }
+                step *= 0.5;
+            }
+            if !improved {
+                // Newton has stalled; keep the best iterate instead of a worse one.
+                break;
+            }
+            x.copy_from_slice(&trial);
+            r.copy_from_slice(&trial_r);
+        }
+
+        if !self.converged {
+            self.failures.push(ConvergenceFailure {
+                t: state.t,
+                dt,
+                iterations: self.iterations,
+                residual: max_abs(r.iter().copied()),
+            });
+        }
+        x
+    }
+
+    /// Shared step: `q1 = q0 + h x`, with `v1 = x` (`θ = 1`) or `v1 = 2x - v0`
+    /// (`θ = 1/2`). Rotations and directors are advanced as in `VelocityVerlet`.
+    fn step(
+        &mut self,
+        state: &mut PhaseSpace,
+        laws: &LawRegistry,
+        constraints: &[Box<dyn Constraint>],
+        dt: f64,
+        theta: f64,
+    ) {
+        laws.sync(state);
+        let x = self.solve(state, laws, dt, theta);
+
+        for ((q, v), x) in state.q.iter_mut().zip(state.v.iter_mut()).zip(&x) {
+            *q += dt * x;
+            *v = (x - (1.0 - theta) * *v) / theta;
+        }
+
+        advance_rotations(state, laws, dt);
+
+        for c in constraints {
+            c.project(state);
+        }
+
+        state.t += dt;
+    }
+}
+
+/// Backward (implicit) Euler: `v1 = v0 - h M⁻¹ ∇V(q1)`, `q1 = q0 + h v1`.
+///
+/// First order and unconditionally stable, at the price of numerical damping:
+/// stiff modes it cannot resolve are dissipated instead of blowing up.
+#[derive(Debug, Clone, Default)]
+pub struct ImplicitEuler {
+    pub solver: NewtonKrylov,
+}
+
+impl ImplicitEuler {
+    pub fn new(tolerance: f64, max_iterations: usize) -> Self {
+        Self {
+            solver: NewtonKrylov::new(tolerance, max_iterations),
+        }
+    }
+}
+
+impl Integrator for ImplicitEuler {
+    fn step(
+        &mut self,
+        state: &mut PhaseSpace,
+        laws: &LawRegistry,
+        constraints: &[Box<dyn Constraint>],
+        dt: f64,
+    ) {
+        self.solver.step(state, laws, constraints, dt, 1.0);
+    }
+}
+
+/// Implicit midpoint rule: `v̄ = (v0 + v1) / 2`, `q1 = q0 + h v̄`,
+/// `v1 = v0 - h M⁻¹ ∇V(q0 + h v̄ / 2)`.
+///
+/// Second order, symplectic and A-stable: it conserves quadratic invariants (the
+/// energy of harmonic potentials) exactly, so stiff oscillations neither grow nor decay.
+#[derive(Debug, Clone, Default)]
+pub struct ImplicitMidpoint {
+    pub solver: NewtonKrylov,
+}
+
+impl ImplicitMidpoint {
+    pub fn new(tolerance: f64, max_iterations: usize) -> Self {
+        Self {
+            solver: NewtonKrylov::new(tolerance, max_iterations),
+        }
+    }
+}
+
+impl Integrator for ImplicitMidpoint {
+    fn step(
+        &mut self,
+        state: &mut PhaseSpace,
+        laws: &LawRegistry,
+        constraints: &[Box<dyn Constraint>],
+        dt: f64,
+    ) {
+        self.solver.step(state, laws, constraints, dt, 0.5);
+    }
+}
@@ -1,9 +1,19 @@
-use crate::core::math::ad::Dual;
-use crate::core::state::PhaseSpace;
+use crate::core::state::{Block, PhaseSpace};
 use crate::laws::registry::LawRegistry;
+use glam::DVec3;
 
+pub mod composition;
 pub mod constraints;
+pub mod implicit;
+pub mod lie_group;
+pub mod runge_kutta;
+pub mod variational;
+pub use composition::Composition;
 use constraints::Constraint;
+pub use implicit::{ConvergenceFailure, ImplicitEuler, ImplicitMidpoint, NewtonKrylov};
+pub use lie_group::LieGroupVariational;
+pub use runge_kutta::{DormandPrince, RungeKutta4};
+pub use variational::{Kinetic, Quadratic, Quadrature, VariationalIntegrator};
 
 pub trait Integrator {
     fn step(
@@ -15,6 +25,43 @@ pub trait Integrator {
     );
 }
 
+/// Evaluates the conservative forces F = -dV/dq at the current configuration.
+fn compute_forces(state: &PhaseSpace, laws: &LawRegistry, forces: &mut [f64]) {
+    laws.gradient(&state.q, &state.mass, forces);
+    for f in forces.iter_mut() {
+        *f = -*f;
+    }
+}
+
+/// Explicit Euler update of the body angular velocities under Euler's equations
+/// `I dω/dt = τ - ω × (I ω)`, with the body torques `τ` of the orientation
+/// potential at the current orientations (see [`LawRegistry::torques`]).
+fn kick_rotations(state: &mut PhaseSpace, laws: &LawRegistry, dt: f64) {
+    let mut torques = vec![DVec3::ZERO; state.rot.len()];
+    laws.torques(&state.rot, &mut torques);
+    for ((omega, inertia), torque) in state.ang_v.iter_mut().zip(&state.inertia).zip(&torques) {
+        let iw = *omega * *inertia;
+        let w_x_iw = omega.cross(iw);
+        let d_omega = (*torque - w_x_iw) / *inertia;
+
+        *omega += d_omega * dt;
+    }
+}
+
+/// Kicks the angular velocities and drifts every block that is not Euclidean
+/// (rigid bodies and directors), as [`VelocityVerlet`] does, for integrators that
+/// only advance the particles themselves.
+fn advance_rotations(state: &mut PhaseSpace, laws: &LawRegistry, dt: f64) {
+    kick_rotations(state, laws, dt);
+    state.drift_where(dt, |block| !matches!(block, Block::Euclidean { .. }));
+}
+
+/// Symplectic Euler: kick `v += h F / m`, then drift every block with the new
+/// velocity (`x <- retract(x, v h)`).
+///
+/// Rigid bodies and directors are blocks too, so their angular velocities get the
+/// same kick as in [`VelocityVerlet`] and their orientations and directions advance
+/// in the drift.
 pub struct SymplecticEuler;
 
 impl Integrator for SymplecticEuler {
@@ -25,25 +72,20 @@ impl Integrator for SymplecticEuler {
         constraints: &[Box<dyn Constraint>],
         dt: f64,
     ) {
+        laws.sync(state);
         let n = state.dof;
         let mut forces = vec![0.0; n];
 
         // 1. Compute Gradients (Forces) F = -dV/dq
-        let mut q_dual: Vec<Dual> = state.q.iter().map(|&x| Dual::constant(x)).collect();
-
-        for i in 0..n {
-            q_dual[i].der = 1.0;
-            let potential = laws.potential(&q_dual, &state.mass);
-            forces[i] = -potential.der;
-            q_dual[i].der = 0.0;
-        }
+        compute_forces(state, laws, &mut forces);
 
-        // 2. Symplectic Euler Step
+        // 2. Symplectic Euler Step: kick, then drift every block with the new velocity
         for (i, f) in forces.iter().enumerate().take(n) {
             let acceleration = f / state.mass[i];
             state.v[i] += acceleration * dt;
-            state.q[i] += state.v[i] * dt;
         }
+        kick_rotations(state, laws, dt);
+        state.drift(dt);
 
         // 3. Constraints
         for c in constraints {
@@ -64,18 +106,13 @@ impl Integrator for VelocityVerlet {
         constraints: &[Box<dyn Constraint>],
         dt: f64,
     ) {
+        laws.sync(state);
         let n = state.dof;
 
         let mut forces = vec![0.0; n];
-        let mut inputs: Vec<Dual> = state.q.iter().map(|&x| Dual::constant(x)).collect();
 
         // Compute Forces F(t)
-        for (i, force) in forces.iter_mut().enumerate().take(n) {
-            inputs[i].der = 1.0;
-            let potential = laws.potential(&inputs, &state.mass);
-            *force = -potential.der;
-            inputs[i].der = 0.0;
-        }
+        compute_forces(state, laws, &mut forces);
 
         // 1. Half Kick v += 0.5 * a * dt
         for (i, v) in state.v.iter_mut().enumerate().take(n) {
@@ -83,10 +120,12 @@ impl Integrator for VelocityVerlet {
             *v += 0.5 * a * dt;
         }
 
-        // 2. Drift x += v * dt
-        for (i, q) in state.q.iter_mut().enumerate().take(n) {
-            *q += state.v[i] * dt;
-        }
+        // Rigid body rotation (splitting method): the gyroscopic term and the torques
+        // do not depend on q, so the angular velocity gets its full kick before the drift.
+        kick_rotations(state, laws, dt);
+
+        // 2. Drift x <- retract(x, v * dt), block by block
+        state.drift(dt);
 
         // --- Constraints Projection ---
         for c in constraints {
@@ -94,16 +133,7 @@ impl Integrator for VelocityVerlet {
         }
 
         // 3. Compute Forces F(t+dt) with new positions
-        for (i, input) in inputs.iter_mut().enumerate().take(n) {
-            input.val = state.q[i]; // Update positions in dual vector
-        }
-
-        for (i, force) in forces.iter_mut().enumerate().take(n) {
-            inputs[i].der = 1.0;
-            let potential = laws.potential(&inputs, &state.mass);
-            *force = -potential.der;
-            inputs[i].der = 0.0;
-        }
+        compute_forces(state, laws, &mut forces);
 
         // 4. Half Kick v += 0.5 * new_a * dt
         for (i, v) in state.v.iter_mut().enumerate().take(n) {
@@ -111,27 +141,6 @@ impl Integrator for VelocityVerlet {
             *v += 0.5 * a * dt;
         }
 
-        // --- Rigid Body Rotation Step (Splitting Method) ---
-        let rb_count = state.rot.len();
-        if rb_count > 0 {
-            use crate::core::geometry::{Manifold, SO3};
-
-            for (omega, (inertia, rot)) in state
-                .ang_v
-                .iter_mut()
-                .zip(state.inertia.iter().zip(state.rot.iter_mut()))
-            {
-                let iw = *omega * *inertia;
-                let w_x_iw = omega.cross(iw);
-                let d_omega = -w_x_iw / *inertia;
-
-                *omega += d_omega * dt;
-
-                let delta_rot = *omega * dt;
-                *rot = SO3::retract(*rot, delta_rot);
-            }
-        }
-
         state.t += dt;
     }
 }
@@ -0,0 +1,338 @@
+use super::Integrator;
+use super::constraints::Constraint;
+use crate::core::geometry::{LieGroup, Manifold, SO3};
+use crate::core::state::{Block, PhaseSpace};
+use crate::laws::registry::LawRegistry;
+use glam::DVec3;
+
+/// Explicit Runge–Kutta methods integrate the first-order system
+/// `y = (q, v, θ, ω)` with `q' = v`, `v' = -∇V / m`, `θ' = J_r(θ)⁻¹ ω` and Euler's
+/// equations `I ω' = τ - ω × I ω`, with the torques `τ` of the orientation potential.
+/// `θ` is the body-frame rotation accumulated over the step (zero at the start), so
+/// orientations end at `retract(rot, θ)`; the inverse right Jacobian of SO(3) turns
+/// the body angular velocity into the rate of that rotation vector.
+///
+/// Laws only see `q`, so directors feel no force and simply follow their great
+/// circles (one exact [`Block::retract`] per step).
+struct FirstOrder {
+    /// Translational degrees of freedom.
+    n: usize,
+    /// Rigid bodies.
+    bodies: usize,
+}
+
+impl FirstOrder {
+    fn new(state: &PhaseSpace) -> Self {
+        Self {
+            n: state.dof,
+            bodies: state.rot.len(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        2 * self.n + 6 * self.bodies
+    }
+
+    fn pack(&self, state: &PhaseSpace) -> Vec<f64> {
+        let mut y = Vec::with_capacity(self.len());
+        y.extend_from_slice(&state.q);
+        y.extend_from_slice(&state.v);
+        y.resize(2 * self.n + 3 * self.bodies, 0.0);
+        y.extend(state.ang_v.iter().flat_map(|w| w.to_array()));
+        y
+    }
+
+    /// `out = y'`.
+    fn derivative(&self, state: &PhaseSpace, laws: &LawRegistry, y: &[f64], out: &mut [f64]) {
+        let (n, b) = (self.n, self.bodies);
+        let (q, rest) = y.split_at(n);
+        let (v, omega) = (&rest[..n], &rest[n + 3 * b..]);
+
+        out[..n].copy_from_slice(v);
+        let accel = &mut out[n..2 * n];
+        accel.fill(0.0);
+        laws.gradient(q, &state.mass, accel);
+        for (a, m) in accel.iter_mut().zip(&state.mass) {
+            *a = -*a / m;
+        }
+
+        let mut rot = Vec::with_capacity(b);
+        for i in 0..b {
+            let theta = DVec3::from_slice(&rest[n + 3 * i..]);
+            let w = DVec3::from_slice(&omega[3 * i..]);
+            (SO3::right_jacobian_inv(theta) * w).write_to_slice(&mut out[2 * n + 3 * i..]);
+            rot.push(SO3::retract(state.rot[i], theta));
+        }
+        let mut torques = vec![DVec3::ZERO; b];
+        laws.torques(&rot, &mut torques);
+        for (i, (inertia, torque)) in state.inertia.iter().zip(&torques).enumerate() {
+            let w = DVec3::from_slice(&omega[3 * i..]);
+            let dw = (*torque - w.cross(w * *inertia)) / *inertia;
+            dw.write_to_slice(&mut out[2 * n + 3 * b + 3 * i..]);
+        }
+    }
+
+    /// Evaluates the stage derivatives `k[i] = f(y0 + h Σ_j a[i][j] k[j])`.
+    fn stages(
+        &self,
+        state: &PhaseSpace,
+        laws: &LawRegistry,
+        y0: &[f64],
+        h: f64,
+        a: &[&[f64]],
+        k: &mut [Vec<f64>],
+    ) {
+        let mut y = vec![0.0; y0.len()];
+        for i in 0..k.len() {
+            y.copy_from_slice(y0);
+            for (j, &aij) in a[i].iter().enumerate() {
+                if aij != 0.0 {
+                    for (y, k) in y.iter_mut().zip(&k[j]) {
+                        *y += h * aij * k;
+                    }
+                }
+            }
+            self.derivative(state, laws, &y, &mut k[i]);
+        }
+    }
+
+    /// `y0 + h Σ b[i] k[i]`.
+    fn combine(y0: &[f64], h: f64, b: &[f64], k: &[Vec<f64>]) -> Vec<f64> {
+        let mut y = y0.to_vec();
+        for (bi, ki) in b.iter().zip(k) {
+            if *bi != 0.0 {
+                for (y, k) in y.iter_mut().zip(ki) {
+                    *y += h * bi * k;
+                }
+            }
+        }
+        y
+    }
+
+    /// Writes `y` back and advances the parts that are not in `y`.
+    fn unpack(&self, state: &mut PhaseSpace, y: &[f64], h: f64) {
+        let (n, b) = (self.n, self.bodies);
+        state.q.copy_from_slice(&y[..n]);
+        state.v.copy_from_slice(&y[n..2 * n]);
+        for i in 0..b {
+            let theta = DVec3::from_slice(&y[2 * n + 3 * i..]);
+            state.rot[i] = SO3::retract(state.rot[i], theta);
+            state.ang_v[i] = DVec3::from_slice(&y[2 * n + 3 * b + 3 * i..]);
+        }
+        state.drift_where(h, |block| matches!(block, Block::Director { .. }));
+        state.t += h;
+    }
+}
+
+/// The classical fourth-order Runge–Kutta method.
+///
+/// Not symplectic: on a Kepler orbit the energy drifts steadily (slowly, but without
+/// bound), where [`VelocityVerlet`](super::VelocityVerlet) keeps it bounded.
+pub struct RungeKutta4;
+
+impl RungeKutta4 {
+    const A: [&[f64]; 4] = [&[], &[0.5], &[0.0, 0.5], &[0.0, 0.0, 1.0]];
+    const B: [f64; 4] = [1.0 / 6.0, 1.0 / 3.0, 1.0 / 3.0, 1.0 / 6.0];
+}
+
+impl Integrator for RungeKutta4 {
+    fn step(
+        &mut self,
+        state: &mut PhaseSpace,
+        laws: &LawRegistry,
+        constraints: &[Box<dyn Constraint>],
+        dt: f64,
+    ) {
+        laws.sync(state);
+        let system = FirstOrder::new(state);
+        let y0 = system.pack(state);
+        let mut k = vec![vec![0.0; y0.len()]; 4];
+        system.stages(state, laws, &y0, dt, &Self::A, &mut k);
+        let y1 = FirstOrder::combine(&y0, dt, &Self::B, &k);
+        system.unpack(state, &y1, dt);
+
+        for c in constraints {
+            c.project(state);
+        }
+    }
+}
+
+/// Adaptive embedded Runge–Kutta 5(4) of Dormand and Prince.
+///
+/// Each [`step`](Integrator::step) advances exactly `dt`, split into as many
+/// substeps as the tolerances require. A substep is rejected and retried smaller
+/// when the scaled error estimate
+/// `sqrt(mean((e_i / (atol + rtol * max(|y0_i|, |y1_i|)))²))` exceeds one;
+/// the next substep size is predicted from the error of the last one.
+/// Constraints are projected after every accepted substep.
+#[derive(Debug, Clone)]
+pub struct DormandPrince {
+    pub rtol: f64,
+    pub atol: f64,
+    /// Substeps are never made smaller than this; a substep at the floor is
+    /// accepted even if it misses the tolerance.
+    pub min_step: f64,
+    pub max_step: f64,
+    /// Size of the next substep, carried over between calls. `None` starts from `dt`.
+    pub h: Option<f64>,
+    /// Substeps taken so far.
+    pub accepted: usize,
+    /// Substeps thrown away so far.
+    pub rejected: usize,
+}
+
+impl Default for DormandPrince {
+    fn default() -> Self {
+        Self::new(1e-6, 1e-9)
+    }
+}
+
+impl DormandPrince {
+    const SAFETY: f64 = 0.9;
+    const MIN_SHRINK: f64 = 0.2;
+    const MAX_GROWTH: f64 = 5.0;
+
+    const A: [&[f64]; 7] = [
+        &[],
+        &[1.0 / 5.0],
+        &[3.0 / 40.0, 9.0 / 40.0],
+        &[44.0 / 45.0, -56.0 / 15.0, 32.0 / 9.0],
+        &[
+            19372.0 / 6561.0,
+            -25360.0 / 2187.0,
+            64448.0 / 6561.0,
+            -212.0 / 729.0,
+        ],
+        &[
+            9017.0 / 3168.0,
+            -355.0 / 33.0,
+            46732.0 / 5247.0,
+            49.0 / 176.0,
+            -5103.0 / 18656.0,
+        ],
+        &[
+            35.0 / 384.0,
+            0.0,
+            500.0 / 1113.0,
+            125.0 / 192.0,
+            -2187.0 / 6784.0,
+            11.0 / 84.0,
+        ],
+    ];
+    /// Fifth-order weights (the solution that is kept).
+    const B: [f64; 7] = [
+        35.0 / 384.0,
+        0.0,
+        500.0 / 1113.0,
+        125.0 / 192.0,
+        -2187.0 / 6784.0,
+        11.0 / 84.0,
+        0.0,
+    ];
+    /// Embedded fourth-order weights, only used for the error estimate.
+    const B_STAR: [f64; 7] = [
+        5179.0 / 57600.0,
+        0.0,
+        7571.0 / 16695.0,
+        393.0 / 640.0,
+        -92097.0 / 339200.0,
+        187.0 / 2100.0,
+        1.0 / 40.0,
+    ];
+
+    pub fn new(rtol: f64, atol: f64) -> Self {
+        Self {
+            rtol,
+            atol,
+            min_step: 1e-12,
+            max_step: f64::INFINITY,
+            h: None,
+            accepted: 0,
+            rejected: 0,
+        }
+    }
+
+    fn error_norm(&self, y0: &[f64], y1: &[f64], err: &[f64]) -> f64 {
+        if err.is_empty() {
+            return 0.0;
+        }
+        let sum: f64 = y0
+            .iter()
+            .zip(y1)
+            .zip(err)
+            .map(|((a, b), e)| {
+                let scale = self.atol + self.rtol * a.abs().max(b.abs());
+                (e / scale).powi(2)
+            })
+            .sum();
+        (sum / err.len() as f64).sqrt()
+    }
+}
+
+impl Integrator for DormandPrince {
+    fn step(
+        &mut self,
+        state: &mut PhaseSpace,
+        laws: &LawRegistry,
+        constraints: &[Box<dyn Constraint>],
+        dt: f64,
+    ) {
+        laws.sync(state);
+        let end = state.t + dt;
+        let mut remaining = dt;
+        let mut h = self.h.unwrap_or(dt).min(self.max_step);
+        let mut k = Vec::new();
+
+        while remaining > 0.0 {
+            let system = FirstOrder::new(state);
+            let y0 = system.pack(state);
+            k.resize(7, Vec::new());
+            k.iter_mut().for_each(|k| k.resize(y0.len(), 0.0));
+
+            // Land exactly on the end of the interval.
+            let last = h >= remaining;
+            let step = if last { remaining } else { h };
+
+            system.stages(state, laws, &y0, step, &Self::A, &mut k);
+            let y1 = FirstOrder::combine(&y0, step, &Self::B, &k);
+            let err: Vec<f64> = (0..y0.len())
+                .map(|i| {
+                    (0..7)
+                        .map(|s| step * (Self::B[s] - Self::B_STAR[s]) * k[s][i])
+                        .sum()
+                })
+                .collect();
+            let norm = self.error_norm(&y0, &y1, &err);
+
+            let factor = if !norm.is_finite() {
+                Self::MIN_SHRINK
+            } else if norm == 0.0 {
+                Self::MAX_GROWTH
+            } else {
+                (Self::SAFETY * norm.powf(-0.2)).clamp(Self::MIN_SHRINK, Self::MAX_GROWTH)
+            };
+
+            if norm <= 1.0 || step <= self.min_step {
+                system.unpack(state, &y1, step);
+                for c in constraints {
+                    c.project(state);
+                }
+                self.accepted += 1;
+                remaining = end - state.t;
+                // Do not chase rounding error with a vanishing extra substep.
+                if last || remaining <= 1e-12 * dt {
+                    remaining = 0.0;
+                }
+                // A short final substep says little about the next one.
+                if !last || factor < 1.0 {
+                    h = (step * factor).clamp(self.min_step, self.max_step);
+                }
+            } else {
+                self.rejected += 1;
+                h = (step * factor.min(1.0)).max(self.min_step);
+            }
+        }
+        state.t = end;
+        self.h = Some(h);
+    }
+}
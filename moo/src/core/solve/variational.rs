@@ -0,0 +1,254 @@
+use super::constraints::Constraint;
+use super::{Integrator, advance_rotations};
+use crate::core::math::linalg::{max_abs, solve_dense};
+use crate::core::math::{HyperDual, Real};
+use crate::core::state::PhaseSpace;
+use crate::laws::registry::LawRegistry;
+
+/// Kinetic energy `T(q, v)` of the translational degrees of freedom.
+///
+/// Written against [`Real`] like [`Law::potential`](crate::laws::registry::Law::potential),
+/// so the integrator can differentiate it exactly. It may depend on `q`
+/// (a configuration-dependent mass matrix), which makes the system non-separable.
+pub trait Kinetic {
+    fn kinetic<T: Real>(&self, q: &[T], v: &[T], mass: &[f64]) -> T;
+}
+
+/// `T = Σ m_i v_i² / 2`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Quadratic;
+
+impl Kinetic for Quadratic {
+    fn kinetic<T: Real>(&self, _q: &[T], v: &[T], mass: &[f64]) -> T {
+        let mut t = T::constant(0.0);
+        for (&v, &m) in v.iter().zip(mass) {
+            t = t + v * v * (0.5 * m);
+        }
+        t
+    }
+}
+
+/// How the action over one step is approximated, with `v̄ = (q1 - q0) / h`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Quadrature {
+    /// `L_d = h L((q0 + q1) / 2, v̄)`. Reduces to the implicit midpoint rule.
+    #[default]
+    Midpoint,
+    /// `L_d = h (L(q0, v̄) + L(q1, v̄)) / 2`. Reduces to Störmer–Verlet for a
+    /// quadratic kinetic energy.
+    Trapezoidal,
+}
+
+/// Integrator derived from a discrete Lagrangian
+/// `L_d(q0, q1) ≈ ∫ T(q, v) - V(q) dt` over one step.
+///
+/// Each step solves the discrete Euler–Lagrange equations in momentum form,
+///
+/// `p0 = -D1 L_d(q0, q1)`,  `p1 = D2 L_d(q0, q1)`,
+///
+/// for `q1` by Newton iteration, with `p = ∂T/∂v` converting to and from the
+/// stored velocities. The resulting map is symplectic and conserves the momentum
+/// of every symmetry that `T` and `V` share, for separable and non-separable
+/// kinetic energies alike.
+///
+/// The derivatives of `L_d` are exact (hyper-dual numbers); a Newton iteration
+/// costs `dof²` evaluations of the Lagrangian plus a dense `dof × dof` solve, so
+/// this is meant for small systems. Rigid bodies and directors are not part of the
+/// Lagrangian and advance as in [`VelocityVerlet`](super::VelocityVerlet).
+#[derive(Debug, Clone)]
+pub struct VariationalIntegrator<K = Quadratic> {
+    pub kinetic: K,
+    pub quadrature: Quadrature,
+    /// Newton stops once no coordinate moves by more than
+    /// `tolerance * (1 + max |q1|)`.
+    pub tolerance: f64,
+    pub max_iterations: usize,
+    /// Newton iterations used by the last step.
+    pub iterations: usize,
+    /// Whether the last step met the tolerance. If not, the last iterate is kept.
+    pub converged: bool,
+}
+
+impl Default for VariationalIntegrator {
+    fn default() -> Self {
+        Self::new(Quadratic, Quadrature::Midpoint)
+    }
+}
+
+impl<K: Kinetic> VariationalIntegrator<K> {
+    pub fn new(kinetic: K, quadrature: Quadrature) -> Self {
+        Self {
+            kinetic,
+            quadrature,
+            tolerance: 1e-12,
+            max_iterations: 50,
+            iterations: 0,
+            converged: true,
+        }
+    }
+
+    /// `L(q, v) = T(q, v) - V(q)`.
+    fn lagrangian(
+        &self,
+        laws: &LawRegistry,
+        q: &[HyperDual],
+        v: &[HyperDual],
+        mass: &[f64],
+    ) -> HyperDual {
+        self.kinetic.kinetic(q, v, mass) - laws.potential(q, mass)
+    }
+
+    fn discrete_lagrangian(
+        &self,
+        laws: &LawRegistry,
+        q0: &[HyperDual],
+        q1: &[HyperDual],
+        mass: &[f64],
+        h: f64,
+    ) -> HyperDual {
+        let v: Vec<HyperDual> = q0.iter().zip(q1).map(|(&a, &b)| (b - a) / h).collect();
+        match self.quadrature {
+            Quadrature::Midpoint => {
+                let mid: Vec<HyperDual> = q0.iter().zip(q1).map(|(&a, &b)| (a + b) * 0.5).collect();
+                self.lagrangian(laws, &mid, &v, mass) * h
+            }
+            Quadrature::Trapezoidal => {
+                (self.lagrangian(laws, q0, &v, mass) + self.lagrangian(laws, q1, &v, mass))
+                    * (0.5 * h)
+            }
+        }
+    }
+
+    /// `p = ∂T/∂v (q, v)`.
+    fn momentum(&self, q: &[f64], v: &[f64], mass: &[f64]) -> Vec<f64> {
+        let q = constants(q);
+        let mut v = constants(v);
+        (0..v.len())
+            .map(|j| {
+                v[j].e1 = 1.0;
+                let p = self.kinetic.kinetic(&q, &v, mass).e1;
+                v[j].e1 = 0.0;
+                p
+            })
+            .collect()
+    }
+
+    /// Solves `∂T/∂v (q, v) = p` for `v`, starting from `guess`. The flag tells
+    /// whether Newton met the tolerance.
+    fn velocity(&self, q: &[f64], p: &[f64], mass: &[f64], guess: &[f64]) -> (Vec<f64>, bool) {
+        let n = p.len();
+        let q = constants(q);
+        let mut v = constants(guess);
+        let mut residual = vec![0.0; n];
+        let mut jacobian = vec![0.0; n * n];
+        let mut converged = false;
+        for _ in 0..self.max_iterations {
+            for i in 0..n {
+                v[i].e1 = 1.0;
+                for j in 0..n {
+                    v[j].e2 = 1.0;
+                    let t = self.kinetic.kinetic(&q, &v, mass);
+                    v[j].e2 = 0.0;
+                    residual[i] = t.e1 - p[i];
+                    jacobian[i * n + j] = t.e12;
+                }
+                v[i].e1 = 0.0;
+            }
+            if !solve_dense(&mut jacobian, &mut residual) {
+                break;
+            }
+            let mut change = 0.0f64;
+            for (v, dv) in v.iter_mut().zip(&residual) {
+                v.val -= dv;
+                change = change.max(dv.abs());
+            }
+            if change <= self.tolerance * (1.0 + max_abs(v.iter().map(|v| v.val))) {
+                converged = true;
+                break;
+            }
+        }
+        (v.iter().map(|v| v.val).collect(), converged)
+    }
+}
+
+impl<K: Kinetic> Integrator for VariationalIntegrator<K> {
+    fn step(
+        &mut self,
+        state: &mut PhaseSpace,
+        laws: &LawRegistry,
+        constraints: &[Box<dyn Constraint>],
+        dt: f64,
+    ) {
+        laws.sync(state);
+        let n = state.dof;
+        let mass = state.mass.clone();
+        let p0 = self.momentum(&state.q, &state.v, &mass);
+
+        // Newton on p0 + D1 L_d(q0, q1) = 0, starting from an explicit drift.
+        let mut q0 = constants(&state.q);
+        let mut q1: Vec<HyperDual> = state
+            .q
+            .iter()
+            .zip(&state.v)
+            .map(|(&q, &v)| HyperDual::constant(q + v * dt))
+            .collect();
+        let mut residual = vec![0.0; n];
+        let mut jacobian = vec![0.0; n * n];
+        self.iterations = 0;
+        self.converged = false;
+        while self.iterations < self.max_iterations {
+            self.iterations += 1;
+            for i in 0..n {
+                q0[i].e1 = 1.0;
+                for j in 0..n {
+                    q1[j].e2 = 1.0;
+                    let l = self.discrete_lagrangian(laws, &q0, &q1, &mass, dt);
+                    q1[j].e2 = 0.0;
+                    residual[i] = p0[i] + l.e1;
+                    jacobian[i * n + j] = l.e12;
+                }
+                q0[i].e1 = 0.0;
+            }
+            if !solve_dense(&mut jacobian, &mut residual) {
+                break;
+            }
+            let mut change = 0.0f64;
+            for (q, dq) in q1.iter_mut().zip(&residual) {
+                q.val -= dq;
+                change = change.max(dq.abs());
+            }
+            if change <= self.tolerance * (1.0 + max_abs(q1.iter().map(|q| q.val))) {
+                self.converged = true;
+                break;
+            }
+        }
+
+        // p1 = D2 L_d(q0, q1).
+        let p1: Vec<f64> = (0..n)
+            .map(|j| {
+                q1[j].e1 = 1.0;
+                let p = self.discrete_lagrangian(laws, &q0, &q1, &mass, dt).e1;
+                q1[j].e1 = 0.0;
+                p
+            })
+            .collect();
+        let q1: Vec<f64> = q1.iter().map(|q| q.val).collect();
+        let guess: Vec<f64> = q1.iter().zip(&state.q).map(|(b, a)| (b - a) / dt).collect();
+        let (v1, converged) = self.velocity(&q1, &p1, &mass, &guess);
+        self.converged &= converged;
+        state.q.copy_from_slice(&q1);
+        state.v.copy_from_slice(&v1);
+
+        advance_rotations(state, laws, dt);
+
+        for c in constraints {
+            c.project(state);
+        }
+
+        state.t += dt;
+    }
+}
+
+fn constants(x: &[f64]) -> Vec<HyperDual> {
+    x.iter().map(|&x| HyperDual::constant(x)).collect()
+}
@@ -0,0 +1,198 @@
+use glam::{DVec3, Vec4};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Conventional channel names, so independent laws and probes agree on them.
+pub mod names {
+    /// Electric charge (`f64`).
+    pub const CHARGE: &str = "charge";
+    /// Temperature (`f64`).
+    pub const TEMPERATURE: &str = "temperature";
+    /// Material or phase id (`u32`).
+    pub const MATERIAL: &str = "material";
+    /// Display color, RGBA (`Vec4`).
+    pub const COLOR: &str = "color";
+    /// Time since emission (`f64`).
+    pub const AGE: &str = "age";
+    /// Free-form user bits (`u32`).
+    pub const TAG: &str = "tag";
+}
+
+/// One per-particle attribute column, with the value given to new particles.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Channel {
+    Scalar { values: Vec<f64>, default: f64 },
+    Index { values: Vec<u32>, default: u32 },
+    Vector { values: Vec<DVec3>, default: DVec3 },
+    Color { values: Vec<Vec4>, default: Vec4 },
+}
+
+/// Element types that can be stored in a [`Channel`].
+pub trait AttributeType: Copy + 'static {
+    fn channel(default: Self, len: usize) -> Channel;
+    fn values(channel: &Channel) -> Option<&Vec<Self>>;
+    fn values_mut(channel: &mut Channel) -> Option<&mut Vec<Self>>;
+}
+
+macro_rules! attribute_type {
+    ($ty:ty, $variant:ident) => {
+        impl AttributeType for $ty {
+            fn channel(default: Self, len: usize) -> Channel {
+                Channel::$variant {
+                    values: vec![default; len],
+                    default,
+                }
+            }
+
+            fn values(channel: &Channel) -> Option<&Vec<Self>> {
+                match channel {
+                    Channel::$variant { values, .. } => Some(values),
+                    _ => None,
+                }
+            }
+
+            fn values_mut(channel: &mut Channel) -> Option<&mut Vec<Self>> {
+                match channel {
+                    Channel::$variant { values, .. } => Some(values),
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+attribute_type!(f64, Scalar);
+attribute_type!(u32, Index);
+attribute_type!(DVec3, Vector);
+attribute_type!(Vec4, Color);
+
+impl Channel {
+    pub fn len(&self) -> usize {
+        match self {
+            Channel::Scalar { values, .. } => values.len(),
+            Channel::Index { values, .. } => values.len(),
+            Channel::Vector { values, .. } => values.len(),
+            Channel::Color { values, .. } => values.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn resize(&mut self, len: usize) {
+        match self {
+            Channel::Scalar { values, default } => values.resize(len, *default),
+            Channel::Index { values, default } => values.resize(len, *default),
+            Channel::Vector { values, default } => values.resize(len, *default),
+            Channel::Color { values, default } => values.resize(len, *default),
+        }
+    }
+
+    fn retain(&mut self, keep: &[bool]) {
+        fn apply<T>(values: &mut Vec<T>, keep: &[bool]) {
+            let mut flags = keep.iter();
+            values.retain(|_| *flags.next().unwrap_or(&true));
+        }
+        match self {
+            Channel::Scalar { values, .. } => apply(values, keep),
+            Channel::Index { values, .. } => apply(values, keep),
+            Channel::Vector { values, .. } => apply(values, keep),
+            Channel::Color { values, .. } => apply(values, keep),
+        }
+    }
+
+    /// The values packed for a GPU storage buffer: `f32` scalars, `u32` indices,
+    /// and `vec4<f32>` for vectors (w = 0) and colors.
+    pub fn to_gpu_bytes(&self) -> Vec<u8> {
+        match self {
+            Channel::Scalar { values, .. } => {
+                let data: Vec<f32> = values.iter().map(|&x| x as f32).collect();
+                bytemuck::cast_slice(&data).to_vec()
+            }
+            Channel::Index { values, .. } => bytemuck::cast_slice(values).to_vec(),
+            Channel::Vector { values, .. } => {
+                let data: Vec<[f32; 4]> = values
+                    .iter()
+                    .map(|v| v.as_vec3().extend(0.0).to_array())
+                    .collect();
+                bytemuck::cast_slice(&data).to_vec()
+            }
+            Channel::Color { values, .. } => {
+                let data: Vec<[f32; 4]> = values.iter().map(|c| c.to_array()).collect();
+                bytemuck::cast_slice(&data).to_vec()
+            }
+        }
+    }
+}
+
+/// Named per-particle attribute channels, kept the same length as the particle count.
+///
+/// Channels are Structure-of-Arrays like the rest of [`PhaseSpace`](super::PhaseSpace):
+/// `attributes.get::<f64>("charge")` is one contiguous slice indexed by particle.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Attributes {
+    channels: BTreeMap<String, Channel>,
+    len: usize,
+}
+
+impl Attributes {
+    /// Adds (or replaces) a channel filled with `default`, which is also the
+    /// value of particles added later.
+    pub fn insert<T: AttributeType>(&mut self, name: &str, default: T) {
+        self.channels
+            .insert(name.to_string(), T::channel(default, self.len));
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<Channel> {
+        self.channels.remove(name)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.channels.contains_key(name)
+    }
+
+    /// The values of channel `name`, or `None` if it is missing or holds another type.
+    pub fn get<T: AttributeType>(&self, name: &str) -> Option<&[T]> {
+        self.channels
+            .get(name)
+            .and_then(T::values)
+            .map(Vec::as_slice)
+    }
+
+    pub fn get_mut<T: AttributeType>(&mut self, name: &str) -> Option<&mut [T]> {
+        self.channels
+            .get_mut(name)
+            .and_then(T::values_mut)
+            .map(Vec::as_mut_slice)
+    }
+
+    pub fn channel(&self, name: &str) -> Option<&Channel> {
+        self.channels.get(name)
+    }
+
+    /// Channel names in sorted order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.channels.keys().map(String::as_str)
+    }
+
+    /// Number of particles every channel covers.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub(crate) fn resize(&mut self, len: usize) {
+        self.len = len;
+        self.channels.values_mut().for_each(|c| c.resize(len));
+    }
+
+    /// Keeps the entries whose flag in `keep` is set.
+    pub(crate) fn retain(&mut self, keep: &[bool]) {
+        self.channels.values_mut().for_each(|c| c.retain(keep));
+        self.len = keep.iter().filter(|&&k| k).count() + self.len.saturating_sub(keep.len());
+    }
+}
@@ -0,0 +1,186 @@
+use super::PhaseSpace;
+use glam::DVec3;
+
+/// A stable reference to a particle that survives insertions and removals.
+///
+/// Particle *indices* shift when earlier particles are removed; a handle keeps
+/// pointing at the same particle, and stops resolving once that particle is gone
+/// (its slot may be reused, but with a new generation).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParticleHandle {
+    slot: u32,
+    generation: u32,
+}
+
+/// How particle indices moved in one compaction: old index to new index,
+/// `None` for removed particles.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Reindex {
+    pub(super) map: Vec<Option<usize>>,
+}
+
+impl Reindex {
+    /// The new index of the particle that was at `old`, or `None` if it was removed.
+    /// Indices beyond the mapped range (particles added later) are unchanged.
+    pub fn get(&self, old: usize) -> Option<usize> {
+        self.map.get(old).copied().unwrap_or(Some(old))
+    }
+
+    /// Whether any particle moved or disappeared.
+    pub fn is_identity(&self) -> bool {
+        self.map.iter().enumerate().all(|(i, m)| *m == Some(i))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Slot {
+    generation: u32,
+    /// Current particle index, `None` while the slot is free.
+    particle: Option<usize>,
+}
+
+/// Handle bookkeeping for the particles of a [`PhaseSpace`].
+///
+/// All structural changes go through the registry so it can track indices.
+/// Removal preserves the order of the remaining particles and returns a [`Reindex`],
+/// which must be passed on to [`LawRegistry::reindex`](crate::laws::registry::LawRegistry::reindex)
+/// and [`Constraint::reindex`](crate::core::solve::constraints::Constraint::reindex)
+/// so index-based laws (e.g. springs) follow their particles.
+#[derive(Debug, Clone, Default)]
+pub struct ParticleRegistry {
+    slots: Vec<Slot>,
+    /// Slot of each particle, by particle index; `None` for particles added
+    /// without the registry.
+    owners: Vec<Option<u32>>,
+    free: Vec<u32>,
+}
+
+impl ParticleRegistry {
+    /// A registry with one handle for every particle already in `state`.
+    pub fn new(state: &PhaseSpace) -> Self {
+        let mut registry = Self::default();
+        registry.owners.resize(state.particle_count(), None);
+        for i in 0..state.particle_count() {
+            registry.attach(i);
+        }
+        registry
+    }
+
+    /// Number of live handles.
+    pub fn len(&self) -> usize {
+        self.owners.iter().flatten().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.owners.iter().all(Option::is_none)
+    }
+
+    /// Current index of the particle, or `None` if it has been removed.
+    pub fn index(&self, handle: ParticleHandle) -> Option<usize> {
+        let slot = self.slots.get(handle.slot as usize)?;
+        (slot.generation == handle.generation)
+            .then_some(slot.particle)
+            .flatten()
+    }
+
+    pub fn contains(&self, handle: ParticleHandle) -> bool {
+        self.index(handle).is_some()
+    }
+
+    /// Handle of the particle currently at `index`, or `None` if it has none.
+    pub fn handle(&self, index: usize) -> Option<ParticleHandle> {
+        let slot = (*self.owners.get(index)?)?;
+        Some(ParticleHandle {
+            slot,
+            generation: self.slots[slot as usize].generation,
+        })
+    }
+
+    /// Appends a particle with unit mass and radius (an emitter).
+    pub fn insert(
+        &mut self,
+        state: &mut PhaseSpace,
+        position: DVec3,
+        velocity: DVec3,
+    ) -> ParticleHandle {
+        self.track(state);
+        let index = state.particle_count();
+        state.resize(state.dof + state.dim);
+        state.set_position(index, position);
+        state.set_velocity(index, velocity);
+        self.owners.push(None);
+        self.attach(index)
+    }
+
+    /// Removes one particle (a sink), or returns `None` for a stale handle.
+    pub fn remove(&mut self, state: &mut PhaseSpace, handle: ParticleHandle) -> Option<Reindex> {
+        self.contains(handle)
+            .then(|| self.remove_many(state, &[handle]))
+    }
+
+    /// Removes several particles in one compaction pass. Stale handles are ignored.
+    ///
+    /// Particles added to `state` without going through the registry (e.g. with
+    /// [`PhaseSpace::resize`]) have no handle; they are kept and simply compacted.
+    pub fn remove_many(&mut self, state: &mut PhaseSpace, handles: &[ParticleHandle]) -> Reindex {
+        self.track(state);
+        let mut doomed = vec![false; state.particle_count()];
+        for &h in handles {
+            if let Some(i) = self.index(h) {
+                doomed[i] = true;
+            }
+        }
+
+        let reindex = state.retain_particles(|i| !doomed[i]);
+
+        for (old, &slot) in self.owners.iter().enumerate() {
+            let Some(slot) = slot else { continue };
+            let entry = &mut self.slots[slot as usize];
+            entry.particle = reindex.get(old);
+            if entry.particle.is_none() {
+                entry.generation = entry.generation.wrapping_add(1);
+                self.free.push(slot);
+            }
+        }
+        let mut doomed = doomed.into_iter();
+        self.owners.retain(|_| !doomed.next().unwrap_or(false));
+
+        reindex
+    }
+
+    /// Matches `owners` to the particles of `state`, which may have been resized
+    /// behind the registry's back: new particles have no handle, and handles of
+    /// particles cut off the end stop resolving.
+    fn track(&mut self, state: &PhaseSpace) {
+        let count = state.particle_count();
+        for slot in self.owners.iter().skip(count).flatten() {
+            let entry = &mut self.slots[*slot as usize];
+            entry.particle = None;
+            entry.generation = entry.generation.wrapping_add(1);
+            self.free.push(*slot);
+        }
+        self.owners.resize(count, None);
+    }
+
+    fn attach(&mut self, index: usize) -> ParticleHandle {
+        debug_assert!(self.owners[index].is_none());
+        let slot = match self.free.pop() {
+            Some(slot) => {
+                self.slots[slot as usize].particle = Some(index);
+                slot
+            }
+            None => {
+                self.slots.push(Slot {
+                    generation: 0,
+                    particle: Some(index),
+                });
+                (self.slots.len() - 1) as u32
+            }
+        };
+        self.owners[index] = Some(slot);
+        ParticleHandle {
+            slot,
+            generation: self.slots[slot as usize].generation,
+        }
+    }
+}
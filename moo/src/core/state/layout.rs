@@ -0,0 +1,95 @@
+use crate::core::geometry::{Euclidean2, Euclidean3, Manifold, S2, SO3};
+use crate::core::state::PhaseSpace;
+use glam::{DVec2, DVec3};
+use serde::{Deserialize, Serialize};
+
+/// A contiguous piece of the configuration that lives on a single manifold.
+///
+/// The storage stays Structure-of-Arrays (`q`/`v` for vector coordinates,
+/// `rot`/`ang_v` for orientations); a block records which manifold a range of that
+/// storage belongs to, so integrators can move every piece with its own
+/// [`Manifold::retract`] instead of assuming `q += v dt`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Block {
+    /// `count` points of `R^dim` in `q[start..start + count * dim]`, velocities in `v`.
+    Euclidean {
+        dim: usize,
+        start: usize,
+        count: usize,
+    },
+    /// `count` orientations in `rot[start..start + count]` (SO(3)),
+    /// with body angular velocities in `ang_v`.
+    Rotation { start: usize, count: usize },
+    /// `count` unit vectors in `dir[start..start + count]` (S²),
+    /// with tangent velocities in `dir_v`.
+    Director { start: usize, count: usize },
+}
+
+impl Block {
+    /// Number of tangent-space degrees of freedom covered by the block.
+    pub fn tangent_dim(&self) -> usize {
+        match *self {
+            Block::Euclidean { dim, count, .. } => dim * count,
+            Block::Rotation { count, .. } => SO3::dim() * count,
+            Block::Director { count, .. } => S2::dim() * count,
+        }
+    }
+
+    /// Moves every point of the block along its velocity for a time `dt`:
+    /// `x <- retract(x, v dt)`.
+    pub fn retract(&self, state: &mut PhaseSpace, dt: f64) {
+        match *self {
+            Block::Euclidean { dim, start, count } => {
+                let end = start + dim * count;
+                let (q, v) = (&mut state.q[start..end], &state.v[start..end]);
+                match dim {
+                    3 => {
+                        for (q, v) in q.chunks_exact_mut(3).zip(v.chunks_exact(3)) {
+                            let p = Euclidean3::retract(
+                                DVec3::from_slice(q),
+                                DVec3::from_slice(v) * dt,
+                            );
+                            p.write_to_slice(q);
+                        }
+                    }
+                    2 => {
+                        for (q, v) in q.chunks_exact_mut(2).zip(v.chunks_exact(2)) {
+                            let p = Euclidean2::retract(
+                                DVec2::from_slice(q),
+                                DVec2::from_slice(v) * dt,
+                            );
+                            p.write_to_slice(q);
+                        }
+                    }
+                    _ => {
+                        for (q, v) in q.iter_mut().zip(v) {
+                            *q += v * dt;
+                        }
+                    }
+                }
+            }
+            Block::Rotation { start, count } => {
+                let end = start + count;
+                for (rot, omega) in state.rot[start..end]
+                    .iter_mut()
+                    .zip(&state.ang_v[start..end])
+                {
+                    *rot = SO3::retract(*rot, *omega * dt);
+                }
+            }
+            Block::Director { start, count } => {
+                let end = start + count;
+                for (d, w) in state.dir[start..end]
+                    .iter_mut()
+                    .zip(&mut state.dir_v[start..end])
+                {
+                    // Move along the great circle and carry the velocity with it,
+                    // so it stays tangent to the sphere at the new point.
+                    let step = *w * dt;
+                    *w = S2::transport(*d, step, *w);
+                    *d = S2::retract(*d, step);
+                }
+            }
+        }
+    }
+}
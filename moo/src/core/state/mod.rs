@@ -1,4 +1,17 @@
+use crate::core::geometry::se2::Pose2;
+use crate::core::geometry::se3::Pose;
+use crate::core::geometry::so2::SO2;
 use crate::core::math::ad::Dual;
+use serde::{Deserialize, Serialize};
+
+pub mod attributes;
+pub mod handles;
+pub mod layout;
+pub mod snapshot;
+pub use attributes::{AttributeType, Attributes, Channel};
+pub use handles::{ParticleHandle, ParticleRegistry, Reindex};
+pub use layout::Block;
+pub use snapshot::SNAPSHOT_VERSION;
 
 /// Represents the State of the system in Phase Space (q, p).
 ///
@@ -7,11 +20,21 @@ use crate::core::math::ad::Dual;
 /// and one for momenta.
 ///
 /// This is critical for cache coherence when the Integrator iterates over the state.
-#[derive(Debug, Clone, Default)]
+///
+/// Serializable for checkpoints (see [`PhaseSpace::save`]); fields missing from an
+/// older RON checkpoint take their [`Default`] values. Binary checkpoints are read
+/// through a frozen copy of their version's layout instead, so adding a field here
+/// means bumping [`SNAPSHOT_VERSION`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct PhaseSpace {
     /// Dimension of the configuration space (number of degrees of freedom).
     pub dof: usize,
 
+    /// Spatial dimension of a particle or body: 3, or 2 for planar systems.
+    /// Each particle occupies `dim` consecutive entries of `q`, `v` and `mass`.
+    pub dim: usize,
+
     /// Generalized Coordinates (q).
     /// Size: `dof`
     pub q: Vec<f64>,
@@ -27,7 +50,7 @@ pub struct PhaseSpace {
     pub mass: Vec<f64>,
 
     /// Collision/Geometric Radius.
-    /// Size: `dof / 3` (One per particle/body).
+    /// Size: `dof / dim` (One per particle/body).
     pub radius: Vec<f64>,
 
     // --- Rigid Body Extensions ---
@@ -36,31 +59,76 @@ pub struct PhaseSpace {
     /// Indices here might correspond to indices in q/v (if mixed) or be separate.
     /// For Prototype 2, let's assume we can have particles AND rigid bodies.
     /// But simplest is: A rigid body i has center of mass at q[3i..3i+3] and rotation at rot[i].
+    /// Planar bodies (`dim == 2`) rotate about Z only, so `rot[i]` is a Z rotation.
     pub rot: Vec<glam::DQuat>,
 
-    /// Angular Velocities (Body frame).
+    /// Angular Velocities (Body frame). Only the Z component is used when planar.
     pub ang_v: Vec<glam::DVec3>,
 
     /// Inertia Tensor diagonals (Principal moments).
     pub inertia: Vec<glam::DVec3>,
 
+    // --- Director Extensions ---
+    /// Unit vectors on S² (liquid-crystal directors, spins, beads on a sphere).
+    pub dir: Vec<glam::DVec3>,
+
+    /// Director velocities, tangent to the sphere at `dir[i]`.
+    pub dir_v: Vec<glam::DVec3>,
+
+    // --- Attribute Channels ---
+    /// Named per-particle data (charge, temperature, material id, ...), one entry
+    /// per particle in every channel. Resized and compacted along with `q`.
+    pub attributes: Attributes,
+
+    /// The configuration as a list of manifold blocks over the storage above.
+    /// [`PhaseSpace::resize`], [`PhaseSpace::resize_rigid`] and
+    /// [`PhaseSpace::resize_directors`] rebuild the default
+    /// layout (one block each for `q`, `rot` and `dir`).
+    pub blocks: Vec<Block>,
+
     /// Current time of the state snapshot.
     pub t: f64,
 }
 
+impl Default for PhaseSpace {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
 impl PhaseSpace {
+    /// A 3D state with `dof` degrees of freedom.
     pub fn new(dof: usize) -> Self {
-        Self {
+        Self::with_dim(dof, 3)
+    }
+
+    /// A planar state: particles store (x, y) only.
+    pub fn planar(dof: usize) -> Self {
+        Self::with_dim(dof, 2)
+    }
+
+    /// A state whose particles live in `dim` spatial dimensions (2 or 3).
+    pub fn with_dim(dof: usize, dim: usize) -> Self {
+        debug_assert!(dim == 2 || dim == 3, "unsupported spatial dimension {dim}");
+        let mut state = Self {
             dof,
+            dim,
             q: vec![0.0; dof],
             v: vec![0.0; dof],
             mass: vec![1.0; dof],
-            radius: vec![1.0; dof / 3],
+            radius: vec![1.0; dof / dim],
             rot: Vec::new(),
             ang_v: Vec::new(),
             inertia: Vec::new(),
+            dir: Vec::new(),
+            dir_v: Vec::new(),
+            attributes: Attributes::default(),
+            blocks: Vec::new(),
             t: 0.0,
-        }
+        };
+        state.attributes.resize(state.particle_count());
+        state.rebuild_blocks();
+        state
     }
 
     /// Resize the state container (e.g., adding particles).
@@ -69,16 +137,160 @@ impl PhaseSpace {
         self.q.resize(new_dof, 0.0);
         self.v.resize(new_dof, 0.0);
         self.mass.resize(new_dof, 1.0);
-        self.radius.resize(new_dof / 3, 1.0);
+        self.radius.resize(new_dof / self.dim, 1.0);
+        self.attributes.resize(self.particle_count());
+        self.rebuild_blocks();
+    }
+
+    /// Drops every particle for which `keep(index)` is false, preserving the order
+    /// of the rest, and reports where each particle went.
+    ///
+    /// Rigid bodies are the leading particles, so their `rot`, `ang_v` and `inertia`
+    /// entries are compacted alongside, as are the attribute channels.
+    pub fn retain_particles(&mut self, keep: impl FnMut(usize) -> bool) -> Reindex {
+        let dim = self.dim;
+        let rigid = self.rot.len();
+        let keep: Vec<bool> = (0..self.particle_count()).map(keep).collect();
+        let mut map = Vec::with_capacity(keep.len());
+        let mut kept = 0;
+        let mut kept_rigid = 0;
+
+        for (i, &keep_i) in keep.iter().enumerate() {
+            if !keep_i {
+                map.push(None);
+                continue;
+            }
+            map.push(Some(kept));
+            if kept != i {
+                self.q.copy_within(i * dim..(i + 1) * dim, kept * dim);
+                self.v.copy_within(i * dim..(i + 1) * dim, kept * dim);
+                self.mass.copy_within(i * dim..(i + 1) * dim, kept * dim);
+                self.radius[kept] = self.radius[i];
+            }
+            if i < rigid {
+                self.rot[kept_rigid] = self.rot[i];
+                self.ang_v[kept_rigid] = self.ang_v[i];
+                self.inertia[kept_rigid] = self.inertia[i];
+                kept_rigid += 1;
+            }
+            kept += 1;
+        }
+
+        self.rot.truncate(kept_rigid);
+        self.ang_v.truncate(kept_rigid);
+        self.inertia.truncate(kept_rigid);
+        self.attributes.retain(&keep);
+        self.resize(kept * dim);
+
+        Reindex { map }
+    }
+
+    /// Moves every block along its velocity for a time `dt` (the "drift" of a
+    /// splitting integrator), using each block's own retraction.
+    pub fn drift(&mut self, dt: f64) {
+        self.drift_where(dt, |_| true);
+    }
+
+    /// [`drift`](Self::drift) restricted to the blocks selected by `keep`, for
+    /// integrators that advance the other blocks themselves.
+    pub fn drift_where(&mut self, dt: f64, keep: impl Fn(&Block) -> bool) {
+        for i in 0..self.blocks.len() {
+            let block = self.blocks[i];
+            if keep(&block) {
+                block.retract(self, dt);
+            }
+        }
+    }
+
+    /// Total tangent-space dimension over all blocks.
+    pub fn tangent_dim(&self) -> usize {
+        self.blocks.iter().map(Block::tangent_dim).sum()
+    }
+
+    fn rebuild_blocks(&mut self) {
+        self.blocks.clear();
+        if self.dof > 0 {
+            // A flat `q` that does not split into particles is treated as R^dof.
+            let dim = if self.dof.is_multiple_of(self.dim) {
+                self.dim
+            } else {
+                1
+            };
+            self.blocks.push(Block::Euclidean {
+                dim,
+                start: 0,
+                count: self.dof / dim,
+            });
+        }
+        if !self.rot.is_empty() {
+            self.blocks.push(Block::Rotation {
+                start: 0,
+                count: self.rot.len(),
+            });
+        }
+        if !self.dir.is_empty() {
+            self.blocks.push(Block::Director {
+                start: 0,
+                count: self.dir.len(),
+            });
+        }
+    }
+
+    /// Number of particles (or rigid body centers) stored in `q`.
+    pub fn particle_count(&self) -> usize {
+        self.dof / self.dim
     }
 
-    /// Convenience for setting isotropic particle mass across x/y/z DOF.
+    /// Convenience for setting isotropic particle mass across its spatial DOF.
     pub fn set_particle_mass(&mut self, particle_idx: usize, mass: f64) {
-        let base = particle_idx * 3;
-        debug_assert!(base + 2 < self.mass.len());
-        self.mass[base] = mass;
-        self.mass[base + 1] = mass;
-        self.mass[base + 2] = mass;
+        let base = particle_idx * self.dim;
+        debug_assert!(base + self.dim <= self.mass.len());
+        self.mass[base..base + self.dim].fill(mass);
+    }
+
+    /// Adds a per-particle attribute channel filled with `default`.
+    pub fn add_attribute<T: AttributeType>(&mut self, name: &str, default: T) {
+        self.attributes.insert(name, default);
+    }
+
+    /// The values of attribute `name`, if present with element type `T`.
+    pub fn attribute<T: AttributeType>(&self, name: &str) -> Option<&[T]> {
+        self.attributes.get(name)
+    }
+
+    pub fn attribute_mut<T: AttributeType>(&mut self, name: &str) -> Option<&mut [T]> {
+        self.attributes.get_mut(name)
+    }
+
+    /// Position of particle `i`, with `z = 0` for planar states.
+    pub fn position(&self, i: usize) -> glam::DVec3 {
+        self.read_vec(&self.q, i)
+    }
+
+    /// Sets the position of particle `i`. `z` is ignored for planar states.
+    pub fn set_position(&mut self, i: usize, p: glam::DVec3) {
+        let (base, dim) = (i * self.dim, self.dim);
+        self.q[base..base + dim].copy_from_slice(&p.to_array()[..dim]);
+    }
+
+    /// Velocity of particle `i`, with `z = 0` for planar states.
+    pub fn velocity(&self, i: usize) -> glam::DVec3 {
+        self.read_vec(&self.v, i)
+    }
+
+    /// Sets the velocity of particle `i`. `z` is ignored for planar states.
+    pub fn set_velocity(&mut self, i: usize, v: glam::DVec3) {
+        let (base, dim) = (i * self.dim, self.dim);
+        self.v[base..base + dim].copy_from_slice(&v.to_array()[..dim]);
+    }
+
+    fn read_vec(&self, data: &[f64], i: usize) -> glam::DVec3 {
+        let base = i * self.dim;
+        let mut out = glam::DVec3::ZERO;
+        for k in 0..self.dim {
+            out[k] = data[base + k];
+        }
+        out
     }
 
     /// Resize rigid body storage.
@@ -86,6 +298,39 @@ impl PhaseSpace {
         self.rot.resize(count, glam::DQuat::IDENTITY);
         self.ang_v.resize(count, glam::DVec3::ZERO);
         self.inertia.resize(count, glam::DVec3::ONE);
+        self.rebuild_blocks();
+    }
+
+    /// Resize director storage. New directors point along +Z at rest.
+    pub fn resize_directors(&mut self, count: usize) {
+        self.dir.resize(count, glam::DVec3::Z);
+        self.dir_v.resize(count, glam::DVec3::ZERO);
+        self.rebuild_blocks();
+    }
+
+    /// The pose of rigid body `i` (center of mass at `q[dim*i..]`, rotation `rot[i]`).
+    pub fn rigid_pose(&self, i: usize) -> Pose {
+        Pose::new(self.rot[i], self.position(i))
+    }
+
+    /// Writes a pose back into `q` and `rot` for rigid body `i`.
+    pub fn set_rigid_pose(&mut self, i: usize, pose: Pose) {
+        self.set_position(i, pose.trans);
+        self.rot[i] = pose.rot;
+    }
+
+    /// The planar pose of rigid body `i` (angle about Z and the (x, y) center).
+    pub fn planar_pose(&self, i: usize) -> Pose2 {
+        let rot = self.rot[i];
+        let angle = SO2::wrap(2.0 * rot.z.atan2(rot.w));
+        let p = self.position(i);
+        Pose2::new(angle, glam::DVec2::new(p.x, p.y))
+    }
+
+    /// Writes a planar pose back into `q` and `rot` for rigid body `i`.
+    pub fn set_planar_pose(&mut self, i: usize, pose: Pose2) {
+        self.set_position(i, pose.trans.extend(0.0));
+        self.rot[i] = glam::DQuat::from_rotation_z(pose.angle);
     }
 }
 
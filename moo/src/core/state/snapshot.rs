@@ -0,0 +1,239 @@
+use super::{Attributes, Block, PhaseSpace};
+use glam::{DQuat, DVec3};
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::Path;
+
+/// Format version written into every snapshot. Bump it whenever the fields of
+/// [`PhaseSpace`] change: RON snapshots absorb new fields through
+/// `#[serde(default)]`, but binary ones are decoded through a frozen copy of each
+/// version's layout (see `PhaseSpaceV1`).
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// Leading bytes of a binary snapshot, followed by the version as a little-endian `u32`.
+const MAGIC: &[u8; 4] = b"MOO\0";
+
+/// Header of a RON snapshot, read before the state so the version can be checked first.
+#[derive(Deserialize)]
+struct Header {
+    version: u32,
+}
+
+#[derive(Serialize)]
+struct SnapshotRef<'a> {
+    version: u32,
+    state: &'a PhaseSpace,
+}
+
+#[derive(Deserialize)]
+struct Snapshot {
+    state: PhaseSpace,
+}
+
+/// The fields of [`PhaseSpace`] as of snapshot version 1, in serialization order.
+///
+/// bincode is not self-describing (it cannot skip or default a field), so binary
+/// snapshots are decoded into the frozen layout of their own version and converted.
+/// When `PhaseSpace` changes, leave this struct as it is, add a `PhaseSpaceV2` with
+/// the new layout and a conversion, and dispatch on the version in `from_bytes`.
+#[derive(Deserialize)]
+struct PhaseSpaceV1 {
+    dof: usize,
+    dim: usize,
+    q: Vec<f64>,
+    v: Vec<f64>,
+    mass: Vec<f64>,
+    radius: Vec<f64>,
+    rot: Vec<DQuat>,
+    ang_v: Vec<DVec3>,
+    inertia: Vec<DVec3>,
+    dir: Vec<DVec3>,
+    dir_v: Vec<DVec3>,
+    attributes: Attributes,
+    blocks: Vec<Block>,
+    t: f64,
+}
+
+impl From<PhaseSpaceV1> for PhaseSpace {
+    fn from(v1: PhaseSpaceV1) -> Self {
+        Self {
+            dof: v1.dof,
+            dim: v1.dim,
+            q: v1.q,
+            v: v1.v,
+            mass: v1.mass,
+            radius: v1.radius,
+            rot: v1.rot,
+            ang_v: v1.ang_v,
+            inertia: v1.inertia,
+            dir: v1.dir,
+            dir_v: v1.dir_v,
+            attributes: v1.attributes,
+            blocks: v1.blocks,
+            t: v1.t,
+        }
+    }
+}
+
+impl PhaseSpace {
+    /// Writes a checkpoint: RON for a `.ron` extension, the binary form otherwise.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        if is_ron(path) {
+            std::fs::write(path, self.to_ron()?)
+        } else {
+            std::fs::write(path, self.to_bytes()?)
+        }
+    }
+
+    /// Reads a checkpoint written by [`PhaseSpace::save`].
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        if is_ron(path) {
+            Self::from_ron(&std::fs::read_to_string(path)?)
+        } else {
+            Self::from_bytes(&std::fs::read(path)?)
+        }
+    }
+
+    /// Human-readable snapshot: `(version: 1, state: (dof: ..., q: [...], ...))`.
+    pub fn to_ron(&self) -> io::Result<String> {
+        let config = ron::ser::PrettyConfig::new().struct_names(false);
+        ron::ser::to_string_pretty(&self.snapshot(), config).map_err(invalid)
+    }
+
+    pub fn from_ron(text: &str) -> io::Result<Self> {
+        let header: Header = ron::from_str(text).map_err(invalid)?;
+        check_version(header.version)?;
+        let snapshot: Snapshot = ron::from_str(text).map_err(invalid)?;
+        upgrade(header.version, snapshot.state)
+    }
+
+    /// Compact snapshot for large states: a magic number and version, then the
+    /// state in bincode (fixed-width little-endian numbers), field by field in the
+    /// order of `PhaseSpaceV1`.
+    pub fn to_bytes(&self) -> io::Result<Vec<u8>> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend_from_slice(&SNAPSHOT_VERSION.to_le_bytes());
+        bincode::serialize_into(&mut bytes, self).map_err(invalid)?;
+        Ok(bytes)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> io::Result<Self> {
+        let header = bytes
+            .get(..8)
+            .filter(|h| h.starts_with(MAGIC))
+            .ok_or_else(|| invalid("not a moo snapshot"))?;
+        let version = u32::from_le_bytes(header[4..8].try_into().unwrap());
+        check_version(version)?;
+        let body = &bytes[8..];
+        let state = match version {
+            1 => bincode::deserialize::<PhaseSpaceV1>(body).map(PhaseSpace::from),
+            _ => unreachable!("rejected by check_version"),
+        };
+        upgrade(version, state.map_err(invalid)?)
+    }
+
+    fn snapshot(&self) -> SnapshotRef<'_> {
+        SnapshotRef {
+            version: SNAPSHOT_VERSION,
+            state: self,
+        }
+    }
+
+    /// Checks that the SoA arrays agree with `dof`, `dim` and each other.
+    fn check_consistent(&self) -> Result<(), String> {
+        if self.dim != 2 && self.dim != 3 {
+            return Err(format!("unsupported spatial dimension {}", self.dim));
+        }
+        let particles = self.particle_count();
+        let checks = [
+            ("q", self.q.len(), self.dof),
+            ("v", self.v.len(), self.dof),
+            ("mass", self.mass.len(), self.dof),
+            ("radius", self.radius.len(), particles),
+            ("ang_v", self.ang_v.len(), self.rot.len()),
+            ("inertia", self.inertia.len(), self.rot.len()),
+            ("dir_v", self.dir_v.len(), self.dir.len()),
+            ("attributes", self.attributes.len(), particles),
+        ];
+        for (name, len, expected) in checks {
+            if len != expected {
+                return Err(format!("`{name}` has {len} entries, expected {expected}"));
+            }
+        }
+        for name in self.attributes.names() {
+            let len = self.attributes.channel(name).map_or(0, |c| c.len());
+            if len != particles {
+                return Err(format!(
+                    "channel `{name}` has {len} entries, expected {particles}"
+                ));
+            }
+        }
+        if self.rot.len() > particles {
+            return Err(format!(
+                "{} rigid bodies but only {particles} particles",
+                self.rot.len()
+            ));
+        }
+        for block in &self.blocks {
+            // Checked arithmetic: a corrupt file may hold any counts.
+            let (name, end, len) = match *block {
+                Block::Euclidean { dim, start, count } => (
+                    "q",
+                    dim.checked_mul(count).and_then(|n| n.checked_add(start)),
+                    self.q.len(),
+                ),
+                Block::Rotation { start, count } => {
+                    ("rot", start.checked_add(count), self.rot.len())
+                }
+                Block::Director { start, count } => {
+                    ("dir", start.checked_add(count), self.dir.len())
+                }
+            };
+            if matches!(block, Block::Euclidean { dim: 0, .. }) || end.is_none_or(|end| end > len) {
+                return Err(format!(
+                    "block {block:?} does not fit `{name}` ({len} entries)"
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn check_version(version: u32) -> io::Result<()> {
+    if version == 0 || version > SNAPSHOT_VERSION {
+        return Err(invalid(format!(
+            "snapshot version {version} is not supported (this build reads 1..={SNAPSHOT_VERSION})"
+        )));
+    }
+    Ok(())
+}
+
+/// Fills in what a `version` snapshot may leave out and validates the result.
+/// Version 1 is the current layout, so there is nothing to migrate yet.
+fn upgrade(version: u32, mut state: PhaseSpace) -> io::Result<PhaseSpace> {
+    debug_assert!(version <= SNAPSHOT_VERSION);
+    // Checkpoints without channels may omit `attributes` entirely.
+    if state.attributes.names().next().is_none() {
+        let particles = state.dof.checked_div(state.dim).unwrap_or(0);
+        state.attributes.resize(particles);
+    }
+    // Older checkpoints carry no `blocks`; without them nothing would ever drift.
+    if state.blocks.is_empty() && (state.dof > 0 || !state.rot.is_empty() || !state.dir.is_empty())
+    {
+        state.rebuild_blocks();
+    }
+    state.check_consistent().map_err(invalid)?;
+    Ok(state)
+}
+
+fn is_ron(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("ron"))
+}
+
+fn invalid(error: impl ToString) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error.to_string())
+}
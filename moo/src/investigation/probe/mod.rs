@@ -1,4 +1,3 @@
-use crate::core::math::ad::Dual;
 use crate::core::state::PhaseSpace;
 use crate::laws::registry::LawRegistry;
 
@@ -33,11 +32,56 @@ impl Probe for EnergyProbe {
         }
 
         // 3. Potential V
-        // We need Dual numbers for the potential function
-        // Optimization: We only need value, so derivative seed can be 0.
-        let q_dual: Vec<Dual> = state.q.iter().map(|&x| Dual::constant(x)).collect();
-        let potential = laws.potential(&q_dual, &state.mass).val;
+        // Only the value is needed, so evaluate with plain f64 (no AD overhead).
+        laws.sync(state);
+        let potential =
+            laws.potential(&state.q, &state.mass) + laws.orientation_potential(&state.rot);
 
         kinetic + rot_kinetic + potential
     }
 }
+
+/// How [`AttributeProbe`] reduces a channel to one number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reduction {
+    Sum,
+    Mean,
+    Min,
+    Max,
+}
+
+/// Reduces a scalar attribute channel (e.g. total charge, mean temperature).
+/// Missing channels measure as zero.
+pub struct AttributeProbe {
+    pub channel: String,
+    pub reduction: Reduction,
+}
+
+impl AttributeProbe {
+    pub fn new(channel: &str, reduction: Reduction) -> Self {
+        Self {
+            channel: channel.to_string(),
+            reduction,
+        }
+    }
+}
+
+impl Probe for AttributeProbe {
+    fn name(&self) -> &str {
+        &self.channel
+    }
+
+    fn measure(&self, state: &PhaseSpace, _laws: &LawRegistry) -> f64 {
+        let values = state.attribute::<f64>(&self.channel).unwrap_or(&[]);
+        if values.is_empty() {
+            return 0.0;
+        }
+        let it = values.iter().copied();
+        match self.reduction {
+            Reduction::Sum => it.sum(),
+            Reduction::Mean => it.sum::<f64>() / values.len() as f64,
+            Reduction::Min => it.fold(f64::INFINITY, f64::min),
+            Reduction::Max => it.fold(f64::NEG_INFINITY, f64::max),
+        }
+    }
+}
@@ -0,0 +1,165 @@
+use crate::core::math::tape::{Tape, Var};
+use crate::core::state::PhaseSpace;
+use crate::laws::registry::{Law, LawRegistry};
+use std::fmt;
+
+/// Verifies AD forces against central finite differences of the potential.
+///
+/// Intended for integration tests of new laws: a hand-written derivative helper or a
+/// non-smooth branch shows up as a DOF whose AD and FD forces disagree.
+#[derive(Debug, Clone, Copy)]
+pub struct GradientCheck {
+    /// Relative finite-difference step; the actual step is `step * max(1, |q_i|)`.
+    pub step: f64,
+    /// A DOF passes if its absolute error is below this...
+    pub abs_tol: f64,
+    /// ...or its relative error is below this.
+    pub rel_tol: f64,
+}
+
+impl Default for GradientCheck {
+    fn default() -> Self {
+        Self {
+            step: 1e-6,
+            abs_tol: 1e-6,
+            rel_tol: 1e-5,
+        }
+    }
+}
+
+/// Comparison of the force on a single degree of freedom.
+#[derive(Debug, Clone, Copy)]
+pub struct DofError {
+    pub dof: usize,
+    /// Force from the engine (automatic differentiation or the law's analytic gradient).
+    pub ad: f64,
+    /// Force from central finite differences.
+    pub fd: f64,
+    pub abs_error: f64,
+    pub rel_error: f64,
+    pub passed: bool,
+}
+
+/// Per-DOF result of a [`GradientCheck`].
+#[derive(Debug, Clone, Default)]
+pub struct GradientReport {
+    pub entries: Vec<DofError>,
+}
+
+impl GradientReport {
+    pub fn passed(&self) -> bool {
+        self.entries.iter().all(|e| e.passed)
+    }
+
+    pub fn failures(&self) -> impl Iterator<Item = &DofError> {
+        self.entries.iter().filter(|e| !e.passed)
+    }
+
+    pub fn max_abs_error(&self) -> f64 {
+        self.entries.iter().map(|e| e.abs_error).fold(0.0, f64::max)
+    }
+
+    pub fn max_rel_error(&self) -> f64 {
+        self.entries.iter().map(|e| e.rel_error).fold(0.0, f64::max)
+    }
+}
+
+impl fmt::Display for GradientReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Gradient check: {} / {} DOF passed (max abs {:.3e}, max rel {:.3e})",
+            self.entries.len() - self.failures().count(),
+            self.entries.len(),
+            self.max_abs_error(),
+            self.max_rel_error()
+        )?;
+        for e in self.failures() {
+            writeln!(
+                f,
+                "  DOF {}: AD={:.9e} FD={:.9e} abs={:.3e} rel={:.3e}",
+                e.dof, e.ad, e.fd, e.abs_error, e.rel_error
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl GradientCheck {
+    pub fn new(step: f64, abs_tol: f64, rel_tol: f64) -> Self {
+        Self {
+            step,
+            abs_tol,
+            rel_tol,
+        }
+    }
+
+    /// Checks a single law at the configuration of `state`.
+    ///
+    /// If the law provides an analytic [`Law::gradient`], that is what gets checked;
+    /// otherwise its potential is differentiated in reverse mode. The law is synced
+    /// with `state` first (see [`Law::sync`]), as an integrator would.
+    pub fn check_law<L: Law>(&self, law: &L, state: &PhaseSpace) -> GradientReport {
+        law.sync(state);
+        let mut grad = vec![0.0; state.dof];
+        if !law.gradient(&state.q, &state.mass, &mut grad) {
+            let tape = Tape::with_capacity(state.dof * 4);
+            let inputs: Vec<Var> = state.q.iter().map(|&x| tape.var(x)).collect();
+            let adjoints = law.potential(&inputs, &state.mass).backward();
+            for (g, &x) in grad.iter_mut().zip(&inputs) {
+                *g = adjoints.wrt(x);
+            }
+        }
+        let ad_forces: Vec<f64> = grad.iter().map(|g| -g).collect();
+
+        self.compare(state, &ad_forces, |q| law.potential(q, &state.mass))
+    }
+
+    /// Checks the total potential of a registry, differentiated with its current
+    /// [`GradientMode`](crate::laws::registry::GradientMode), after syncing it with `state`.
+    pub fn check_registry(&self, laws: &LawRegistry, state: &PhaseSpace) -> GradientReport {
+        laws.sync(state);
+        let mut grad = vec![0.0; state.dof];
+        laws.gradient(&state.q, &state.mass, &mut grad);
+        let ad_forces: Vec<f64> = grad.iter().map(|g| -g).collect();
+
+        self.compare(state, &ad_forces, |q| laws.potential(q, &state.mass))
+    }
+
+    fn compare(
+        &self,
+        state: &PhaseSpace,
+        ad_forces: &[f64],
+        potential: impl Fn(&[f64]) -> f64,
+    ) -> GradientReport {
+        let mut q = state.q.clone();
+        let mut entries = Vec::with_capacity(state.dof);
+
+        for (i, &ad) in ad_forces.iter().enumerate() {
+            let x = q[i];
+            let h = self.step * x.abs().max(1.0);
+
+            q[i] = x + h;
+            let v_plus = potential(&q);
+            q[i] = x - h;
+            let v_minus = potential(&q);
+            q[i] = x;
+
+            let fd = -(v_plus - v_minus) / (2.0 * h);
+            let abs_error = (ad - fd).abs();
+            let scale = ad.abs().max(fd.abs());
+            let rel_error = if scale > 0.0 { abs_error / scale } else { 0.0 };
+
+            entries.push(DofError {
+                dof: i,
+                ad,
+                fd,
+                abs_error,
+                rel_error,
+                passed: abs_error <= self.abs_tol || rel_error <= self.rel_tol,
+            });
+        }
+
+        GradientReport { entries }
+    }
+}
@@ -0,0 +1,146 @@
+use crate::core::math::real::Real;
+use crate::core::state::attributes::names;
+use crate::core::state::{PhaseSpace, Reindex};
+use crate::laws::classical::particle_position;
+use crate::laws::registry::Law;
+use std::cell::RefCell;
+
+/// Electrostatics: V = k * q1 * q2 / r, with charges read from the
+/// [`names::CHARGE`] attribute channel.
+///
+/// The law keeps its own copy of the charges, refreshed by
+/// [`LawRegistry::sync`](crate::laws::registry::LawRegistry::sync) at the start of
+/// every integrator step.
+#[derive(Debug, Clone)]
+pub struct Coulomb {
+    pub k: f64,
+    /// Softening length to avoid singularities at r=0.
+    pub softening: f64,
+    /// Spatial dimension of each particle (3, or 2 for planar systems).
+    pub dim: usize,
+    /// Charge per particle; particles beyond the end are neutral.
+    charges: RefCell<Vec<f64>>,
+}
+
+impl Coulomb {
+    pub fn new(k: f64) -> Self {
+        Self {
+            k,
+            softening: DEFAULT_SOFTENING,
+            dim: 3,
+            charges: RefCell::default(),
+        }
+    }
+
+    /// Sets the spatial dimension of the particles (`q` holds `dim` coordinates each).
+    pub fn with_dim(mut self, dim: usize) -> Self {
+        self.dim = dim;
+        self
+    }
+
+    /// Takes the charges from `state` straight away.
+    pub fn with_charges_from(self, state: &PhaseSpace) -> Self {
+        Law::sync(&self, state);
+        self
+    }
+
+    /// The charges as of the last sync.
+    pub fn charges(&self) -> Vec<f64> {
+        self.charges.borrow().clone()
+    }
+}
+
+const DEFAULT_SOFTENING: f64 = 1e-3;
+
+impl Law for Coulomb {
+    fn dim(&self) -> Option<usize> {
+        Some(self.dim)
+    }
+
+    /// Leaves out the charges, which belong to the state.
+    fn describe(&self) -> String {
+        format!(
+            "Coulomb {{ k: {:?}, softening: {:?}, dim: {} }}",
+            self.k, self.softening, self.dim
+        )
+    }
+
+    fn potential<T: Real>(&self, q: &[T], _mass: &[f64]) -> T {
+        let mut total_potential = T::constant(0.0);
+        let dim = self.dim;
+        if !q.len().is_multiple_of(dim) {
+            return total_potential;
+        }
+
+        let charges = self.charges.borrow();
+        let n_particles = (q.len() / dim).min(charges.len());
+        let softening_sq = self.softening * self.softening;
+
+        for i in 0..n_particles {
+            for j in (i + 1)..n_particles {
+                let qq = charges[i] * charges[j];
+                if qq == 0.0 {
+                    continue;
+                }
+                let mut dist_sq = T::constant(softening_sq);
+                for k in 0..dim {
+                    let d = q[i * dim + k] - q[j * dim + k];
+                    dist_sq = dist_sq + d * d;
+                }
+                total_potential = total_potential + dist_sq.sqrt().recip() * (self.k * qq);
+            }
+        }
+
+        total_potential
+    }
+
+    /// Closed form: dV/dq_i = -k * q1 * q2 * (q_i - q_j) / d^3, with d the softened distance.
+    fn gradient(&self, q: &[f64], _mass: &[f64], out: &mut [f64]) -> bool {
+        let dim = self.dim;
+        if !q.len().is_multiple_of(dim) {
+            return true;
+        }
+
+        let charges = self.charges.borrow();
+        let n_particles = (q.len() / dim).min(charges.len());
+        let softening_sq = self.softening * self.softening;
+
+        for i in 0..n_particles {
+            let pi = particle_position(q, i * dim, dim);
+            for j in (i + 1)..n_particles {
+                let qq = charges[i] * charges[j];
+                if qq == 0.0 {
+                    continue;
+                }
+                let diff = pi - particle_position(q, j * dim, dim);
+                let inv_dist = 1.0 / (diff.length_squared() + softening_sq).sqrt();
+                let g = diff * (-self.k * qq * inv_dist * inv_dist * inv_dist);
+
+                for k in 0..dim {
+                    out[i * dim + k] += g[k];
+                    out[j * dim + k] -= g[k];
+                }
+            }
+        }
+
+        true
+    }
+
+    /// Removal preserves particle order, so dropping the removed charges is enough.
+    fn reindex(&mut self, map: &Reindex) -> bool {
+        let mut old = 0;
+        self.charges.get_mut().retain(|_| {
+            old += 1;
+            map.get(old - 1).is_some()
+        });
+        true
+    }
+
+    fn sync(&self, state: &PhaseSpace) {
+        let mut own = self.charges.borrow_mut();
+        own.clear();
+        if let Some(charges) = state.attribute::<f64>(names::CHARGE) {
+            own.extend_from_slice(charges);
+        }
+    }
+}
@@ -1,11 +1,15 @@
-use crate::core::math::ad::Dual;
+use crate::core::math::real::Real;
+use crate::laws::classical::particle_position;
 use crate::laws::registry::Law;
 
 /// Newtonian Gravity: V = -G * m1 * m2 / r
+#[derive(Debug, Clone)]
 pub struct Gravity {
     pub g: f64,
     /// Softening length to avoid singularities at r=0.
     pub softening: f64,
+    /// Spatial dimension of each particle (3, or 2 for planar systems).
+    pub dim: usize,
 }
 
 impl Gravity {
@@ -13,6 +17,7 @@ impl Gravity {
         Self {
             g,
             softening: DEFAULT_SOFTENING,
+            dim: 3,
         }
     }
 
@@ -20,47 +25,62 @@ impl Gravity {
         Self {
             g,
             softening: softening.abs(),
+            dim: 3,
         }
     }
+
+    /// Sets the spatial dimension of the particles (`q` holds `dim` coordinates each).
+    pub fn with_dim(mut self, dim: usize) -> Self {
+        self.dim = dim;
+        self
+    }
 }
 
 const DEFAULT_SOFTENING: f64 = 1e-3;
 
 impl Law for Gravity {
-    fn potential(&self, q: &[Dual], mass: &[f64]) -> Dual {
-        let mut total_potential = Dual::constant(0.0);
-        let n_particles = q.len() / 3;
+    fn dim(&self) -> Option<usize> {
+        Some(self.dim)
+    }
+
+    fn describe(&self) -> String {
+        format!("{self:?}")
+    }
+
+    fn potential<T: Real>(&self, q: &[T], mass: &[f64]) -> T {
+        let mut total_potential = T::constant(0.0);
+        let dim = self.dim;
+        let n_particles = q.len() / dim;
 
         // Ensure mass definition is consistent
-        // If mass.len() == q.len() (Per DOF mass), use stride 3.
+        // If mass.len() == q.len() (Per DOF mass), use stride `dim`.
         // If mass.len() == n_particles (Per particle mass), use stride 1.
-        let mass_stride = if mass.len() == q.len() { 3 } else { 1 };
+        let mass_stride = if mass.len() == q.len() { dim } else { 1 };
 
-        if !q.len().is_multiple_of(3) {
-            return Dual::constant(0.0);
+        if !q.len().is_multiple_of(dim) {
+            return T::constant(0.0);
         }
 
-        let softening_sq = Dual::constant(self.softening.abs() * self.softening.abs());
+        let softening_sq = self.softening.abs() * self.softening.abs();
 
         for i in 0..n_particles {
             for j in (i + 1)..n_particles {
-                let idx_i = i * 3;
-                let idx_j = j * 3;
+                let idx_i = i * dim;
+                let idx_j = j * dim;
 
-                let dx = q[idx_i] - q[idx_j];
-                let dy = q[idx_i + 1] - q[idx_j + 1];
-                let dz = q[idx_i + 2] - q[idx_j + 2];
-
-                let mut dist_sq = dx * dx + dy * dy + dz * dz + softening_sq;
-                let dist_val = dist_sq.val.sqrt();
-                if dist_val == 0.0 {
-                    dist_sq = dx * dx + dy * dy + dz * dz + Dual::constant(1e-4);
+                let mut dist_sq = T::constant(softening_sq);
+                for k in 0..dim {
+                    let d = q[idx_i + k] - q[idx_j + k];
+                    dist_sq = dist_sq + d * d;
+                }
+                if dist_sq.value() == 0.0 {
+                    dist_sq = dist_sq + 1e-4;
                 }
-                let dist = Dual::new(dist_sq.val.sqrt(), 0.5 * dist_sq.der / dist_sq.val.sqrt());
+                let dist = dist_sq.sqrt();
 
                 // V = -G * m1 * m2 / r
                 let m1m2 = mass[i * mass_stride] * mass[j * mass_stride];
-                let term = dist.inv() * Dual::constant(-self.g * m1m2);
+                let term = dist.recip() * (-self.g * m1m2);
 
                 total_potential = total_potential + term;
             }
@@ -68,11 +88,42 @@ impl Law for Gravity {
 
         total_potential
     }
-}
 
-impl Dual {
-    fn inv(self) -> Self {
-        // 1/x -> -1/x^2
-        Self::new(1.0 / self.val, -self.der / (self.val * self.val))
+    /// Closed form: dV/dq_i = G * m1 * m2 * (q_i - q_j) / d^3, with d the softened distance.
+    fn gradient(&self, q: &[f64], mass: &[f64], out: &mut [f64]) -> bool {
+        let dim = self.dim;
+        if !q.len().is_multiple_of(dim) {
+            return true;
+        }
+
+        let n_particles = q.len() / dim;
+        let mass_stride = if mass.len() == q.len() { dim } else { 1 };
+        let softening_sq = self.softening.abs() * self.softening.abs();
+
+        for i in 0..n_particles {
+            let idx_i = i * dim;
+            let pi = particle_position(q, idx_i, dim);
+
+            for j in (i + 1)..n_particles {
+                let idx_j = j * dim;
+                let diff = pi - particle_position(q, idx_j, dim);
+
+                let mut dist_sq = diff.length_squared() + softening_sq;
+                if dist_sq == 0.0 {
+                    dist_sq += 1e-4;
+                }
+                let inv_dist = 1.0 / dist_sq.sqrt();
+
+                let m1m2 = mass[i * mass_stride] * mass[j * mass_stride];
+                let g = diff * (self.g * m1m2 * inv_dist * inv_dist * inv_dist);
+
+                for k in 0..dim {
+                    out[idx_i + k] += g[k];
+                    out[idx_j + k] -= g[k];
+                }
+            }
+        }
+
+        true
     }
 }
@@ -1,5 +1,16 @@
+pub mod coulomb;
 pub mod gravity;
 pub mod spring;
 
+pub use coulomb::Coulomb;
 pub use gravity::Gravity;
 pub use spring::Spring;
+
+/// Reads `dim` coordinates starting at `base`, zero-padding the missing axes.
+pub(crate) fn particle_position(q: &[f64], base: usize, dim: usize) -> glam::DVec3 {
+    let mut p = glam::DVec3::ZERO;
+    for k in 0..dim {
+        p[k] = q[base + k];
+    }
+    p
+}
@@ -1,11 +1,16 @@
-use crate::core::math::ad::Dual;
+use crate::core::math::real::Real;
+use crate::core::state::Reindex;
+use crate::laws::classical::particle_position;
 use crate::laws::registry::Law;
 
+#[derive(Debug, Clone)]
 pub struct Spring {
     pub k: f64,
     pub rest_length: f64,
     pub p1_idx: usize,
     pub p2_idx: usize,
+    /// Spatial dimension of each particle (3, or 2 for planar systems).
+    pub dim: usize,
 }
 
 impl Spring {
@@ -15,35 +20,89 @@ impl Spring {
             rest_length,
             p1_idx,
             p2_idx,
+            dim: 3,
         }
     }
+
+    /// Sets the spatial dimension of the particles (`q` holds `dim` coordinates each).
+    pub fn with_dim(mut self, dim: usize) -> Self {
+        self.dim = dim;
+        self
+    }
 }
 
 impl Law for Spring {
-    fn potential(&self, q: &[Dual], _mass: &[f64]) -> Dual {
-        let idx1 = self.p1_idx * 3;
-        let idx2 = self.p2_idx * 3;
+    fn dim(&self) -> Option<usize> {
+        Some(self.dim)
+    }
 
-        if idx1 + 2 >= q.len() || idx2 + 2 >= q.len() {
-            return Dual::constant(0.0);
-        }
+    fn describe(&self) -> String {
+        format!("{self:?}")
+    }
 
-        let dx = q[idx1] - q[idx2];
-        let dy = q[idx1 + 1] - q[idx2 + 1];
-        let dz = q[idx1 + 2] - q[idx2 + 2];
+    fn potential<T: Real>(&self, q: &[T], _mass: &[f64]) -> T {
+        let dim = self.dim;
+        let idx1 = self.p1_idx * dim;
+        let idx2 = self.p2_idx * dim;
 
-        let dist_sq = dx * dx + dy * dy + dz * dz;
-        // Manual sqrt for Dual
-        let dist_val = dist_sq.val.sqrt();
-        let dist = if dist_val > 1e-6 {
-            Dual::new(dist_val, 0.5 * dist_sq.der / dist_val)
+        if idx1 + dim > q.len() || idx2 + dim > q.len() {
+            return T::constant(0.0);
+        }
+
+        let mut dist_sq = T::constant(0.0);
+        for k in 0..dim {
+            let d = q[idx1 + k] - q[idx2 + k];
+            dist_sq = dist_sq + d * d;
+        }
+        let dist = if dist_sq.value() > 1e-12 {
+            dist_sq.sqrt()
         } else {
-            Dual::constant(0.0) // Handle singularity
+            T::constant(0.0) // Handle singularity
         };
 
         // V = 0.5 * k * (r - r0)^2
-        let displacement = dist - Dual::constant(self.rest_length);
+        let displacement = dist - self.rest_length;
+
+        displacement * displacement * (0.5 * self.k)
+    }
+
+    /// Closed form: dV/dq_1 = k * (r - r0) * (q_1 - q_2) / r.
+    fn gradient(&self, q: &[f64], _mass: &[f64], out: &mut [f64]) -> bool {
+        let dim = self.dim;
+        let idx1 = self.p1_idx * dim;
+        let idx2 = self.p2_idx * dim;
 
-        displacement * displacement * Dual::constant(0.5 * self.k)
+        if idx1 + dim > q.len() || idx2 + dim > q.len() {
+            return true;
+        }
+
+        let diff = particle_position(q, idx1, dim) - particle_position(q, idx2, dim);
+        let dist_sq = diff.length_squared();
+        if dist_sq <= 1e-12 {
+            // Matches the potential, which is flat at the singularity.
+            return true;
+        }
+
+        let dist = dist_sq.sqrt();
+        let g = diff * (self.k * (dist - self.rest_length) / dist);
+
+        for k in 0..dim {
+            out[idx1 + k] += g[k];
+            out[idx2 + k] -= g[k];
+        }
+
+        true
+    }
+
+    /// The spring follows its endpoints and is dropped with either of them.
+    fn reindex(&mut self, map: &Reindex) -> bool {
+        match (map.get(self.p1_idx), map.get(self.p2_idx)) {
+            (Some(p1), Some(p2)) => {
+                self.p1_idx = p1;
+                self.p2_idx = p2;
+                true
+            }
+            _ => false,
+        }
     }
 }
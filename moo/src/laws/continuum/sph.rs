@@ -1,4 +1,4 @@
-use crate::core::math::ad::Dual;
+use crate::core::math::real::Real;
 use crate::laws::registry::Law;
 use std::f64::consts::PI;
 
@@ -20,11 +20,14 @@ use std::f64::consts::PI;
 /// This leads to linear pressure (approx).
 ///
 /// Or proper Tait Potential. Let's try simple stiffness first.
+#[derive(Debug, Clone)]
 pub struct SPH {
     pub h: f64,    // Smoothing radius
     pub rho0: f64, // Rest density
     pub k: f64,    // Stiffness (Bulk modulus related)
     pub poly6_coeff: f64,
+    /// Spatial dimension of each particle (3, or 2 for planar fluids).
+    pub dim: usize,
 }
 
 impl SPH {
@@ -36,36 +39,57 @@ impl SPH {
             rho0,
             k,
             poly6_coeff,
+            dim: 3,
         }
     }
+
+    /// Switches to `dim`-dimensional particles, renormalising the kernel.
+    /// In 2D the Poly6 constant is 4 / (pi * h^8).
+    pub fn with_dim(mut self, dim: usize) -> Self {
+        self.dim = dim;
+        self.poly6_coeff = match dim {
+            2 => 4.0 / (PI * self.h.powi(8)),
+            _ => 315.0 / (64.0 * PI * self.h.powi(9)),
+        };
+        self
+    }
 }
 
 impl Law for SPH {
-    fn potential(&self, q: &[Dual], mass: &[f64]) -> Dual {
-        let n = q.len() / 3;
-        let mass_stride = if mass.len() == q.len() { 3 } else { 1 };
+    fn dim(&self) -> Option<usize> {
+        Some(self.dim)
+    }
+
+    fn describe(&self) -> String {
+        format!("{self:?}")
+    }
+
+    fn potential<T: Real>(&self, q: &[T], mass: &[f64]) -> T {
+        let dim = self.dim;
+        let n = q.len() / dim;
+        let mass_stride = if mass.len() == q.len() { dim } else { 1 };
         let h_sq = self.h * self.h;
 
-        let mut total_potential = Dual::constant(0.0);
+        let mut total_potential = T::constant(0.0);
 
         // 1. Calculate Density field (rho) per particle
-        // Note: In AD, density is a Dual number dependent on positions q.
-        let mut densities = vec![Dual::constant(0.0); n];
+        // Note: In AD, density is a differentiable scalar dependent on positions q.
+        let mut densities = vec![T::constant(0.0); n];
 
         for (i, rho) in densities.iter_mut().enumerate().take(n) {
-            let idx_i = i * 3;
+            let idx_i = i * dim;
             // Self-density contribution (r=0 -> W(0)=315/(64*pi*h^9)*h^6 = 315/(64*pi*h^3))
             // W(0) = 315 / 64pi * h^9 * (h^2)^3 = 315/64pi*h^3
             // Code below handles r=0 naturally if we iterate j including i.
 
             for j in 0..n {
-                let idx_j = j * 3;
+                let idx_j = j * dim;
 
-                let dx = q[idx_i] - q[idx_j];
-                let dy = q[idx_i + 1] - q[idx_j + 1];
-                let dz = q[idx_i + 2] - q[idx_j + 2];
-
-                let dist_sq = dx * dx + dy * dy + dz * dz;
+                let mut dist_sq = T::constant(0.0);
+                for k in 0..dim {
+                    let d = q[idx_i + k] - q[idx_j + k];
+                    dist_sq = dist_sq + d * d;
+                }
 
                 // Poly6 Kernel
                 // W(r, h) = coeff * (h^2 - r^2)^3   if 0 <= r <= h
@@ -74,10 +98,10 @@ impl Law for SPH {
 
                 // Note: branching 'if' with Duals is tricky if we are precisely at h.
                 // But generally safe.
-                if dist_sq.val < h_sq {
-                    let term = Dual::constant(h_sq) - dist_sq;
-                    let w = Dual::constant(self.poly6_coeff) * term * term * term;
-                    *rho = *rho + Dual::constant(mass[j * mass_stride]) * w;
+                if dist_sq.value() < h_sq {
+                    let term = T::constant(h_sq) - dist_sq;
+                    let w = T::constant(self.poly6_coeff) * term * term * term;
+                    *rho = *rho + T::constant(mass[j * mass_stride]) * w;
                 }
             }
         }
@@ -94,15 +118,15 @@ impl Law for SPH {
             let m = mass[i * mass_stride];
 
             // Avoid division by zero
-            let vol = if rho.val > 1e-6 {
-                Dual::constant(m) / *rho
+            let vol = if rho.value() > 1e-6 {
+                T::constant(m) / *rho
             } else {
-                Dual::constant(0.0)
+                T::constant(0.0)
             };
 
-            let delta = *rho - Dual::constant(self.rho0);
+            let delta = *rho - T::constant(self.rho0);
             // Elastic potential energy
-            let u = Dual::constant(0.5 * self.k) * delta * delta; // Energy density
+            let u = T::constant(0.5 * self.k) * delta * delta; // Energy density
 
             total_potential = total_potential + u * vol;
         }
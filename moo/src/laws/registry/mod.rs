@@ -1,4 +1,11 @@
+use crate::core::geometry::{LieGroup, SO3};
 use crate::core::math::ad::Dual;
+use crate::core::math::hyper::HyperDual;
+use crate::core::math::multi::{ChunkDual, GRADIENT_CHUNK};
+use crate::core::math::real::Real;
+use crate::core::math::tape::{Tape, Var};
+use crate::core::state::{PhaseSpace, Reindex};
+use glam::{DMat3, DQuat, DVec3};
 
 /// A Physical Law that governs the evolution of the system.
 ///
@@ -8,19 +15,205 @@ use crate::core::math::ad::Dual;
 ///
 /// This ensures strict energy conservation (symplecticity) because the forces
 /// are guaranteed to be conservative gradients.
+///
+/// A law is written once against the [`Real`] scalar trait. The engine instantiates
+/// it with `f64` when only the energy is needed and with the AD types when it needs
+/// derivatives.
 pub trait Law {
     /// Computes the total potential energy of the system given the state configuration `q`.
     ///
     /// # Arguments
-    /// * `q` - The generalized coordinates in Dual number form (for AD).
+    /// * `q` - The generalized coordinates, in whichever scalar the caller evaluates with.
     /// * `mass` - The mass constants of the degrees of freedom.
-    fn potential(&self, q: &[Dual], mass: &[f64]) -> Dual;
+    fn potential<T: Real>(&self, q: &[T], mass: &[f64]) -> T;
+
+    /// Optional closed-form gradient.
+    ///
+    /// Laws with a known analytic force override this to **accumulate** $\partial V / \partial q$
+    /// into `out` and return `true`. The default returns `false`, in which case the
+    /// registry differentiates [`Law::potential`] with AD instead.
+    fn gradient(&self, _q: &[f64], _mass: &[f64], _out: &mut [f64]) -> bool {
+        false
+    }
+
+    /// Follows particles to their new indices after particles were removed.
+    ///
+    /// Laws that name particles by index (like [`Spring`](crate::laws::classical::Spring))
+    /// override this to remap them, returning `false` when a particle they need is gone
+    /// so the registry drops the law. Laws over all particles keep the default.
+    fn reindex(&mut self, _map: &Reindex) -> bool {
+        true
+    }
+
+    /// Refreshes per-particle parameters the law reads from the state's attribute
+    /// channels (e.g. charges). The potential itself only sees `q` and `mass`.
+    ///
+    /// Every integrator calls this (through [`LawRegistry::sync`]) at the start of
+    /// each step, so edited or inserted attributes take effect on the next step.
+    /// Laws that cache parameters keep them behind interior mutability.
+    fn sync(&self, _state: &PhaseSpace) {}
+
+    /// Potential energy of the rigid-body orientations, $V(R_1, \dots, R_n)$.
+    ///
+    /// Each `rot[i]` is the body-to-world rotation matrix of body `i` in row-major
+    /// order. Laws without orientation dependence keep the default of zero. The
+    /// engine differentiates it along body-frame rotations to obtain torques
+    /// (see [`LawRegistry::torques`]), which every integrator applies to the
+    /// angular velocities.
+    fn orientation_potential<T: Real>(&self, _rot: &[[T; 9]]) -> T {
+        T::constant(0.0)
+    }
+
+    /// Spatial dimension the law assumes for each particle, if it has one. The
+    /// registry checks it against [`PhaseSpace::dim`] on every sync.
+    fn dim(&self) -> Option<usize> {
+        None
+    }
+
+    /// The law and its parameters, for run metadata. Defaults to the type name.
+    fn describe(&self) -> String {
+        std::any::type_name::<Self>().to_string()
+    }
+}
+
+/// Object-safe form of [`Law`] with one entry point per supported scalar.
+///
+/// Implemented automatically for every `Law`; this is what [`LawRegistry`] stores.
+pub trait ErasedLaw {
+    fn potential_f64(&self, q: &[f64], mass: &[f64]) -> f64;
+    fn potential_dual(&self, q: &[Dual], mass: &[f64]) -> Dual;
+    fn potential_chunk(&self, q: &[ChunkDual], mass: &[f64]) -> ChunkDual;
+    fn potential_tape<'t>(&self, q: &[Var<'t>], mass: &[f64]) -> Var<'t>;
+    fn potential_tape_dual<'t>(&self, q: &[Var<'t, Dual>], mass: &[f64]) -> Var<'t, Dual>;
+    fn potential_hyper(&self, q: &[HyperDual], mass: &[f64]) -> HyperDual;
+    fn orientation_potential_f64(&self, rot: &[[f64; 9]]) -> f64;
+    fn orientation_potential_dual(&self, rot: &[[Dual; 9]]) -> Dual;
+    fn gradient(&self, q: &[f64], mass: &[f64], out: &mut [f64]) -> bool;
+    fn reindex(&mut self, map: &Reindex) -> bool;
+    fn sync(&self, state: &PhaseSpace);
+    fn dim(&self) -> Option<usize>;
+    /// Type name of the law, for run metadata.
+    fn name(&self) -> &'static str;
+    fn describe(&self) -> String;
+}
+
+impl<L: Law> ErasedLaw for L {
+    fn potential_f64(&self, q: &[f64], mass: &[f64]) -> f64 {
+        self.potential(q, mass)
+    }
+
+    fn potential_dual(&self, q: &[Dual], mass: &[f64]) -> Dual {
+        self.potential(q, mass)
+    }
+
+    fn potential_chunk(&self, q: &[ChunkDual], mass: &[f64]) -> ChunkDual {
+        self.potential(q, mass)
+    }
+
+    fn potential_tape<'t>(&self, q: &[Var<'t>], mass: &[f64]) -> Var<'t> {
+        self.potential(q, mass)
+    }
+
+    fn potential_tape_dual<'t>(&self, q: &[Var<'t, Dual>], mass: &[f64]) -> Var<'t, Dual> {
+        self.potential(q, mass)
+    }
+
+    fn potential_hyper(&self, q: &[HyperDual], mass: &[f64]) -> HyperDual {
+        self.potential(q, mass)
+    }
+
+    fn orientation_potential_f64(&self, rot: &[[f64; 9]]) -> f64 {
+        self.orientation_potential(rot)
+    }
+
+    fn orientation_potential_dual(&self, rot: &[[Dual; 9]]) -> Dual {
+        self.orientation_potential(rot)
+    }
+
+    fn gradient(&self, q: &[f64], mass: &[f64], out: &mut [f64]) -> bool {
+        Law::gradient(self, q, mass, out)
+    }
+
+    fn reindex(&mut self, map: &Reindex) -> bool {
+        Law::reindex(self, map)
+    }
+
+    fn sync(&self, state: &PhaseSpace) {
+        Law::sync(self, state)
+    }
+
+    fn dim(&self) -> Option<usize> {
+        Law::dim(self)
+    }
+
+    fn name(&self) -> &'static str {
+        std::any::type_name::<L>()
+    }
+
+    fn describe(&self) -> String {
+        Law::describe(self)
+    }
+}
+
+/// A scalar type the registry can evaluate its (type-erased) laws with.
+pub trait LawScalar: Real {
+    /// Selects the matching instantiation of `law`.
+    fn eval(law: &dyn ErasedLaw, q: &[Self], mass: &[f64]) -> Self;
+}
+
+impl LawScalar for f64 {
+    fn eval(law: &dyn ErasedLaw, q: &[Self], mass: &[f64]) -> Self {
+        law.potential_f64(q, mass)
+    }
+}
+
+impl LawScalar for Dual {
+    fn eval(law: &dyn ErasedLaw, q: &[Self], mass: &[f64]) -> Self {
+        law.potential_dual(q, mass)
+    }
+}
+
+impl LawScalar for ChunkDual {
+    fn eval(law: &dyn ErasedLaw, q: &[Self], mass: &[f64]) -> Self {
+        law.potential_chunk(q, mass)
+    }
+}
+
+impl LawScalar for Var<'_> {
+    fn eval(law: &dyn ErasedLaw, q: &[Self], mass: &[f64]) -> Self {
+        law.potential_tape(q, mass)
+    }
+}
+
+impl LawScalar for Var<'_, Dual> {
+    fn eval(law: &dyn ErasedLaw, q: &[Self], mass: &[f64]) -> Self {
+        law.potential_tape_dual(q, mass)
+    }
+}
+
+impl LawScalar for HyperDual {
+    fn eval(law: &dyn ErasedLaw, q: &[Self], mass: &[f64]) -> Self {
+        law.potential_hyper(q, mass)
+    }
+}
+
+/// How [`LawRegistry::gradient`] differentiates the total potential.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GradientMode {
+    /// Forward-mode: `ceil(dof / GRADIENT_CHUNK)` vector-mode evaluations. The cost
+    /// grows linearly with the DOF count, so this only pays off for small systems.
+    Forward,
+    /// Reverse-mode: one taped evaluation plus a backward sweep. The scalable
+    /// choice, and the default.
+    #[default]
+    Reverse,
 }
 
 /// A registry that aggregates multiple laws.
 /// $V_{total} = \sum V_i$
 pub struct LawRegistry {
-    laws: Vec<Box<dyn Law>>,
+    laws: Vec<Box<dyn ErasedLaw>>,
+    mode: GradientMode,
 }
 
 impl Default for LawRegistry {
@@ -31,18 +224,256 @@ impl Default for LawRegistry {
 
 impl LawRegistry {
     pub fn new() -> Self {
-        Self { laws: Vec::new() }
+        Self {
+            laws: Vec::new(),
+            mode: GradientMode::default(),
+        }
+    }
+
+    pub fn gradient_mode(&self) -> GradientMode {
+        self.mode
+    }
+
+    pub fn set_gradient_mode(&mut self, mode: GradientMode) {
+        self.mode = mode;
     }
 
     pub fn add(&mut self, law: impl Law + 'static) {
         self.laws.push(Box::new(law));
     }
 
-    pub fn potential(&self, q: &[Dual], mass: &[f64]) -> Dual {
-        let mut total = Dual::new(0.0, 0.0);
+    pub fn len(&self) -> usize {
+        self.laws.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.laws.is_empty()
+    }
+
+    /// Type names of the registered laws, in insertion order.
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.laws.iter().map(|law| law.name())
+    }
+
+    /// [`Law::describe`] of the registered laws, in insertion order.
+    pub fn descriptions(&self) -> impl Iterator<Item = String> + '_ {
+        self.laws.iter().map(|law| law.describe())
+    }
+
+    /// Remaps every law after particles were removed (see [`Law::reindex`]),
+    /// dropping laws whose particles no longer exist.
+    pub fn reindex(&mut self, map: &Reindex) {
+        self.laws.retain_mut(|law| law.reindex(map));
+    }
+
+    /// Lets every law re-read the attribute channels it depends on (see [`Law::sync`]).
+    /// Integrators do this at the start of every step; call it directly before
+    /// evaluating the registry outside of a step.
+    ///
+    /// Panics if a law's [`Law::dim`] disagrees with the state's dimension: such a
+    /// law would misread the coordinates and silently contribute nothing.
+    pub fn sync(&self, state: &PhaseSpace) {
         for law in &self.laws {
-            total = total + law.potential(q, mass);
+            assert!(
+                law.dim().is_none_or(|dim| dim == state.dim),
+                "{} expects {}D particles, the state is {}D",
+                law.name(),
+                law.dim().unwrap_or_default(),
+                state.dim
+            );
+            law.sync(state);
+        }
+    }
+
+    /// Total potential energy, evaluated with the scalar type of `q`.
+    /// Pass plain `f64` coordinates when only the energy is needed.
+    pub fn potential<T: LawScalar>(&self, q: &[T], mass: &[f64]) -> T {
+        sum_potential(self.laws.iter().map(|law| law.as_ref()), q, mass)
+    }
+
+    /// Total orientation potential (see [`Law::orientation_potential`]).
+    pub fn orientation_potential(&self, rot: &[DQuat]) -> f64 {
+        let rot: Vec<[f64; 9]> = rot
+            .iter()
+            .map(|&r| row_major(DMat3::from_quat(r)))
+            .collect();
+        self.laws
+            .iter()
+            .map(|law| law.orientation_potential_f64(&rot))
+            .sum()
+    }
+
+    /// Body-frame torques `out[i]` on every rigid body from the orientation potential:
+    /// the work along a body rotation `R_i exp(ε ξ)` is `-dV/dε = out[i] · ξ`.
+    ///
+    /// Each component is one dual-number evaluation (`3 * rot.len()` in total).
+    pub fn torques(&self, rot: &[DQuat], out: &mut [DVec3]) {
+        debug_assert_eq!(rot.len(), out.len());
+        out.fill(DVec3::ZERO);
+        if self.laws.is_empty() {
+            return;
+        }
+        let mut inputs: Vec<[Dual; 9]> = rot
+            .iter()
+            .map(|&r| row_major(DMat3::from_quat(r)).map(Dual::constant))
+            .collect();
+
+        for (i, torque) in out.iter_mut().enumerate() {
+            for axis in 0..3 {
+                // d/dε of R exp(ε ê) at ε = 0 is R ê.
+                let tangent = row_major(DMat3::from_quat(rot[i]) * SO3::hat(DVec3::AXES[axis]));
+                for (x, d) in inputs[i].iter_mut().zip(tangent) {
+                    x.der = d;
+                }
+                let dv: f64 = self
+                    .laws
+                    .iter()
+                    .map(|law| law.orientation_potential_dual(&inputs).der)
+                    .sum();
+                torque[axis] = -dv;
+            }
+            for x in inputs[i].iter_mut() {
+                x.der = 0.0;
+            }
+        }
+    }
+
+    /// Computes the gradient $\nabla V(q)$ into `grad`.
+    ///
+    /// Laws with an analytic [`Law::gradient`] are accumulated directly; the remaining
+    /// laws are differentiated together using the registry's [`GradientMode`].
+    pub fn gradient(&self, q: &[f64], mass: &[f64], grad: &mut [f64]) {
+        debug_assert_eq!(q.len(), grad.len());
+        grad.fill(0.0);
+
+        let mut pending: Vec<&dyn ErasedLaw> = Vec::new();
+        for law in &self.laws {
+            if !law.gradient(q, mass, grad) {
+                pending.push(law.as_ref());
+            }
+        }
+
+        if !pending.is_empty() {
+            match self.mode {
+                GradientMode::Forward => accumulate_forward(&pending, q, mass, grad),
+                GradientMode::Reverse => accumulate_reverse(&pending, q, mass, grad),
+            }
+        }
+    }
+
+    /// Forward-mode gradient of every law, ignoring analytic gradients.
+    /// DOFs are seeded `GRADIENT_CHUNK` at a time, so the full gradient takes
+    /// `ceil(dof / GRADIENT_CHUNK)` potential evaluations instead of `dof`. That is
+    /// still linear in the DOF count; large systems should use
+    /// [`gradient_reverse`](Self::gradient_reverse).
+    pub fn gradient_forward(&self, q: &[f64], mass: &[f64], grad: &mut [f64]) {
+        grad.fill(0.0);
+        accumulate_forward(&self.erased(), q, mass, grad);
+    }
+
+    /// Reverse-mode gradient of every law, ignoring analytic gradients.
+    /// The potential is recorded once and back-propagated, so the cost is a small
+    /// multiple of one evaluation regardless of the DOF count.
+    pub fn gradient_reverse(&self, q: &[f64], mass: &[f64], grad: &mut [f64]) {
+        grad.fill(0.0);
+        accumulate_reverse(&self.erased(), q, mass, grad);
+    }
+
+    fn erased(&self) -> Vec<&dyn ErasedLaw> {
+        self.laws.iter().map(|law| law.as_ref()).collect()
+    }
+
+    /// Dense Hessian $\partial^2 V / \partial q_i \partial q_j$ in row-major order (`dof * dof`).
+    ///
+    /// Each entry of the upper triangle is one hyper-dual evaluation (seed `q_i` on ε₁,
+    /// `q_j` on ε₂), so the result is exact but costs `dof * (dof + 1) / 2` evaluations.
+    pub fn hessian(&self, q: &[f64], mass: &[f64]) -> Vec<f64> {
+        let n = q.len();
+        let mut hessian = vec![0.0; n * n];
+        let mut inputs: Vec<HyperDual> = q.iter().map(|&x| HyperDual::constant(x)).collect();
+
+        for i in 0..n {
+            inputs[i].e1 = 1.0;
+            for j in i..n {
+                inputs[j].e2 = 1.0;
+                let h_ij = self.potential(&inputs, mass).e12;
+                inputs[j].e2 = 0.0;
+
+                hessian[i * n + j] = h_ij;
+                hessian[j * n + i] = h_ij;
+            }
+            inputs[i].e1 = 0.0;
         }
-        total
+
+        hessian
+    }
+
+    /// Hessian-vector product `out = H(q) v` without forming the Hessian.
+    ///
+    /// Forward over reverse: the potential is taped over dual numbers seeded with `v`,
+    /// so one backward sweep yields `∇V(q) + ε H(q) v`. Like
+    /// [`gradient_reverse`](Self::gradient_reverse) it costs a small multiple of one
+    /// evaluation regardless of the DOF count, and it ignores analytic gradients.
+    pub fn hessian_vector_product(&self, q: &[f64], mass: &[f64], v: &[f64], out: &mut [f64]) {
+        debug_assert_eq!(q.len(), v.len());
+        debug_assert_eq!(q.len(), out.len());
+        let tape = Tape::with_capacity(q.len() * 4);
+        let inputs: Vec<Var<Dual>> = q
+            .iter()
+            .zip(v)
+            .map(|(&x, &dx)| tape.var(Dual::new(x, dx)))
+            .collect();
+
+        let adjoints = self.potential(&inputs, mass).backward();
+        for (hv, &x) in out.iter_mut().zip(&inputs) {
+            *hv = adjoints.wrt(x).der;
+        }
+    }
+}
+
+fn row_major(m: DMat3) -> [f64; 9] {
+    m.transpose().to_cols_array()
+}
+
+fn sum_potential<'a, T: LawScalar>(
+    laws: impl IntoIterator<Item = &'a dyn ErasedLaw>,
+    q: &[T],
+    mass: &[f64],
+) -> T {
+    let mut total = T::constant(0.0);
+    for law in laws {
+        total = total + T::eval(law, q, mass);
+    }
+    total
+}
+
+fn accumulate_forward(laws: &[&dyn ErasedLaw], q: &[f64], mass: &[f64], grad: &mut [f64]) {
+    let mut inputs: Vec<ChunkDual> = q.iter().map(|&x| ChunkDual::constant(x)).collect();
+
+    for start in (0..q.len()).step_by(GRADIENT_CHUNK) {
+        let end = (start + GRADIENT_CHUNK).min(q.len());
+
+        for (k, input) in inputs[start..end].iter_mut().enumerate() {
+            input.der[k] = 1.0;
+        }
+
+        let potential = sum_potential(laws.iter().copied(), &inputs, mass);
+        for (g, d) in grad[start..end].iter_mut().zip(&potential.der) {
+            *g += d;
+        }
+
+        for (k, input) in inputs[start..end].iter_mut().enumerate() {
+            input.der[k] = 0.0;
+        }
+    }
+}
+
+fn accumulate_reverse(laws: &[&dyn ErasedLaw], q: &[f64], mass: &[f64], grad: &mut [f64]) {
+    let tape = Tape::with_capacity(q.len() * 4);
+    let inputs: Vec<Var> = q.iter().map(|&x| tape.var(x)).collect();
+
+    let adjoints = sum_potential(laws.iter().copied(), &inputs, mass).backward();
+    for (g, &x) in grad.iter_mut().zip(&inputs) {
+        *g += adjoints.wrt(x);
     }
 }
@@ -16,6 +16,7 @@ pub mod platform;
 
 pub mod investigation {
     pub mod probe;
+    pub mod verify;
 }
 
 pub mod simulation;
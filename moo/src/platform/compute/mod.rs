@@ -1,3 +1,8 @@
+use crate::core::geometry::shapes::{Aabb, Sdf, VoxelSdf};
+use crate::core::state::PhaseSpace;
+use glam::DVec3;
+use std::io;
+use std::sync::mpsc;
 use wgpu::util::DeviceExt;
 
 pub struct ComputeEngine {
@@ -23,6 +28,8 @@ pub struct ComputeEngine {
     offset_buffer: wgpu::Buffer,
     uniform_buffer: wgpu::Buffer,
     sort_params_buffer: wgpu::Buffer,
+    sdf_buffer: wgpu::Buffer,
+    sdf_params_buffer: wgpu::Buffer,
 
     particle_count: u32,
     grid_dim: u32,
@@ -40,6 +47,26 @@ pub struct SimConfig {
     pub mouse_pressed: bool,
 }
 
+/// How particles respond to the boundary SDF.
+#[derive(Copy, Clone, Debug)]
+pub struct BoundaryConfig {
+    /// Particles are kept this far outside the zero level set.
+    pub margin: f32,
+    pub restitution: f32,
+    /// Fraction of the tangential velocity removed on impact.
+    pub friction: f32,
+}
+
+impl Default for BoundaryConfig {
+    fn default() -> Self {
+        Self {
+            margin: 0.0,
+            restitution: 0.5,
+            friction: 0.1,
+        }
+    }
+}
+
 #[repr(C)]
 #[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
 struct Particle {
@@ -63,6 +90,45 @@ struct SimParams {
     _pad1: u32,
 }
 
+// Matches `SdfParams` in sph.wgsl (vec3 members are 16-byte aligned).
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct SdfParams {
+    origin: [f32; 3],
+    spacing: f32,
+    dims: [u32; 3],
+    margin: f32,
+    restitution: f32,
+    friction: f32,
+    _pad: [f32; 2],
+}
+
+impl SdfParams {
+    fn new(grid: &VoxelSdf, config: BoundaryConfig) -> Self {
+        Self {
+            origin: grid.origin.as_vec3().to_array(),
+            spacing: grid.spacing as f32,
+            dims: grid.dims.map(|d| d as u32),
+            margin: config.margin,
+            restitution: config.restitution,
+            friction: config.friction,
+            _pad: [0.0; 2],
+        }
+    }
+}
+
+/// The scene used until [`ComputeEngine::set_boundary`] is called: a floor at `y = -200`.
+/// A plane is linear, so the baked grid reproduces it exactly, and the extrapolation
+/// past the grid box keeps it infinite.
+fn default_boundary() -> VoxelSdf {
+    let floor = Sdf::half_space(DVec3::Y, -200.0);
+    let bounds = Aabb::new(
+        DVec3::new(-1000.0, -400.0, -25.0),
+        DVec3::new(1000.0, 1200.0, 25.0),
+    );
+    VoxelSdf::bake(&floor, bounds, 25.0)
+}
+
 // ... helper for sort params
 #[repr(C)]
 #[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
@@ -148,6 +214,18 @@ impl ComputeEngine {
             usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
         });
 
+        let boundary = default_boundary();
+        let sdf_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("SDF Buffer"),
+            contents: bytemuck::cast_slice(&boundary.values),
+            usage: wgpu::BufferUsages::STORAGE,
+        });
+        let sdf_params_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("SDF Params Buffer"),
+            contents: bytemuck::cast_slice(&[SdfParams::new(&boundary, BoundaryConfig::default())]),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
         // 2. Shaders
         let shader_sph = device.create_shader_module(wgpu::include_wgsl!("sph.wgsl"));
         let shader_grid = device.create_shader_module(wgpu::include_wgsl!("grid.wgsl"));
@@ -217,6 +295,26 @@ impl ComputeEngine {
                     },
                     count: None,
                 },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 6,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: true },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 7,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
             ],
             label: Some("Main Bind Group Layout"),
         });
@@ -311,36 +409,20 @@ impl ComputeEngine {
             cache: None,
         });
 
-        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            layout: &bind_group_layout,
-            entries: &[
-                wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: uniform_buffer.as_entire_binding(),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 1,
-                    resource: particle_buffer_a.as_entire_binding(),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 2,
-                    resource: density_buffer.as_entire_binding(),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 3,
-                    resource: particle_buffer_b.as_entire_binding(),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 4,
-                    resource: grid_buffer.as_entire_binding(),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 5,
-                    resource: offset_buffer.as_entire_binding(),
-                },
+        let bind_group = main_bind_group(
+            device,
+            &bind_group_layout,
+            [
+                &uniform_buffer,
+                &particle_buffer_a,
+                &density_buffer,
+                &particle_buffer_b,
+                &grid_buffer,
+                &offset_buffer,
+                &sdf_buffer,
+                &sdf_params_buffer,
             ],
-            label: Some("Main Bind Group"),
-        });
+        );
 
         // Sort Bind Group
         // Dynamic Offset for Params (256 byte alignment)
@@ -392,6 +474,8 @@ impl ComputeEngine {
             offset_buffer,
             uniform_buffer,
             sort_params_buffer,
+            sdf_buffer,
+            sdf_params_buffer,
             particle_count: count,
             grid_dim,
         }
@@ -519,63 +603,140 @@ impl ComputeEngine {
         std::mem::swap(&mut self.particle_buffer_a, &mut self.particle_buffer_b);
 
         // Re-create Main Bind Group for next direction
-        self.bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            layout: &self.bind_group_layout,
-            entries: &[
-                wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: self.uniform_buffer.as_entire_binding(),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 1,
-                    resource: self.particle_buffer_a.as_entire_binding(),
-                }, // New Src
-                wgpu::BindGroupEntry {
-                    binding: 2,
-                    resource: self.density_buffer.as_entire_binding(),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 3,
-                    resource: self.particle_buffer_b.as_entire_binding(),
-                }, // New Dst
-                wgpu::BindGroupEntry {
-                    binding: 4,
-                    resource: self.grid_buffer.as_entire_binding(),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 5,
-                    resource: self.offset_buffer.as_entire_binding(),
-                },
+        self.rebuild_bind_group(device);
+    }
+
+    fn rebuild_bind_group(&mut self, device: &wgpu::Device) {
+        self.bind_group = main_bind_group(
+            device,
+            &self.bind_group_layout,
+            [
+                &self.uniform_buffer,
+                &self.particle_buffer_a, // Src
+                &self.density_buffer,
+                &self.particle_buffer_b, // Dst
+                &self.grid_buffer,
+                &self.offset_buffer,
+                &self.sdf_buffer,
+                &self.sdf_params_buffer,
             ],
-            label: Some("SPH Bind Group"),
+        );
+    }
+
+    /// Replaces the boundary geometry that `calc_force` resolves collisions against.
+    /// Particles are pushed to the positive side of `sdf`.
+    pub fn set_boundary(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        sdf: &VoxelSdf,
+        config: BoundaryConfig,
+    ) {
+        self.sdf_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("SDF Buffer"),
+            contents: bytemuck::cast_slice(&sdf.values),
+            usage: wgpu::BufferUsages::STORAGE,
         });
+        queue.write_buffer(
+            &self.sdf_params_buffer,
+            0,
+            bytemuck::cast_slice(&[SdfParams::new(sdf, config)]),
+        );
+        self.rebuild_bind_group(device);
     }
 
     pub fn current_buffer(&self) -> &wgpu::Buffer {
         &self.particle_buffer_a
     }
 
-    pub fn write_state(&self, queue: &wgpu::Queue, q: &[f64], v: &[f64], mass: &[f64]) {
+    /// Uploads particle positions, velocities and masses.
+    /// Planar states are written with `z = 0`.
+    pub fn write_state(&self, queue: &wgpu::Queue, state: &PhaseSpace) {
         let count = self.particle_count as usize;
         let mut data = Vec::with_capacity(count);
         for i in 0..count {
-            let idx = i * 3;
-            let m_stride = if mass.len() == q.len() { 3 } else { 1 };
+            let p = state.position(i).as_vec3();
+            let v = state.velocity(i).as_vec3();
+            let m = state.mass[i * state.dim];
 
             data.push(Particle {
-                pos: [
-                    q[idx] as f32,
-                    q[idx + 1] as f32,
-                    q[idx + 2] as f32,
-                    mass[i * m_stride] as f32,
-                ],
-                vel: [v[idx] as f32, v[idx + 1] as f32, v[idx + 2] as f32, 0.0],
+                pos: [p.x, p.y, p.z, m as f32],
+                vel: [v.x, v.y, v.z, 0.0],
             });
         }
         // Write to current read source
         queue.write_buffer(&self.particle_buffer_a, 0, bytemuck::cast_slice(&data));
     }
 
+    /// Copies the particles back from the GPU (blocking) into a fresh 3D state with
+    /// positions, velocities and masses, e.g. for export.
+    ///
+    /// Fails if the readback buffer cannot be mapped or the device is lost.
+    pub fn read_state(&self, device: &wgpu::Device, queue: &wgpu::Queue) -> io::Result<PhaseSpace> {
+        let count = self.particle_count as usize;
+        let mut state = PhaseSpace::new(count * 3);
+        if count == 0 {
+            return Ok(state);
+        }
+
+        let size = (count * std::mem::size_of::<Particle>()) as u64;
+        let staging = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Particle Readback"),
+            size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Particle Readback Encoder"),
+        });
+        encoder.copy_buffer_to_buffer(&self.particle_buffer_a, 0, &staging, 0, size);
+        queue.submit(Some(encoder.finish()));
+
+        let slice = staging.slice(..);
+        let (sender, receiver) = mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            // The receiver outlives the blocking poll below.
+            let _ = sender.send(result);
+        });
+        device
+            .poll(wgpu::PollType::wait_indefinitely())
+            .map_err(io::Error::other)?;
+        receiver
+            .recv()
+            .map_err(io::Error::other)?
+            .map_err(io::Error::other)?;
+
+        {
+            let data = slice.get_mapped_range();
+            let particles: &[Particle] = bytemuck::cast_slice(&data);
+            for (i, p) in particles.iter().enumerate() {
+                state.set_position(i, glam::Vec3::from_slice(&p.pos[..3]).as_dvec3());
+                state.set_velocity(i, glam::Vec3::from_slice(&p.vel[..3]).as_dvec3());
+                state.set_particle_mass(i, p.pos[3] as f64);
+            }
+        }
+        staging.unmap();
+        Ok(state)
+    }
+
+    /// Uploads attribute channel `name` as a storage buffer (one `f32`, `u32` or
+    /// `vec4<f32>` per particle), or `None` if the state has no such channel.
+    pub fn attribute_buffer(
+        &self,
+        device: &wgpu::Device,
+        state: &PhaseSpace,
+        name: &str,
+    ) -> Option<wgpu::Buffer> {
+        let bytes = state.attributes.channel(name)?.to_gpu_bytes();
+        Some(
+            device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some(name),
+                contents: &bytes,
+                usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+            }),
+        )
+    }
+
     pub fn write_params(&self, queue: &wgpu::Queue, config: SimConfig) {
         let params = SimParams {
             dt: config.dt,
@@ -593,3 +754,24 @@ impl ComputeEngine {
         queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[params]));
     }
 }
+
+/// Bind group for the SPH and grid passes; `buffers` are bound in order from binding 0.
+fn main_bind_group(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    buffers: [&wgpu::Buffer; 8],
+) -> wgpu::BindGroup {
+    let entries: Vec<wgpu::BindGroupEntry> = buffers
+        .iter()
+        .enumerate()
+        .map(|(binding, buffer)| wgpu::BindGroupEntry {
+            binding: binding as u32,
+            resource: buffer.as_entire_binding(),
+        })
+        .collect();
+    device.create_bind_group(&wgpu::BindGroupDescriptor {
+        layout,
+        entries: &entries,
+        label: Some("Main Bind Group"),
+    })
+}
@@ -16,6 +16,17 @@ struct Particle {
     vel: vec4<f32>, // xyz
 }
 
+// Boundary geometry: a signed distance field on a regular grid (x fastest).
+struct SdfParams {
+    origin: vec3<f32>,
+    spacing: f32,
+    dims: vec3<u32>,
+    margin: f32,      // Keep particles this far outside the surface
+    restitution: f32,
+    friction: f32,
+    _pad: vec2<f32>,
+}
+
 struct GridPair {
     cell_id: u32,
     particle_id: u32,
@@ -27,6 +38,8 @@ struct GridPair {
 @group(0) @binding(3) var<storage, read_write> particlesDst: array<Particle>;
 @group(0) @binding(4) var<storage, read_write> grid_pairs: array<GridPair>;
 @group(0) @binding(5) var<storage, read_write> cell_offsets: array<u32>;
+@group(0) @binding(6) var<storage, read> sdf_values: array<f32>;
+@group(0) @binding(7) var<uniform> sdf: SdfParams;
 
 const PI: f32 = 3.1415926535;
 
@@ -46,6 +59,61 @@ fn spiky_grad(r_vec: vec3<f32>, r: f32, h: f32) -> vec3<f32> {
     return r_vec * scalar;
 }
 
+fn sdf_at(c: vec3<i32>) -> f32 {
+    let d = vec3<i32>(sdf.dims);
+    let cc = clamp(c, vec3<i32>(0), d - 1);
+    return sdf_values[u32(cc.x + d.x * (cc.y + d.y * cc.z))];
+}
+
+// Trilinear sample. Outside the grid, extend the sample at the nearest point of the
+// grid box: by the distance to the box outside the shape, along the interpolated
+// gradient inside it, so a floor cut off by the box stays solid (see VoxelSdf::distance).
+fn sdf_distance(p: vec3<f32>) -> f32 {
+    let last = vec3<f32>(sdf.dims - 1u) * sdf.spacing;
+    let q = clamp(p, sdf.origin, sdf.origin + last);
+    let g = (q - sdf.origin) / sdf.spacing;
+    let base = min(vec3<i32>(floor(g)), vec3<i32>(sdf.dims) - 2);
+    let f = g - vec3<f32>(base);
+
+    let c000 = sdf_at(base);
+    let c100 = sdf_at(base + vec3<i32>(1, 0, 0));
+    let c010 = sdf_at(base + vec3<i32>(0, 1, 0));
+    let c110 = sdf_at(base + vec3<i32>(1, 1, 0));
+    let c001 = sdf_at(base + vec3<i32>(0, 0, 1));
+    let c101 = sdf_at(base + vec3<i32>(1, 0, 1));
+    let c011 = sdf_at(base + vec3<i32>(0, 1, 1));
+    let c111 = sdf_at(base + vec3<i32>(1, 1, 1));
+    let x00 = mix(c000, c100, f.x);
+    let x10 = mix(c010, c110, f.x);
+    let x01 = mix(c001, c101, f.x);
+    let x11 = mix(c011, c111, f.x);
+    let y0 = mix(x00, x10, f.y);
+    let y1 = mix(x01, x11, f.y);
+    let d = mix(y0, y1, f.z);
+    if (d >= 0.0) {
+        return d + length(p - q);
+    }
+
+    let grad = vec3<f32>(
+        mix(mix(c100 - c000, c110 - c010, f.y), mix(c101 - c001, c111 - c011, f.y), f.z),
+        mix(x10 - x00, x11 - x01, f.z),
+        y1 - y0,
+    ) / sdf.spacing;
+    return d + dot(grad, p - q);
+}
+
+fn sdf_normal(p: vec3<f32>) -> vec3<f32> {
+    let e = 0.5 * sdf.spacing;
+    let n = vec3<f32>(
+        sdf_distance(p + vec3<f32>(e, 0.0, 0.0)) - sdf_distance(p - vec3<f32>(e, 0.0, 0.0)),
+        sdf_distance(p + vec3<f32>(0.0, e, 0.0)) - sdf_distance(p - vec3<f32>(0.0, e, 0.0)),
+        sdf_distance(p + vec3<f32>(0.0, 0.0, e)) - sdf_distance(p - vec3<f32>(0.0, 0.0, e)),
+    );
+    let len = length(n);
+    if (len < 1e-12) { return vec3<f32>(0.0, 1.0, 0.0); }
+    return n / len;
+}
+
 @compute @workgroup_size(256)
 fn calc_density(@builtin(global_invocation_id) global_id: vec3<u32>) {
     let i = global_id.x;
@@ -213,14 +281,19 @@ fn calc_force(@builtin(global_invocation_id) global_id: vec3<u32>) {
     let vel = pi.vel.xyz + accel * params.dt;
     var pos = pi.pos.xyz + vel * params.dt;
 
-    // Floor Constraint
-    if (pos.y < -200.0) {
-        pos.y = -200.0;
-        let damp = 0.5;
-        particlesDst[i].vel = vec4<f32>(vel.x * 0.9, -vel.y * damp, vel.z * 0.9, 0.0);
-    } else {
-        particlesDst[i].vel = vec4<f32>(vel, 0.0);
+    // Boundary Constraint (SDF)
+    var new_vel = vel;
+    let d = sdf_distance(pos);
+    if (d < sdf.margin) {
+        let n = sdf_normal(pos);
+        pos += n * (sdf.margin - d);
+        let vn = dot(vel, n);
+        if (vn < 0.0) {
+            let vt = vel - n * vn;
+            new_vel = vt * (1.0 - sdf.friction) - n * vn * sdf.restitution;
+        }
     }
+    particlesDst[i].vel = vec4<f32>(new_vel, 0.0);
 
     particlesDst[i].pos = vec4<f32>(pos, mass_i);
 }
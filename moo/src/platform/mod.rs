@@ -1,2 +1,2 @@
 pub mod compute;
-pub mod storage {} // Still placeholder
+pub mod storage;
@@ -0,0 +1,391 @@
+use crate::core::state::{Channel, PhaseSpace};
+use glam::{DQuat, DVec3};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// File formats understood by ParaView (`.vtk`, `.vtu`) and OVITO (`.xyz`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// Legacy ASCII VTK polydata.
+    VtkLegacy,
+    /// XML VTK unstructured grid.
+    Vtu,
+    /// Extended XYZ (one frame per file).
+    ExtendedXyz,
+}
+
+impl ExportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::VtkLegacy => "vtk",
+            ExportFormat::Vtu => "vtu",
+            ExportFormat::ExtendedXyz => "xyz",
+        }
+    }
+
+    /// Writes one frame of `state` in this format.
+    pub fn write(self, state: &PhaseSpace, out: impl Write) -> io::Result<()> {
+        match self {
+            ExportFormat::VtkLegacy => write_vtk(state, out),
+            ExportFormat::Vtu => write_vtu(state, out),
+            ExportFormat::ExtendedXyz => write_xyz(state, out),
+        }
+    }
+}
+
+enum Values {
+    Real(Vec<f64>),
+    Index(Vec<u32>),
+}
+
+/// One per-particle array: `components` values per particle.
+struct PointArray {
+    name: String,
+    components: usize,
+    values: Values,
+}
+
+impl PointArray {
+    fn real(name: &str, components: usize, values: Vec<f64>) -> Self {
+        Self {
+            name: name.to_string(),
+            components,
+            values: Values::Real(values),
+        }
+    }
+
+    fn write_tuple(&self, out: &mut impl Write, i: usize) -> io::Result<()> {
+        let range = i * self.components..(i + 1) * self.components;
+        for (k, j) in range.enumerate() {
+            let sep = if k == 0 { "" } else { " " };
+            match &self.values {
+                Values::Real(v) => write!(out, "{sep}{}", v[j])?,
+                Values::Index(v) => write!(out, "{sep}{}", v[j])?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Particle positions, 3D (planar states get `z = 0`).
+fn points(state: &PhaseSpace) -> Vec<DVec3> {
+    (0..state.particle_count())
+        .map(|i| state.position(i))
+        .collect()
+}
+
+/// Velocity, mass, radius, rigid-body orientation and angular velocity (identity and
+/// zero for plain particles) and every attribute channel.
+fn point_arrays(state: &PhaseSpace) -> Vec<PointArray> {
+    let n = state.particle_count();
+
+    let mut arrays = vec![
+        PointArray::real("velocity", 3, vec3s((0..n).map(|i| state.velocity(i)))),
+        PointArray::real(
+            "mass",
+            1,
+            (0..n).map(|i| state.mass[i * state.dim]).collect(),
+        ),
+        PointArray::real("radius", 1, state.radius.clone()),
+    ];
+    if !state.rot.is_empty() {
+        let rot = (0..n).map(|i| state.rot.get(i).copied().unwrap_or(DQuat::IDENTITY));
+        arrays.push(PointArray::real(
+            "orientation",
+            4,
+            rot.flat_map(|q| q.to_array()).collect(),
+        ));
+        let omega = (0..n).map(|i| state.ang_v.get(i).copied().unwrap_or(DVec3::ZERO));
+        arrays.push(PointArray::real("angular_velocity", 3, vec3s(omega)));
+    }
+    for name in state.attributes.names() {
+        let array = match state.attributes.channel(name) {
+            Some(Channel::Scalar { values, .. }) => PointArray::real(name, 1, values.clone()),
+            Some(Channel::Index { values, .. }) => PointArray {
+                name: name.to_string(),
+                components: 1,
+                values: Values::Index(values.clone()),
+            },
+            Some(Channel::Vector { values, .. }) => {
+                PointArray::real(name, 3, vec3s(values.iter().copied()))
+            }
+            Some(Channel::Color { values, .. }) => PointArray::real(
+                name,
+                4,
+                values
+                    .iter()
+                    .flat_map(|c| c.to_array())
+                    .map(f64::from)
+                    .collect(),
+            ),
+            None => continue,
+        };
+        arrays.push(array);
+    }
+    arrays
+}
+
+fn vec3s(values: impl Iterator<Item = DVec3>) -> Vec<f64> {
+    values.flat_map(|v| v.to_array()).collect()
+}
+
+/// Names in legacy VTK and extended XYZ headers cannot contain whitespace (or `:`).
+fn token(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_whitespace() || c == ':' {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+/// Legacy ASCII VTK: a polydata point cloud with one vertex cell per particle.
+pub fn write_vtk(state: &PhaseSpace, out: impl Write) -> io::Result<()> {
+    let mut out = BufWriter::new(out);
+    let points = points(state);
+    let n = points.len();
+
+    writeln!(out, "# vtk DataFile Version 3.0")?;
+    writeln!(out, "moo t={}", state.t)?;
+    writeln!(out, "ASCII")?;
+    writeln!(out, "DATASET POLYDATA")?;
+    writeln!(out, "FIELD FieldData 1")?;
+    writeln!(out, "TIME 1 1 double")?;
+    writeln!(out, "{}", state.t)?;
+    writeln!(out, "POINTS {n} double")?;
+    for p in &points {
+        writeln!(out, "{} {} {}", p.x, p.y, p.z)?;
+    }
+    writeln!(out, "VERTICES {n} {}", 2 * n)?;
+    for i in 0..n {
+        writeln!(out, "1 {i}")?;
+    }
+
+    writeln!(out, "POINT_DATA {n}")?;
+    for array in point_arrays(state) {
+        let name = token(&array.name);
+        let ty = match array.values {
+            Values::Real(_) => "double",
+            Values::Index(_) => "unsigned_int",
+        };
+        if array.components == 3 {
+            writeln!(out, "VECTORS {name} {ty}")?;
+        } else {
+            writeln!(out, "SCALARS {name} {ty} {}", array.components)?;
+            writeln!(out, "LOOKUP_TABLE default")?;
+        }
+        for i in 0..n {
+            array.write_tuple(&mut out, i)?;
+            writeln!(out)?;
+        }
+    }
+    out.flush()
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// XML VTK unstructured grid (ASCII data arrays), with the time as `TimeValue` field data.
+pub fn write_vtu(state: &PhaseSpace, out: impl Write) -> io::Result<()> {
+    let mut out = BufWriter::new(out);
+    let points = points(state);
+    let n = points.len();
+
+    writeln!(out, r#"<?xml version="1.0"?>"#)?;
+    writeln!(
+        out,
+        r#"<VTKFile type="UnstructuredGrid" version="1.0" byte_order="LittleEndian" header_type="UInt64">"#
+    )?;
+    writeln!(out, "  <UnstructuredGrid>")?;
+    writeln!(out, "    <FieldData>")?;
+    writeln!(
+        out,
+        r#"      <DataArray type="Float64" Name="TimeValue" NumberOfTuples="1" format="ascii">{}</DataArray>"#,
+        state.t
+    )?;
+    writeln!(out, "    </FieldData>")?;
+    writeln!(
+        out,
+        r#"    <Piece NumberOfPoints="{n}" NumberOfCells="{n}">"#
+    )?;
+
+    writeln!(out, "      <PointData>")?;
+    for array in point_arrays(state) {
+        let ty = match array.values {
+            Values::Real(_) => "Float64",
+            Values::Index(_) => "UInt32",
+        };
+        writeln!(
+            out,
+            r#"        <DataArray type="{ty}" Name="{}" NumberOfComponents="{}" format="ascii">"#,
+            xml_escape(&array.name),
+            array.components
+        )?;
+        for i in 0..n {
+            array.write_tuple(&mut out, i)?;
+            writeln!(out)?;
+        }
+        writeln!(out, "        </DataArray>")?;
+    }
+    writeln!(out, "      </PointData>")?;
+
+    writeln!(out, "      <Points>")?;
+    writeln!(
+        out,
+        r#"        <DataArray type="Float64" Name="position" NumberOfComponents="3" format="ascii">"#
+    )?;
+    for p in &points {
+        writeln!(out, "{} {} {}", p.x, p.y, p.z)?;
+    }
+    writeln!(out, "        </DataArray>")?;
+    writeln!(out, "      </Points>")?;
+
+    // One VTK_VERTEX (type 1) per particle.
+    writeln!(out, "      <Cells>")?;
+    write_cells(&mut out, "Int64", "connectivity", 0..n)?;
+    write_cells(&mut out, "Int64", "offsets", 1..n + 1)?;
+    write_cells(&mut out, "UInt8", "types", std::iter::repeat_n(1, n))?;
+    writeln!(out, "      </Cells>")?;
+
+    writeln!(out, "    </Piece>")?;
+    writeln!(out, "  </UnstructuredGrid>")?;
+    writeln!(out, "</VTKFile>")?;
+    out.flush()
+}
+
+fn write_cells(
+    out: &mut impl Write,
+    ty: &str,
+    name: &str,
+    values: impl Iterator<Item = usize>,
+) -> io::Result<()> {
+    writeln!(
+        out,
+        r#"        <DataArray type="{ty}" Name="{name}" format="ascii">"#
+    )?;
+    for value in values {
+        writeln!(out, "{value}")?;
+    }
+    writeln!(out, "        </DataArray>")
+}
+
+/// Extended XYZ: a particle count, a `Properties=` comment line, then one row per particle.
+/// Columns follow OVITO/ASE naming (`pos`, `velo`, `mass`, `radius`).
+pub fn write_xyz(state: &PhaseSpace, out: impl Write) -> io::Result<()> {
+    let mut out = BufWriter::new(out);
+    let points = points(state);
+    let mut arrays = point_arrays(state);
+    arrays[0].name = "velo".to_string();
+
+    let mut properties = "species:S:1:pos:R:3".to_string();
+    for array in &arrays {
+        let ty = match array.values {
+            Values::Real(_) => "R",
+            Values::Index(_) => "I",
+        };
+        properties += &format!(":{}:{ty}:{}", token(&array.name), array.components);
+    }
+
+    writeln!(out, "{}", points.len())?;
+    writeln!(
+        out,
+        r#"Properties={properties} Time={} pbc="F F F""#,
+        state.t
+    )?;
+    for (i, p) in points.iter().enumerate() {
+        write!(out, "X {} {} {}", p.x, p.y, p.z)?;
+        for array in &arrays {
+            write!(out, " ")?;
+            array.write_tuple(&mut out, i)?;
+        }
+        writeln!(out)?;
+    }
+    out.flush()
+}
+
+/// A ParaView `.pvd` collection: one `(time, file)` entry per frame.
+pub fn write_pvd<'a>(
+    entries: impl IntoIterator<Item = (f64, &'a str)>,
+    out: impl Write,
+) -> io::Result<()> {
+    let mut out = BufWriter::new(out);
+    writeln!(out, r#"<?xml version="1.0"?>"#)?;
+    writeln!(out, r#"<VTKFile type="Collection" version="1.0">"#)?;
+    writeln!(out, "  <Collection>")?;
+    for (t, file) in entries {
+        writeln!(
+            out,
+            r#"    <DataSet timestep="{t}" part="0" file="{}"/>"#,
+            xml_escape(file)
+        )?;
+    }
+    writeln!(out, "  </Collection>")?;
+    writeln!(out, "</VTKFile>")?;
+    out.flush()
+}
+
+/// Writes numbered frames (`{stem}_00000.vtu`, ...) into a directory.
+///
+/// For the VTK formats the `{stem}.pvd` series index is rewritten after every
+/// frame, so ParaView can open a run that is still in progress (or crashed).
+/// OVITO picks up extended XYZ sequences by their numbered file names.
+pub struct FrameExporter {
+    dir: PathBuf,
+    stem: String,
+    format: ExportFormat,
+    frames: Vec<(f64, String)>,
+}
+
+impl FrameExporter {
+    /// Creates `dir` if needed.
+    pub fn new(dir: impl AsRef<Path>, stem: &str, format: ExportFormat) -> io::Result<Self> {
+        std::fs::create_dir_all(dir.as_ref())?;
+        Ok(Self {
+            dir: dir.as_ref().to_path_buf(),
+            stem: stem.to_string(),
+            format,
+            frames: Vec::new(),
+        })
+    }
+
+    /// Number of frames written so far.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Writes the next frame and returns its path.
+    pub fn write(&mut self, state: &PhaseSpace) -> io::Result<PathBuf> {
+        let file = format!(
+            "{}_{:05}.{}",
+            self.stem,
+            self.frames.len(),
+            self.format.extension()
+        );
+        let path = self.dir.join(&file);
+        self.format.write(state, File::create(&path)?)?;
+        self.frames.push((state.t, file));
+
+        if self.format != ExportFormat::ExtendedXyz {
+            let entries = self.frames.iter().map(|(t, f)| (*t, f.as_str()));
+            write_pvd(entries, File::create(self.pvd_path())?)?;
+        }
+        Ok(path)
+    }
+
+    /// Path of the `.pvd` series index.
+    pub fn pvd_path(&self) -> PathBuf {
+        self.dir.join(format!("{}.pvd", self.stem))
+    }
+}
@@ -0,0 +1,8 @@
+pub mod export;
+pub mod trajectory;
+
+pub use export::{ExportFormat, FrameExporter, write_pvd, write_vtk, write_vtu, write_xyz};
+pub use trajectory::{
+    Compression, Field, Frame, TRAJECTORY_VERSION, TrajectoryMeta, TrajectoryReader,
+    TrajectoryWriter,
+};
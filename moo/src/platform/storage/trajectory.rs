@@ -0,0 +1,549 @@
+use crate::core::state::{Channel, PhaseSpace};
+use crate::investigation::probe::Probe;
+use crate::laws::registry::LawRegistry;
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use glam::DVec3;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+/// Format version written after the magic number; readers accept `1..=TRAJECTORY_VERSION`.
+pub const TRAJECTORY_VERSION: u32 = 1;
+
+const MAGIC: &[u8; 8] = b"MOOTRAJ\0";
+/// Trailer magic, preceded by the byte offset of the chunk index.
+const END_MAGIC: &[u8; 8] = b"MOOTEND\0";
+/// Magic, version and metadata length.
+const HEADER_LEN: u64 = 8 + 4 + 8;
+/// Codec byte, frame count and payload length.
+const CHUNK_HEADER_LEN: u64 = 1 + 4 + 8;
+const TRAILER_LEN: u64 = 8 + 8;
+
+const CODEC_RAW: u8 = 0;
+const CODEC_DEFLATE: u8 = 1;
+
+/// A quantity copied out of the [`PhaseSpace`] into every frame.
+///
+/// Each field is stored as one flat `f64` array, so the particle count may change
+/// between frames (emitters, sinks).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Field {
+    Positions,
+    Velocities,
+    Masses,
+    Radii,
+    /// Rigid-body orientations as `(x, y, z, w)` quaternions.
+    Rotations,
+    AngularVelocities,
+    Directors,
+    DirectorVelocities,
+    /// An attribute channel; vectors and colors are interleaved. Empty if missing.
+    Attribute(String),
+}
+
+impl Field {
+    fn extract(&self, state: &PhaseSpace) -> Vec<f64> {
+        fn vec3s(values: &[DVec3]) -> Vec<f64> {
+            values.iter().flat_map(|v| v.to_array()).collect()
+        }
+        match self {
+            Field::Positions => state.q.clone(),
+            Field::Velocities => state.v.clone(),
+            Field::Masses => state.mass.clone(),
+            Field::Radii => state.radius.clone(),
+            Field::Rotations => state.rot.iter().flat_map(|r| r.to_array()).collect(),
+            Field::AngularVelocities => vec3s(&state.ang_v),
+            Field::Directors => vec3s(&state.dir),
+            Field::DirectorVelocities => vec3s(&state.dir_v),
+            Field::Attribute(name) => match state.attributes.channel(name) {
+                Some(Channel::Scalar { values, .. }) => values.clone(),
+                Some(Channel::Index { values, .. }) => values.iter().map(|&i| i as f64).collect(),
+                Some(Channel::Vector { values, .. }) => vec3s(values),
+                Some(Channel::Color { values, .. }) => values
+                    .iter()
+                    .flat_map(|c| c.to_array())
+                    .map(f64::from)
+                    .collect(),
+                None => Vec::new(),
+            },
+        }
+    }
+}
+
+/// Self-description stored (as RON) at the head of a trajectory file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrajectoryMeta {
+    pub dt: f64,
+    /// As given to [`TrajectoryMeta::new`].
+    pub integrator: String,
+    /// [`Law::describe`](crate::laws::registry::Law::describe) of each law.
+    pub laws: Vec<String>,
+    /// What each frame holds, in [`Frame::fields`] order.
+    pub fields: Vec<Field>,
+    /// Probe names, in [`Frame::probes`] order.
+    pub probes: Vec<String>,
+    /// Free-form notes (units, seeds, parameters, ...).
+    pub notes: BTreeMap<String, String>,
+}
+
+impl TrajectoryMeta {
+    /// Describes a run with step `dt` over `laws`, integrated by `integrator`
+    /// (a name or a fuller description, e.g. `"Composition::yoshida4"`).
+    /// Records positions and velocities unless told otherwise.
+    pub fn new(integrator: impl Into<String>, laws: &LawRegistry, dt: f64) -> Self {
+        Self {
+            dt,
+            integrator: integrator.into(),
+            laws: laws.descriptions().collect(),
+            fields: vec![Field::Positions, Field::Velocities],
+            probes: Vec::new(),
+            notes: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_fields(mut self, fields: impl IntoIterator<Item = Field>) -> Self {
+        self.fields = fields.into_iter().collect();
+        self
+    }
+
+    /// Names the probes whose values will be passed to [`TrajectoryWriter::record`].
+    pub fn with_probes(mut self, probes: &[&dyn Probe]) -> Self {
+        self.probes = probes.iter().map(|p| p.name().to_string()).collect();
+        self
+    }
+
+    pub fn with_note(mut self, key: &str, value: impl ToString) -> Self {
+        self.notes.insert(key.to_string(), value.to_string());
+        self
+    }
+
+    pub fn field_index(&self, field: &Field) -> Option<usize> {
+        self.fields.iter().position(|f| f == field)
+    }
+
+    pub fn probe_index(&self, name: &str) -> Option<usize> {
+        self.probes.iter().position(|p| p == name)
+    }
+}
+
+/// One recorded instant.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Frame {
+    pub t: f64,
+    /// One flat array per entry of [`TrajectoryMeta::fields`].
+    pub fields: Vec<Vec<f64>>,
+    /// One value per entry of [`TrajectoryMeta::probes`].
+    pub probes: Vec<f64>,
+}
+
+/// How chunks are compressed (DEFLATE).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Compression {
+    None,
+    #[default]
+    Fast,
+    Best,
+}
+
+/// Where a chunk lives and which frames it holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct ChunkEntry {
+    offset: u64,
+    first_frame: u64,
+    frames: u32,
+}
+
+impl ChunkEntry {
+    fn end_frame(&self) -> u64 {
+        self.first_frame + u64::from(self.frames)
+    }
+}
+
+/// Appends frames to a chunked trajectory file.
+///
+/// Layout: a header (magic, version, RON metadata), then chunks of
+/// [`chunk_frames`](Self::with_chunk_frames) frames each (codec, frame count,
+/// payload length, bincode frames, optionally deflated), then a chunk index and a
+/// trailer pointing at it. The index is written by [`finish`](Self::finish) (or on
+/// drop); files cut short before that are recovered by scanning the chunks.
+pub struct TrajectoryWriter<W: Write> {
+    inner: Option<W>,
+    meta: TrajectoryMeta,
+    chunk_frames: usize,
+    compression: Compression,
+    pending: Vec<Frame>,
+    index: Vec<ChunkEntry>,
+    /// Bytes written so far.
+    offset: u64,
+    /// Frames pushed so far, including the pending ones.
+    frames: u64,
+}
+
+impl TrajectoryWriter<BufWriter<File>> {
+    pub fn create(path: impl AsRef<Path>, meta: TrajectoryMeta) -> io::Result<Self> {
+        Self::new(BufWriter::new(File::create(path)?), meta)
+    }
+}
+
+impl<W: Write> TrajectoryWriter<W> {
+    pub const DEFAULT_CHUNK_FRAMES: usize = 64;
+
+    /// Writes the header to `inner`.
+    pub fn new(mut inner: W, meta: TrajectoryMeta) -> io::Result<Self> {
+        let text = ron::to_string(&meta).map_err(invalid)?;
+        inner.write_all(MAGIC)?;
+        inner.write_all(&TRAJECTORY_VERSION.to_le_bytes())?;
+        inner.write_all(&(text.len() as u64).to_le_bytes())?;
+        inner.write_all(text.as_bytes())?;
+        Ok(Self {
+            inner: Some(inner),
+            meta,
+            chunk_frames: Self::DEFAULT_CHUNK_FRAMES,
+            compression: Compression::default(),
+            pending: Vec::new(),
+            index: Vec::new(),
+            offset: HEADER_LEN + text.len() as u64,
+            frames: 0,
+        })
+    }
+
+    /// Frames per chunk: larger chunks compress better, smaller ones make random
+    /// access cheaper.
+    pub fn with_chunk_frames(mut self, frames: usize) -> Self {
+        self.chunk_frames = frames.clamp(1, u32::MAX as usize);
+        self
+    }
+
+    pub fn with_compression(mut self, compression: Compression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    pub fn meta(&self) -> &TrajectoryMeta {
+        &self.meta
+    }
+
+    /// Number of frames recorded so far.
+    pub fn len(&self) -> usize {
+        self.frames as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames == 0
+    }
+
+    /// Records the configured fields of `state` and the value of each probe,
+    /// which must match [`TrajectoryMeta::probes`].
+    pub fn record(
+        &mut self,
+        state: &PhaseSpace,
+        laws: &LawRegistry,
+        probes: &[&dyn Probe],
+    ) -> io::Result<()> {
+        let frame = Frame {
+            t: state.t,
+            fields: self.meta.fields.iter().map(|f| f.extract(state)).collect(),
+            probes: probes.iter().map(|p| p.measure(state, laws)).collect(),
+        };
+        self.push(frame)
+    }
+
+    /// Appends a prepared frame.
+    pub fn push(&mut self, frame: Frame) -> io::Result<()> {
+        if frame.fields.len() != self.meta.fields.len()
+            || frame.probes.len() != self.meta.probes.len()
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "frame has {} fields and {} probes, the trajectory records {} and {}",
+                    frame.fields.len(),
+                    frame.probes.len(),
+                    self.meta.fields.len(),
+                    self.meta.probes.len()
+                ),
+            ));
+        }
+        self.pending.push(frame);
+        self.frames += 1;
+        if self.pending.len() >= self.chunk_frames {
+            self.write_chunk()?;
+        }
+        Ok(())
+    }
+
+    /// Writes the pending frames as a (possibly short) chunk and flushes the output,
+    /// so everything recorded so far survives a crash.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.write_chunk()?;
+        self.output().flush()
+    }
+
+    /// Writes the last chunk and the index, and hands back the output.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.write_tail()?;
+        Ok(self.inner.take().expect("writer already finished"))
+    }
+
+    fn output(&mut self) -> &mut W {
+        self.inner.as_mut().expect("writer already finished")
+    }
+
+    fn write_chunk(&mut self) -> io::Result<()> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        let raw = bincode::serialize(&self.pending).map_err(invalid)?;
+        let (codec, payload) = match self.compression {
+            Compression::None => (CODEC_RAW, raw),
+            level => {
+                let level = match level {
+                    Compression::Best => flate2::Compression::best(),
+                    _ => flate2::Compression::fast(),
+                };
+                let mut encoder = DeflateEncoder::new(Vec::new(), level);
+                encoder.write_all(&raw)?;
+                (CODEC_DEFLATE, encoder.finish()?)
+            }
+        };
+
+        let frames = self.pending.len() as u32;
+        let out = self.output();
+        out.write_all(&[codec])?;
+        out.write_all(&frames.to_le_bytes())?;
+        out.write_all(&(payload.len() as u64).to_le_bytes())?;
+        out.write_all(&payload)?;
+
+        self.index.push(ChunkEntry {
+            offset: self.offset,
+            first_frame: self.frames - u64::from(frames),
+            frames,
+        });
+        self.offset += CHUNK_HEADER_LEN + payload.len() as u64;
+        self.pending.clear();
+        Ok(())
+    }
+
+    fn write_tail(&mut self) -> io::Result<()> {
+        self.write_chunk()?;
+        let index = bincode::serialize(&self.index).map_err(invalid)?;
+        let index_offset = self.offset;
+        let out = self.output();
+        out.write_all(&index)?;
+        out.write_all(&index_offset.to_le_bytes())?;
+        out.write_all(END_MAGIC)?;
+        out.flush()
+    }
+}
+
+impl<W: Write> Drop for TrajectoryWriter<W> {
+    fn drop(&mut self) {
+        if self.inner.is_some() {
+            let _ = self.write_tail();
+        }
+    }
+}
+
+/// Random access to the frames of a trajectory file.
+///
+/// Only the chunk holding the requested frame is read and decompressed; the most
+/// recent chunk is cached, so sequential reads decode each chunk once.
+pub struct TrajectoryReader<R: Read + Seek> {
+    inner: R,
+    meta: TrajectoryMeta,
+    index: Vec<ChunkEntry>,
+    cached: Option<(usize, Vec<Frame>)>,
+}
+
+impl TrajectoryReader<BufReader<File>> {
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read + Seek> TrajectoryReader<R> {
+    pub fn new(mut inner: R) -> io::Result<Self> {
+        inner.seek(SeekFrom::Start(0))?;
+        let mut magic = [0; 8];
+        inner.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid("not a moo trajectory"));
+        }
+        let version = read_u32(&mut inner)?;
+        if version == 0 || version > TRAJECTORY_VERSION {
+            return Err(invalid(format!(
+                "trajectory version {version} is not supported (this build reads 1..={TRAJECTORY_VERSION})"
+            )));
+        }
+        let meta_len = read_u64(&mut inner)?;
+        let text = read_bytes(&mut inner, meta_len)?;
+        let text = std::str::from_utf8(&text).map_err(invalid)?;
+        let meta = ron::from_str(text).map_err(invalid)?;
+
+        let data_start = HEADER_LEN + meta_len;
+        let index = match read_index(&mut inner, data_start)? {
+            Some(index) => index,
+            None => scan_chunks(&mut inner, data_start)?,
+        };
+        Ok(Self {
+            inner,
+            meta,
+            index,
+            cached: None,
+        })
+    }
+
+    pub fn meta(&self) -> &TrajectoryMeta {
+        &self.meta
+    }
+
+    /// Number of frames in the file.
+    pub fn len(&self) -> usize {
+        self.index.last().map_or(0, |c| c.end_frame() as usize)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Frame number `index`.
+    pub fn frame(&mut self, index: usize) -> io::Result<Frame> {
+        let chunk = self
+            .index
+            .partition_point(|c| c.end_frame() <= index as u64);
+        let Some(entry) = self.index.get(chunk).copied() else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("frame {index} out of range (0..{})", self.len()),
+            ));
+        };
+        let frames = self.chunk(chunk)?;
+        frames
+            .get(index - entry.first_frame as usize)
+            .cloned()
+            .ok_or_else(|| invalid(format!("chunk {chunk} is shorter than its index says")))
+    }
+
+    /// `(t, value)` of the named probe over the whole run, or `None` if it was not recorded.
+    pub fn probe_series(&mut self, name: &str) -> io::Result<Option<Vec<(f64, f64)>>> {
+        let Some(p) = self.meta.probe_index(name) else {
+            return Ok(None);
+        };
+        let mut series = Vec::with_capacity(self.len());
+        for chunk in 0..self.index.len() {
+            series.extend(self.chunk(chunk)?.iter().map(|f| (f.t, f.probes[p])));
+        }
+        Ok(Some(series))
+    }
+
+    fn chunk(&mut self, chunk: usize) -> io::Result<&[Frame]> {
+        if self.cached.as_ref().is_none_or(|(c, _)| *c != chunk) {
+            let entry = self.index[chunk];
+            self.inner.seek(SeekFrom::Start(entry.offset))?;
+            let (codec, frames, payload) = read_chunk(&mut self.inner)?;
+            let raw = match codec {
+                CODEC_RAW => payload,
+                CODEC_DEFLATE => {
+                    let mut raw = Vec::new();
+                    DeflateDecoder::new(payload.as_slice()).read_to_end(&mut raw)?;
+                    raw
+                }
+                _ => return Err(invalid(format!("unknown chunk codec {codec}"))),
+            };
+            let decoded: Vec<Frame> = bincode::deserialize(&raw).map_err(invalid)?;
+            if decoded.len() != frames as usize {
+                return Err(invalid(format!(
+                    "chunk {chunk} holds {} frames, not {frames}",
+                    decoded.len()
+                )));
+            }
+            self.cached = Some((chunk, decoded));
+        }
+        Ok(&self.cached.as_ref().unwrap().1)
+    }
+}
+
+/// Reads the chunk index through the trailer, or `None` if the file has no valid trailer.
+fn read_index(
+    inner: &mut (impl Read + Seek),
+    data_start: u64,
+) -> io::Result<Option<Vec<ChunkEntry>>> {
+    let end = inner.seek(SeekFrom::End(0))?;
+    if end < data_start + TRAILER_LEN {
+        return Ok(None);
+    }
+    inner.seek(SeekFrom::Start(end - TRAILER_LEN))?;
+    let index_offset = read_u64(inner)?;
+    let mut magic = [0; 8];
+    inner.read_exact(&mut magic)?;
+    if &magic != END_MAGIC || index_offset < data_start || index_offset > end - TRAILER_LEN {
+        return Ok(None);
+    }
+    inner.seek(SeekFrom::Start(index_offset))?;
+    let bytes = read_bytes(inner, end - TRAILER_LEN - index_offset)?;
+    Ok(bincode::deserialize(&bytes).ok())
+}
+
+/// Rebuilds the index of an unfinished file from its chunk headers, dropping a
+/// trailing chunk that was cut short.
+fn scan_chunks(inner: &mut (impl Read + Seek), data_start: u64) -> io::Result<Vec<ChunkEntry>> {
+    let end = inner.seek(SeekFrom::End(0))?;
+    let mut index = Vec::new();
+    let (mut offset, mut first_frame) = (data_start, 0);
+    while offset + CHUNK_HEADER_LEN <= end {
+        inner.seek(SeekFrom::Start(offset))?;
+        let mut codec = [0];
+        inner.read_exact(&mut codec)?;
+        let frames = read_u32(inner)?;
+        let len = read_u64(inner)?;
+        let next = offset + CHUNK_HEADER_LEN + len;
+        if codec[0] > CODEC_DEFLATE || next > end {
+            break;
+        }
+        index.push(ChunkEntry {
+            offset,
+            first_frame,
+            frames,
+        });
+        first_frame += u64::from(frames);
+        offset = next;
+    }
+    Ok(index)
+}
+
+fn read_chunk(inner: &mut impl Read) -> io::Result<(u8, u32, Vec<u8>)> {
+    let mut codec = [0];
+    inner.read_exact(&mut codec)?;
+    let frames = read_u32(inner)?;
+    let len = read_u64(inner)?;
+    Ok((codec[0], frames, read_bytes(inner, len)?))
+}
+
+fn read_u32(inner: &mut impl Read) -> io::Result<u32> {
+    let mut bytes = [0; 4];
+    inner.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64(inner: &mut impl Read) -> io::Result<u64> {
+    let mut bytes = [0; 8];
+    inner.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+/// Reads exactly `len` bytes without trusting `len` for the allocation.
+fn read_bytes(inner: &mut impl Read, len: u64) -> io::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    inner.take(len).read_to_end(&mut bytes)?;
+    if bytes.len() as u64 != len {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    Ok(bytes)
+}
+
+fn invalid(error: impl ToString) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error.to_string())
+}
@@ -11,8 +11,9 @@ pub struct Simulation {
 impl Simulation {
     pub async fn new(device: &wgpu::Device, n_particles: u32) -> Self {
         // --- Physics Setup ---
-        let dof = n_particles as usize * 3;
-        let mut state = PhaseSpace::new(dof); // Fluid particles
+        // The GPU solver and the viewer are planar, so only (x, y) is stored.
+        let dof = n_particles as usize * 2;
+        let mut state = PhaseSpace::planar(dof); // Fluid particles
 
         // Initialize Fluid Block
         let spacing = 15.0; // Safer density (h=25.0)
@@ -24,13 +25,10 @@ impl Simulation {
         for i in 0..n_particles as usize {
             let col = i % cols;
             let row = i / cols;
-            state.q[i * 3] = (col as f64) * spacing - (cols as f64 * spacing / 2.0);
-            state.q[i * 3 + 1] = start_y + (row as f64) * spacing;
-            state.q[i * 3 + 2] = 0.0;
+            state.q[i * 2] = (col as f64) * spacing - (cols as f64 * spacing / 2.0);
+            state.q[i * 2 + 1] = start_y + (row as f64) * spacing;
 
-            state.mass[i * 3] = 1.0;
-            state.mass[i * 3 + 1] = 1.0;
-            state.mass[i * 3 + 2] = 1.0;
+            state.set_particle_mass(i, 1.0);
 
             state.radius[i] = spacing / 2.0;
         }
@@ -55,19 +53,16 @@ impl Simulation {
         for i in 0..self.n_particles as usize {
             let col = i % cols;
             let row = i / cols;
-            self.state.q[i * 3] = (col as f64) * spacing - (cols as f64 * spacing / 2.0);
-            self.state.q[i * 3 + 1] = start_y + (row as f64) * spacing;
-            self.state.q[i * 3 + 2] = 0.0;
+            self.state.q[i * 2] = (col as f64) * spacing - (cols as f64 * spacing / 2.0);
+            self.state.q[i * 2 + 1] = start_y + (row as f64) * spacing;
 
             // Velocity Reset
-            self.state.v[i * 3] = 0.0;
-            self.state.v[i * 3 + 1] = 0.0;
-            self.state.v[i * 3 + 2] = 0.0;
+            self.state.v[i * 2] = 0.0;
+            self.state.v[i * 2 + 1] = 0.0;
         }
 
         // Upload reset state
-        self.compute
-            .write_state(queue, &self.state.q, &self.state.v, &self.state.mass);
+        self.compute.write_state(queue, &self.state);
     }
 
     pub fn step(&mut self, device: &wgpu::Device, queue: &wgpu::Queue) {
@@ -0,0 +1,76 @@
+use moo::core::state::PhaseSpace;
+use moo::laws::classical::{Gravity, Spring};
+use moo::laws::continuum::SPH;
+use moo::laws::registry::{Law, LawRegistry};
+
+fn scattered_state(n: usize) -> PhaseSpace {
+    let mut state = PhaseSpace::new(n * 3);
+    for i in 0..n {
+        let t = i as f64;
+        state.q[i * 3] = (1.9 * t).sin() * (1.0 + 0.4 * t);
+        state.q[i * 3 + 1] = (0.6 * t).cos() * (1.0 + 0.3 * t);
+        state.q[i * 3 + 2] = 0.2 * t - 0.5;
+        state.set_particle_mass(i, 0.5 + t);
+    }
+    state
+}
+
+fn assert_close(name: &str, analytic: &[f64], ad: &[f64]) {
+    for (i, (a, b)) in analytic.iter().zip(ad).enumerate() {
+        assert!(
+            (a - b).abs() < 1e-10 * (1.0 + b.abs()),
+            "{name}, DOF {i}: analytic {a} vs AD {b}"
+        );
+    }
+}
+
+#[test]
+fn test_analytic_gradients_match_ad() {
+    let state = scattered_state(8);
+
+    let laws: [(&str, LawRegistry); 2] = [
+        ("gravity", {
+            let mut r = LawRegistry::new();
+            r.add(Gravity::with_softening(1.3, 0.05));
+            r
+        }),
+        ("spring", {
+            let mut r = LawRegistry::new();
+            r.add(Spring::new(12.0, 0.9, 2, 6));
+            r
+        }),
+    ];
+
+    for (name, registry) in &laws {
+        let mut analytic = vec![0.0; state.dof];
+        let mut ad = vec![0.0; state.dof];
+        registry.gradient(&state.q, &state.mass, &mut analytic);
+        registry.gradient_reverse(&state.q, &state.mass, &mut ad);
+        assert_close(name, &analytic, &ad);
+    }
+}
+
+#[test]
+fn test_mixed_analytic_and_ad_laws_accumulate() {
+    let state = scattered_state(6);
+
+    let mut registry = LawRegistry::new();
+    registry.add(Gravity::new(1.0)); // analytic
+    registry.add(Spring::new(5.0, 1.0, 0, 5)); // analytic
+    registry.add(SPH::new(3.0, 0.5, 2.0)); // AD only
+
+    let mut mixed = vec![0.0; state.dof];
+    let mut ad = vec![0.0; state.dof];
+    registry.gradient(&state.q, &state.mass, &mut mixed);
+    registry.gradient_forward(&state.q, &state.mass, &mut ad);
+    assert_close("mixed", &mixed, &ad);
+}
+
+#[test]
+fn test_laws_report_analytic_support() {
+    let state = scattered_state(2);
+    let mut out = vec![0.0; state.dof];
+    assert!(Gravity::new(1.0).gradient(&state.q, &state.mass, &mut out));
+    assert!(Spring::new(1.0, 1.0, 0, 1).gradient(&state.q, &state.mass, &mut out));
+    assert!(!SPH::new(1.0, 1.0, 1.0).gradient(&state.q, &state.mass, &mut out));
+}
@@ -0,0 +1,163 @@
+use glam::{DVec3, Vec4};
+use moo::core::solve::{Integrator, SymplecticEuler};
+use moo::core::state::attributes::names;
+use moo::core::state::{ParticleRegistry, PhaseSpace};
+use moo::investigation::probe::{AttributeProbe, Probe, Reduction};
+use moo::laws::classical::Coulomb;
+use moo::laws::registry::LawRegistry;
+
+#[test]
+fn test_typed_channels() {
+    let mut state = PhaseSpace::new(9);
+    state.add_attribute(names::TEMPERATURE, 300.0);
+    state.add_attribute(names::MATERIAL, 0u32);
+    state.add_attribute(names::COLOR, Vec4::ONE);
+
+    state.attribute_mut::<f64>(names::TEMPERATURE).unwrap()[1] = 450.0;
+    state.attribute_mut::<u32>(names::MATERIAL).unwrap()[2] = 7;
+
+    assert_eq!(
+        state.attribute::<f64>(names::TEMPERATURE).unwrap(),
+        &[300.0, 450.0, 300.0]
+    );
+    assert_eq!(state.attribute::<u32>(names::MATERIAL).unwrap(), &[0, 0, 7]);
+    // The wrong element type and unknown names both miss.
+    assert!(state.attribute::<u32>(names::TEMPERATURE).is_none());
+    assert!(state.attribute::<f64>(names::AGE).is_none());
+    assert_eq!(
+        state.attributes.names().collect::<Vec<_>>(),
+        [names::COLOR, names::MATERIAL, names::TEMPERATURE]
+    );
+}
+
+#[test]
+fn test_channels_follow_particles() {
+    let mut state = PhaseSpace::new(12);
+    state.add_attribute(names::AGE, 0.0);
+    state.add_attribute(names::TAG, 1u32);
+    for (i, age) in state
+        .attribute_mut::<f64>(names::AGE)
+        .unwrap()
+        .iter_mut()
+        .enumerate()
+    {
+        *age = i as f64;
+    }
+
+    // Emitted particles get the channel defaults.
+    let mut registry = ParticleRegistry::new(&state);
+    let fresh = registry.insert(&mut state, DVec3::ZERO, DVec3::ZERO);
+    assert_eq!(state.attributes.len(), 5);
+    assert_eq!(state.attribute::<f64>(names::AGE).unwrap()[4], 0.0);
+    assert_eq!(state.attribute::<u32>(names::TAG).unwrap()[4], 1);
+
+    // Removal compacts every channel in particle order.
+    let doomed = [registry.handle(0).unwrap(), registry.handle(2).unwrap()];
+    registry.remove_many(&mut state, &doomed);
+    assert_eq!(
+        state.attribute::<f64>(names::AGE).unwrap(),
+        &[1.0, 3.0, 0.0]
+    );
+    assert_eq!(registry.index(fresh), Some(2));
+
+    state.resize(3);
+    assert_eq!(state.attribute::<u32>(names::TAG).unwrap(), &[1]);
+}
+
+#[test]
+fn test_gpu_layout() {
+    let mut state = PhaseSpace::new(6);
+    state.add_attribute(names::CHARGE, -1.5);
+    state.add_attribute("wind", DVec3::new(1.0, 2.0, 3.0));
+
+    let charge = state
+        .attributes
+        .channel(names::CHARGE)
+        .unwrap()
+        .to_gpu_bytes();
+    let charge: &[f32] = bytemuck::cast_slice(&charge);
+    assert_eq!(charge, &[-1.5, -1.5]);
+
+    let wind = state.attributes.channel("wind").unwrap().to_gpu_bytes();
+    assert_eq!(wind.len(), 2 * 16);
+    let wind: &[f32] = bytemuck::cast_slice(&wind);
+    assert_eq!(&wind[4..], &[1.0, 2.0, 3.0, 0.0]);
+}
+
+#[test]
+fn test_coulomb_reads_charges() {
+    let mut state = PhaseSpace::new(9);
+    state.set_position(1, DVec3::X * 2.0);
+    state.set_position(2, DVec3::Y * 100.0);
+    state.add_attribute(names::CHARGE, 0.0);
+    state.attribute_mut::<f64>(names::CHARGE).unwrap()[..2].copy_from_slice(&[1.0, -2.0]);
+
+    let mut laws = LawRegistry::new();
+    laws.add(Coulomb::new(3.0).with_charges_from(&state));
+
+    // Opposite charges attract; the neutral third particle feels nothing.
+    let d = (4.0f64 + 1e-6).sqrt();
+    let energy = laws.potential(&state.q, &state.mass);
+    assert!((energy - 3.0 * -2.0 / d).abs() < 1e-12);
+
+    let mut grad = vec![0.0; state.dof];
+    laws.gradient(&state.q, &state.mass, &mut grad);
+    let pull = 3.0 * 2.0 * 2.0 / (d * d * d);
+    assert!((grad[0] + pull).abs() < 1e-12);
+    assert!((grad[3] - pull).abs() < 1e-12);
+    assert!(grad[6..].iter().all(|&g| g == 0.0));
+
+    // Evaluating the registry directly uses the charges of the last sync.
+    state.attribute_mut::<f64>(names::CHARGE).unwrap()[0] = 0.0;
+    assert!(laws.potential(&state.q, &state.mass) < 0.0);
+    laws.sync(&state);
+    assert_eq!(laws.potential(&state.q, &state.mass), 0.0);
+}
+
+#[test]
+fn test_attribute_probe() {
+    let mut state = PhaseSpace::new(9);
+    state.add_attribute(names::TEMPERATURE, 300.0);
+    state.attribute_mut::<f64>(names::TEMPERATURE).unwrap()[0] = 270.0;
+    let laws = LawRegistry::new();
+
+    let measure = |r| AttributeProbe::new(names::TEMPERATURE, r).measure(&state, &laws);
+    assert_eq!(measure(Reduction::Sum), 870.0);
+    assert_eq!(measure(Reduction::Mean), 290.0);
+    assert_eq!(measure(Reduction::Min), 270.0);
+    assert_eq!(measure(Reduction::Max), 300.0);
+    assert_eq!(
+        AttributeProbe::new(names::AGE, Reduction::Sum).measure(&state, &laws),
+        0.0
+    );
+    assert_eq!(
+        AttributeProbe::new(names::TEMPERATURE, Reduction::Sum).name(),
+        names::TEMPERATURE
+    );
+}
+
+#[test]
+fn test_integrators_see_charge_edits() {
+    let mut state = PhaseSpace::new(6);
+    state.set_position(1, DVec3::X);
+    state.add_attribute(names::CHARGE, 1.0);
+    let mut laws = LawRegistry::new();
+    laws.add(Coulomb::new(1.0).with_charges_from(&state));
+    let kick = |state: &mut PhaseSpace| {
+        let before = state.velocity(1).x;
+        SymplecticEuler.step(state, &laws, &[], 1e-3);
+        state.velocity(1).x - before
+    };
+
+    // Like charges repel; flipping one through the channel makes them attract.
+    assert!(kick(&mut state) > 0.0);
+    state.attribute_mut::<f64>(names::CHARGE).unwrap()[0] = -1.0;
+    assert!(kick(&mut state) < 0.0);
+
+    // A particle inserted later carries the channel's default charge.
+    state.resize(9);
+    state.set_position(2, DVec3::new(1.0, 1.0, 0.0));
+    let before = state.velocity(2).y;
+    SymplecticEuler.step(&mut state, &laws, &[], 1e-3);
+    assert!(state.velocity(2).y > before);
+}
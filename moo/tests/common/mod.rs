@@ -0,0 +1,82 @@
+// Each test crate compiles its own copy and uses only some of the fixtures.
+#![allow(dead_code)]
+
+use glam::DVec3;
+use moo::core::math::Real;
+use moo::core::solve::Integrator;
+use moo::core::state::PhaseSpace;
+use moo::investigation::probe::{EnergyProbe, Probe};
+use moo::laws::registry::{Law, LawRegistry};
+use std::f64::consts::PI;
+
+/// V = k |q|² / 2 on every coordinate.
+pub struct Harmonic(pub f64);
+
+impl Law for Harmonic {
+    fn potential<T: Real>(&self, q: &[T], _mass: &[f64]) -> T {
+        let mut v = T::constant(0.0);
+        for &x in q {
+            v = v + x * x * (0.5 * self.0);
+        }
+        v
+    }
+}
+
+/// V = -m / |q|: a fixed unit-mass sun at the origin.
+pub struct Sun;
+
+impl Law for Sun {
+    fn potential<T: Real>(&self, q: &[T], mass: &[f64]) -> T {
+        let r2 = q[0] * q[0] + q[1] * q[1] + q[2] * q[2];
+        T::constant(-mass[0]) / r2.sqrt()
+    }
+}
+
+/// V = m g y for every particle of a 3D state.
+pub struct UniformGravity(pub f64);
+
+impl Law for UniformGravity {
+    fn potential<T: Real>(&self, q: &[T], mass: &[f64]) -> T {
+        let mut v = T::constant(0.0);
+        for i in (0..q.len()).step_by(3) {
+            v = v + q[i + 1] * (mass[i + 1] * self.0);
+        }
+        v
+    }
+}
+
+/// A planet on an eccentric orbit (semi-major axis 1, period 2π) around [`Sun`],
+/// starting at aphelion.
+pub fn kepler(eccentricity: f64) -> (PhaseSpace, LawRegistry) {
+    let mut state = PhaseSpace::new(3);
+    let r = 1.0 + eccentricity;
+    state.set_position(0, DVec3::new(r, 0.0, 0.0));
+    let speed = ((1.0 - eccentricity) / r).sqrt();
+    state.set_velocity(0, DVec3::new(0.0, speed, 0.0));
+
+    let mut laws = LawRegistry::new();
+    laws.add(Sun);
+    (state, laws)
+}
+
+pub fn energy(state: &PhaseSpace, laws: &LawRegistry) -> f64 {
+    EnergyProbe.measure(state, laws)
+}
+
+/// Worst relative energy error over each of `orbits` orbits of `steps` steps, on
+/// `kepler(0.7)`.
+pub fn energy_error(integrator: &mut dyn Integrator, orbits: usize, steps: usize) -> Vec<f64> {
+    let (mut state, laws) = kepler(0.7);
+    let e0 = energy(&state, &laws);
+    let dt = 2.0 * PI / steps as f64;
+    (0..orbits)
+        .map(|_| {
+            let mut worst = 0.0f64;
+            for _ in 0..steps {
+                integrator.step(&mut state, &laws, &[], dt);
+                worst = worst.max((energy(&state, &laws) - e0).abs() / e0.abs());
+            }
+            worst
+        })
+        .collect()
+}
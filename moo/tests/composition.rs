@@ -0,0 +1,89 @@
+mod common;
+
+use common::{energy_error, kepler};
+use moo::core::solve::{Composition, Integrator, VelocityVerlet};
+use std::f64::consts::PI;
+
+/// Distance from the starting point after one full orbit of `steps` steps.
+fn orbit_error(scheme: &Composition, steps: usize) -> f64 {
+    let (mut state, laws) = kepler(0.5);
+    let start = state.position(0);
+    let dt = 2.0 * PI / steps as f64;
+    let mut integrator = scheme.clone();
+    for _ in 0..steps {
+        integrator.step(&mut state, &laws, &[], dt);
+    }
+    (state.position(0) - start).length()
+}
+
+#[test]
+fn test_published_schemes_reach_their_order() {
+    // Step counts inside the asymptotic range of each scheme, above round-off.
+    let schemes = [
+        (Composition::verlet(), 200),
+        (Composition::yoshida4(), 200),
+        (Composition::forest_ruth(), 200),
+        (Composition::blanes_moan4(), 200),
+        (Composition::yoshida6(), 200),
+        (Composition::blanes_moan6(), 50),
+        (Composition::yoshida8(), 100),
+    ];
+    for (scheme, steps) in schemes {
+        let observed = (orbit_error(&scheme, steps) / orbit_error(&scheme, 2 * steps)).log2();
+        let order = scheme.order() as f64;
+        assert!(
+            (observed - order).abs() < 0.3,
+            "{scheme:?}: observed order {observed}"
+        );
+    }
+}
+
+#[test]
+fn test_verlet_composition_matches_velocity_verlet() {
+    let (mut a, laws) = kepler(0.5);
+    let mut b = a.clone();
+    let mut scheme = Composition::verlet();
+    assert_eq!(scheme.force_evaluations(), 2);
+    for _ in 0..100 {
+        scheme.step(&mut a, &laws, &[], 0.05);
+        VelocityVerlet.step(&mut b, &laws, &[], 0.05);
+    }
+    assert!((a.t - b.t).abs() < 1e-12);
+    for (x, y) in a.q.iter().chain(&a.v).zip(b.q.iter().chain(&b.v)) {
+        assert!((x - y).abs() < 1e-12, "{x} vs {y}");
+    }
+}
+
+#[test]
+fn test_higher_order_wins_at_equal_cost() {
+    // The same number of force evaluations per orbit for every scheme.
+    const BUDGET: usize = 2520;
+    let worst = |scheme: Composition| {
+        let steps = BUDGET / scheme.force_evaluations();
+        let mut integrator = scheme;
+        energy_error(&mut integrator, 5, steps)
+            .into_iter()
+            .fold(0.0, f64::max)
+    };
+    let verlet = worst(Composition::verlet());
+    let yoshida4 = worst(Composition::yoshida4());
+    let blanes_moan4 = worst(Composition::blanes_moan4());
+    let blanes_moan6 = worst(Composition::blanes_moan6());
+    assert!(yoshida4 < verlet, "{yoshida4} vs {verlet}");
+    assert!(
+        blanes_moan4 < yoshida4 / 10.0,
+        "{blanes_moan4} vs {yoshida4}"
+    );
+    assert!(blanes_moan6 < verlet / 1000.0, "{blanes_moan6} vs {verlet}");
+}
+
+#[test]
+fn test_energy_error_stays_bounded() {
+    for mut scheme in [Composition::forest_ruth(), Composition::yoshida6()] {
+        let errors = energy_error(&mut scheme, 200, 100);
+        assert!(
+            errors.iter().all(|&e| e < 1.1 * errors[0]),
+            "{scheme:?}: {errors:?}"
+        );
+    }
+}
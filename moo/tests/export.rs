@@ -0,0 +1,127 @@
+use glam::{DQuat, DVec3, Vec4};
+use moo::core::state::PhaseSpace;
+use moo::core::state::attributes::names;
+use moo::platform::storage::{ExportFormat, FrameExporter, write_vtk, write_vtu, write_xyz};
+
+fn sample() -> PhaseSpace {
+    let mut state = PhaseSpace::new(9);
+    for i in 0..3 {
+        let x = i as f64;
+        state.set_position(i, DVec3::new(x, 2.0 * x, -0.5));
+        state.set_velocity(i, DVec3::new(0.0, 1.0, x));
+        state.set_particle_mass(i, 1.0 + x);
+    }
+    state.resize_rigid(1);
+    state.rot[0] = DQuat::from_rotation_z(0.5);
+    state.add_attribute(names::MATERIAL, 2u32);
+    state.add_attribute(names::COLOR, Vec4::new(1.0, 0.0, 0.5, 1.0));
+    state.add_attribute("grain size", 0.25);
+    state.t = 1.5;
+    state
+}
+
+fn render(
+    write: impl Fn(&PhaseSpace, &mut Vec<u8>) -> std::io::Result<()>,
+    state: &PhaseSpace,
+) -> String {
+    let mut bytes = Vec::new();
+    write(state, &mut bytes).unwrap();
+    String::from_utf8(bytes).unwrap()
+}
+
+#[test]
+fn test_vtk_legacy() {
+    let text = render(|s, out| write_vtk(s, out), &sample());
+    assert!(text.starts_with("# vtk DataFile Version 3.0\n"));
+    assert!(text.contains("TIME 1 1 double\n1.5\n"));
+    assert!(text.contains("POINTS 3 double\n0 0 -0.5\n1 2 -0.5\n2 4 -0.5\n"));
+    assert!(text.contains("VERTICES 3 6\n1 0\n1 1\n1 2\n"));
+    assert!(text.contains("VECTORS velocity double\n0 1 0\n"));
+    assert!(text.contains("SCALARS mass double 1\nLOOKUP_TABLE default\n1\n2\n3\n"));
+    // Plain particles get the identity orientation.
+    assert!(text.contains("SCALARS orientation double 4\nLOOKUP_TABLE default\n"));
+    assert!(text.contains("\n0 0 0 1\n0 0 0 1\n"));
+    assert!(text.contains("SCALARS material unsigned_int 1\nLOOKUP_TABLE default\n2\n2\n2\n"));
+    assert!(text.contains("SCALARS grain_size double 1\n"));
+    assert!(text.contains("SCALARS color double 4\nLOOKUP_TABLE default\n1 0 0.5 1\n"));
+}
+
+#[test]
+fn test_vtu() {
+    let mut state = sample();
+    state.attributes.remove("grain size");
+    state.add_attribute("a<b", 1.0);
+    let text = render(|s, out| write_vtu(s, out), &state);
+
+    assert!(text.contains(r#"<Piece NumberOfPoints="3" NumberOfCells="3">"#));
+    assert!(text.contains(r#"Name="TimeValue" NumberOfTuples="1" format="ascii">1.5<"#));
+    assert!(text.contains(r#"Name="orientation" NumberOfComponents="4""#));
+    assert!(text.contains(r#"type="UInt32" Name="material""#));
+    assert!(text.contains(r#"Name="a&lt;b""#));
+    assert_eq!(
+        text.matches("<DataArray").count(),
+        text.matches("</DataArray>").count()
+    );
+
+    // Points read back.
+    let points = text
+        .split(r#"Name="position" NumberOfComponents="3" format="ascii">"#)
+        .nth(1)
+        .unwrap();
+    let coords: Vec<f64> = points
+        .split("</DataArray>")
+        .next()
+        .unwrap()
+        .split_whitespace()
+        .map(|x| x.parse().unwrap())
+        .collect();
+    assert_eq!(coords.len(), 9);
+    for i in 0..3 {
+        assert_eq!(DVec3::from_slice(&coords[3 * i..]), state.position(i));
+    }
+    assert!(text.contains("<DataArray type=\"UInt8\" Name=\"types\" format=\"ascii\">\n1\n1\n1\n"));
+}
+
+#[test]
+fn test_extended_xyz() {
+    let mut state = PhaseSpace::planar(4);
+    state.set_position(1, DVec3::new(3.0, 4.0, 0.0));
+    state.add_attribute(names::CHARGE, -1.0);
+    state.t = 0.25;
+    let text = render(|s, out| write_xyz(s, out), &state);
+    let lines: Vec<&str> = text.lines().collect();
+
+    assert_eq!(lines.len(), 4);
+    assert_eq!(lines[0], "2");
+    assert_eq!(
+        lines[1],
+        r#"Properties=species:S:1:pos:R:3:velo:R:3:mass:R:1:radius:R:1:charge:R:1 Time=0.25 pbc="F F F""#
+    );
+    assert_eq!(lines[3], "X 3 4 0 0 0 0 1 1 -1");
+}
+
+#[test]
+fn test_frame_series() {
+    let dir = std::env::temp_dir().join(format!("moo-export-{}", std::process::id()));
+    let mut state = sample();
+
+    let mut vtu = FrameExporter::new(dir.join("vtu"), "run", ExportFormat::Vtu).unwrap();
+    let mut xyz = FrameExporter::new(dir.join("xyz"), "run", ExportFormat::ExtendedXyz).unwrap();
+    for step in 0..3 {
+        state.t = step as f64 * 0.5;
+        let path = vtu.write(&state).unwrap();
+        assert_eq!(path, dir.join("vtu").join(format!("run_{step:05}.vtu")));
+        assert!(xyz.write(&state).unwrap().exists());
+    }
+    assert_eq!(vtu.len(), 3);
+
+    let pvd = std::fs::read_to_string(vtu.pvd_path()).unwrap();
+    assert!(pvd.contains(r#"<VTKFile type="Collection""#));
+    assert!(pvd.contains(r#"<DataSet timestep="0" part="0" file="run_00000.vtu"/>"#));
+    assert!(pvd.contains(r#"<DataSet timestep="1" part="0" file="run_00002.vtu"/>"#));
+    assert!(!xyz.pvd_path().exists());
+
+    let frame = std::fs::read_to_string(dir.join("xyz").join("run_00001.xyz")).unwrap();
+    assert!(frame.contains("Time=0.5"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
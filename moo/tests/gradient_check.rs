@@ -0,0 +1,95 @@
+use moo::core::math::real::Real;
+use moo::core::state::PhaseSpace;
+use moo::core::state::attributes::names;
+use moo::investigation::verify::GradientCheck;
+use moo::laws::classical::{Coulomb, Gravity, Spring};
+use moo::laws::continuum::SPH;
+use moo::laws::registry::{GradientMode, Law, LawRegistry};
+
+fn cluster(n: usize, spread: f64) -> PhaseSpace {
+    let mut state = PhaseSpace::new(n * 3);
+    for i in 0..n {
+        let t = i as f64;
+        state.q[i * 3] = spread * (2.3 * t).sin();
+        state.q[i * 3 + 1] = spread * (1.7 * t).cos();
+        state.q[i * 3 + 2] = spread * (0.9 * t).sin() * 0.5;
+        state.set_particle_mass(i, 1.0 + 0.25 * t);
+    }
+    state
+}
+
+#[test]
+fn test_builtin_laws_pass_gradient_check() {
+    let check = GradientCheck::default();
+
+    let state = cluster(6, 2.0);
+    let report = check.check_law(&Gravity::new(1.5), &state);
+    assert!(report.passed(), "{report}");
+
+    let report = check.check_law(&Spring::new(8.0, 0.5, 0, 4), &state);
+    assert!(report.passed(), "{report}");
+
+    let state = cluster(6, 0.6);
+    let report = check.check_law(&SPH::new(1.0, 1.0, 20.0), &state);
+    assert!(report.passed(), "{report}");
+}
+
+#[test]
+fn test_registry_passes_in_both_modes() {
+    let state = cluster(5, 1.5);
+    let mut registry = LawRegistry::new();
+    registry.add(Gravity::new(1.0));
+    registry.add(Spring::new(3.0, 1.0, 1, 2));
+
+    for mode in [GradientMode::Forward, GradientMode::Reverse] {
+        registry.set_gradient_mode(mode);
+        let report = GradientCheck::default().check_registry(&registry, &state);
+        assert!(report.passed(), "{mode:?}: {report}");
+        assert_eq!(report.entries.len(), state.dof);
+    }
+}
+
+#[test]
+fn test_checks_see_attribute_charges() {
+    let mut state = cluster(4, 1.5);
+    state.add_attribute(names::CHARGE, 0.0);
+    state
+        .attribute_mut::<f64>(names::CHARGE)
+        .unwrap()
+        .copy_from_slice(&[1.0, -2.0, 0.5, 1.0]);
+    // No `with_charges_from`: the check itself must read the channel.
+    let mut registry = LawRegistry::new();
+    registry.add(Coulomb::new(1.0));
+
+    let check = GradientCheck::default();
+    for report in [
+        check.check_registry(&registry, &state),
+        check.check_law(&Coulomb::new(1.0), &state),
+    ] {
+        assert!(report.passed(), "{report}");
+        assert!(report.entries.iter().any(|e| e.fd.abs() > 0.1), "{report}");
+    }
+}
+
+/// A potential with a jump at x = 0: AD sees a flat function, FD sees the step.
+struct Step;
+
+impl Law for Step {
+    fn potential<T: Real>(&self, q: &[T], _mass: &[f64]) -> T {
+        let jump = if q[0].value() > 0.0 { 1.0 } else { 0.0 };
+        q[1] * q[1] + jump
+    }
+}
+
+#[test]
+fn test_discontinuous_potential_is_reported() {
+    let mut state = PhaseSpace::new(3);
+    state.q[1] = 0.5;
+
+    let report = GradientCheck::default().check_law(&Step, &state);
+    assert!(!report.passed());
+
+    let failures: Vec<usize> = report.failures().map(|e| e.dof).collect();
+    assert_eq!(failures, vec![0]);
+    assert!(report.entries[1].passed);
+}
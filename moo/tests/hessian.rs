@@ -0,0 +1,160 @@
+use moo::core::math::ad::Dual;
+use moo::core::math::hyper::HyperDual;
+use moo::core::math::real::Real;
+use moo::core::math::tape::Tape;
+use moo::core::state::PhaseSpace;
+use moo::laws::classical::{Gravity, Spring};
+use moo::laws::registry::LawRegistry;
+
+/// Second derivative of a scalar function via hyper-dual seeding.
+fn second_derivative(f: impl Fn(HyperDual) -> HyperDual, x: f64) -> f64 {
+    f(HyperDual::new(x, 1.0, 1.0, 0.0)).e12
+}
+
+#[test]
+fn test_hyperdual_second_derivatives() {
+    let x = 0.6;
+    let cases: [(&str, f64, f64); 8] = [
+        (
+            "sqrt",
+            second_derivative(|d| d.sqrt(), x),
+            -0.25 * x.powf(-1.5),
+        ),
+        ("powi", second_derivative(|d| d.powi(4), x), 12.0 * x * x),
+        ("exp", second_derivative(|d| d.exp(), x), x.exp()),
+        ("ln", second_derivative(|d| d.ln(), x), -1.0 / (x * x)),
+        ("sin", second_derivative(|d| d.sin(), x), -x.sin()),
+        ("tanh", second_derivative(|d| d.tanh(), x), {
+            let t = x.tanh();
+            -2.0 * t * (1.0 - t * t)
+        }),
+        (
+            "recip",
+            second_derivative(|d| d.recip(), x),
+            2.0 / x.powi(3),
+        ),
+        (
+            "quotient",
+            second_derivative(|d| d.sin() / d, x),
+            -x.sin() / x - 2.0 * x.cos() / (x * x) + 2.0 * x.sin() / x.powi(3),
+        ),
+    ];
+
+    for (name, ad, exact) in cases {
+        assert!(
+            (ad - exact).abs() < 1e-12 * (1.0 + exact.abs()),
+            "{name}: AD={ad} exact={exact}"
+        );
+    }
+}
+
+#[test]
+fn test_hyperdual_atan2_mixed_partial() {
+    // d²/dxdy atan2(y, x) = (y² - x²) / (x² + y²)²
+    let (x, y) = (0.8, -0.5);
+    let f = HyperDual::new(y, 1.0, 0.0, 0.0).atan2(HyperDual::new(x, 0.0, 1.0, 0.0));
+    let exact = (y * y - x * x) / (x * x + y * y).powi(2);
+    assert!((f.e12 - exact).abs() < 1e-12);
+}
+
+fn spring_network() -> (PhaseSpace, LawRegistry) {
+    let mut state = PhaseSpace::new(12);
+    let positions = [
+        [0.0, 0.0, 0.0],
+        [1.2, 0.1, 0.0],
+        [0.3, 0.9, 0.2],
+        [0.5, 0.4, 1.1],
+    ];
+    for (i, p) in positions.iter().enumerate() {
+        state.q[i * 3..i * 3 + 3].copy_from_slice(p);
+    }
+
+    let mut registry = LawRegistry::new();
+    registry.add(Spring::new(10.0, 1.0, 0, 1));
+    registry.add(Spring::new(7.0, 0.8, 1, 2));
+    registry.add(Spring::new(4.0, 1.0, 2, 3));
+    registry.add(Spring::new(6.0, 1.3, 0, 3));
+    registry.add(Gravity::new(0.5));
+    (state, registry)
+}
+
+#[test]
+fn test_registry_hessian_matches_gradient_differences() {
+    let (state, registry) = spring_network();
+    let n = state.dof;
+    let hessian = registry.hessian(&state.q, &state.mass);
+
+    let h = 1e-6;
+    let mut grad_plus = vec![0.0; n];
+    let mut grad_minus = vec![0.0; n];
+    for j in 0..n {
+        let mut q = state.q.clone();
+        q[j] += h;
+        registry.gradient(&q, &state.mass, &mut grad_plus);
+        q[j] -= 2.0 * h;
+        registry.gradient(&q, &state.mass, &mut grad_minus);
+
+        for i in 0..n {
+            let fd = (grad_plus[i] - grad_minus[i]) / (2.0 * h);
+            let ad = hessian[i * n + j];
+            assert!(
+                (ad - fd).abs() < 1e-5 * (1.0 + fd.abs()),
+                "H[{i}][{j}]: AD={ad} FD={fd}"
+            );
+            assert_eq!(hessian[i * n + j], hessian[j * n + i]);
+        }
+    }
+}
+
+#[test]
+fn test_hessian_vector_product_matches_dense() {
+    let (state, registry) = spring_network();
+    let n = state.dof;
+    let hessian = registry.hessian(&state.q, &state.mass);
+    let v: Vec<f64> = (0..n).map(|i| (i as f64 * 0.37).sin()).collect();
+
+    let mut hv = vec![0.0; n];
+    registry.hessian_vector_product(&state.q, &state.mass, &v, &mut hv);
+
+    for i in 0..n {
+        let dense: f64 = (0..n).map(|j| hessian[i * n + j] * v[j]).sum();
+        assert!((hv[i] - dense).abs() < 1e-10 * (1.0 + dense.abs()));
+    }
+}
+
+/// Exercises every elementary function the tape records.
+fn composite<T: Real>(x: T, y: T) -> T {
+    (x.sin() / y + x.sqrt() * y.exp()).powi(2) - (x * y).tanh().ln() + y.atan2(x).cos()
+        - x.powf(1.5).recip()
+        + x.max(y) * x.min(y).tan()
+}
+
+#[test]
+fn test_forward_over_reverse_matches_hyperdual() {
+    let (x, y) = (0.7, 0.4);
+    let direction = [0.3, -1.2];
+
+    // H v from one taped evaluation over dual numbers.
+    let tape = Tape::new();
+    let a = tape.var(Dual::new(x, direction[0]));
+    let b = tape.var(Dual::new(y, direction[1]));
+    let adjoints = composite(a, b).backward();
+    let hv = [adjoints.wrt(a).der, adjoints.wrt(b).der];
+
+    let h = |i: usize, j: usize| {
+        let unit = |k: usize, axis: usize| if k == axis { 1.0 } else { 0.0 };
+        let seed = |k: usize| HyperDual::new([x, y][k], unit(k, i), unit(k, j), 0.0);
+        composite(seed(0), seed(1)).e12
+    };
+    for (i, hv) in hv.iter().enumerate() {
+        let dense = h(i, 0) * direction[0] + h(i, 1) * direction[1];
+        assert!(
+            (hv - dense).abs() < 1e-12 * (1.0 + dense.abs()),
+            "{hv} vs {dense}"
+        );
+    }
+    // The primal gradient rides along in the value part.
+    assert!(
+        (adjoints.wrt(a).val - composite(Dual::new(x, 1.0), Dual::constant(y)).der).abs() < 1e-12
+    );
+}
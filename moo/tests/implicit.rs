@@ -0,0 +1,160 @@
+mod common;
+
+use common::{Harmonic, energy};
+use glam::DVec3;
+use moo::core::math::Real;
+use moo::core::solve::{ImplicitEuler, ImplicitMidpoint, Integrator, VelocityVerlet};
+use moo::core::state::PhaseSpace;
+use moo::laws::classical::Spring;
+use moo::laws::registry::{Law, LawRegistry};
+
+/// Three unit masses on the x axis joined by two very stiff springs, the outer
+/// ones pulled out by 0.1.
+fn stiff_chain() -> (PhaseSpace, LawRegistry) {
+    let mut state = PhaseSpace::new(9);
+    state.set_position(0, DVec3::new(-1.1, 0.0, 0.0));
+    state.set_position(2, DVec3::new(1.1, 0.0, 0.0));
+    state.set_velocity(1, DVec3::new(0.5, 0.0, 0.0));
+    let mut laws = LawRegistry::new();
+    laws.add(Spring::new(1e6, 1.0, 0, 1));
+    laws.add(Spring::new(1e6, 1.0, 1, 2));
+    (state, laws)
+}
+
+fn momentum(state: &PhaseSpace) -> DVec3 {
+    (0..3).map(|i| state.velocity(i)).sum()
+}
+
+fn energies(integrator: &mut dyn Integrator, steps: usize) -> (Vec<f64>, PhaseSpace) {
+    let (mut state, laws) = stiff_chain();
+    let mut energies = vec![energy(&state, &laws)];
+    for _ in 0..steps {
+        integrator.step(&mut state, &laws, &[], 0.01);
+        energies.push(energy(&state, &laws));
+    }
+    (energies, state)
+}
+
+#[test]
+fn test_stiff_springs_stay_stable() {
+    // ω ≈ 1700 rad/s: a step of 0.01 s is far beyond Verlet's limit of 2 / ω.
+    let (verlet, _) = energies(&mut VelocityVerlet, 50);
+    assert!(!verlet[50].is_finite() || verlet[50] > 1e6 * verlet[0]);
+
+    let mut euler = ImplicitEuler::default();
+    let (damped, state) = energies(&mut euler, 200);
+    assert!(euler.solver.failures.is_empty());
+    assert!(damped.windows(2).all(|w| w[1] <= w[0] * (1.0 + 1e-9)));
+    assert!(damped[200] < 0.01 * damped[0], "{}", damped[200]);
+    assert!((momentum(&state) - DVec3::new(0.5, 0.0, 0.0)).length() < 1e-8);
+
+    let mut midpoint = ImplicitMidpoint::default();
+    let (conserved, state) = energies(&mut midpoint, 200);
+    assert!(midpoint.solver.failures.is_empty());
+    // Motion along the axis keeps the springs harmonic, and the midpoint rule
+    // conserves quadratic energies.
+    for e in &conserved {
+        assert!((e - conserved[0]).abs() < 1e-6 * conserved[0], "{e}");
+    }
+    assert!((momentum(&state) - DVec3::new(0.5, 0.0, 0.0)).length() < 1e-8);
+    assert!((state.t - 2.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_orders_of_accuracy() {
+    let error = |integrator: &mut dyn Integrator, steps: usize| {
+        let mut state = PhaseSpace::new(1);
+        state.q[0] = 1.0;
+        let mut laws = LawRegistry::new();
+        laws.add(Harmonic(1.0));
+        let dt = 2.0 / steps as f64;
+        for _ in 0..steps {
+            integrator.step(&mut state, &laws, &[], dt);
+        }
+        (state.q[0] - 2f64.cos()).abs()
+    };
+    let euler =
+        error(&mut ImplicitEuler::default(), 200) / error(&mut ImplicitEuler::default(), 400);
+    let midpoint =
+        error(&mut ImplicitMidpoint::default(), 200) / error(&mut ImplicitMidpoint::default(), 400);
+    assert!((1.8..2.2).contains(&euler), "implicit Euler ratio {euler}");
+    assert!(
+        (3.6..4.4).contains(&midpoint),
+        "implicit midpoint ratio {midpoint}"
+    );
+}
+
+#[test]
+fn test_nonlinear_newton_converges() {
+    // A stretched, spinning triangle of springs: strongly nonlinear, so Newton
+    // needs a few iterations per step.
+    let mut state = PhaseSpace::new(9);
+    state.set_position(1, DVec3::new(1.5, 0.0, 0.0));
+    state.set_position(2, DVec3::new(0.3, 1.4, 0.0));
+    state.set_velocity(1, DVec3::new(0.0, 20.0, 0.0));
+    state.set_velocity(2, DVec3::new(-20.0, 0.0, 5.0));
+    let mut laws = LawRegistry::new();
+    laws.add(Spring::new(1e4, 1.0, 0, 1));
+    laws.add(Spring::new(1e4, 1.0, 1, 2));
+    laws.add(Spring::new(1e4, 1.0, 2, 0));
+
+    let mut integrator = ImplicitMidpoint::default();
+    let e0 = energy(&state, &laws);
+    let mut most = 0;
+    for _ in 0..500 {
+        integrator.step(&mut state, &laws, &[], 0.002);
+        assert!(integrator.solver.converged);
+        most = most.max(integrator.solver.iterations);
+    }
+    assert!(most > 1, "{most}");
+    let e1 = energy(&state, &laws);
+    assert!((e1 - e0).abs() < 0.01 * e0, "{e0} -> {e1}");
+}
+
+#[test]
+fn test_reports_convergence_failures() {
+    let (mut state, laws) = stiff_chain();
+    let mut integrator = ImplicitEuler::new(1e-12, 1);
+    integrator.solver.max_krylov_iterations = 1;
+    for _ in 0..3 {
+        integrator.step(&mut state, &laws, &[], 0.01);
+    }
+    assert!(!integrator.solver.converged);
+    let failures = &integrator.solver.failures;
+    assert_eq!(failures.len(), 3);
+    assert!((failures[2].t - 0.02).abs() < 1e-12);
+    assert_eq!(failures[0].dt, 0.01);
+    assert_eq!(failures[0].iterations, 1);
+    assert!(
+        failures
+            .iter()
+            .all(|f| f.residual > 1e-12 && f.residual.is_finite())
+    );
+    assert!(state.q.iter().chain(&state.v).all(|x| x.is_finite()));
+}
+
+/// `V = |x|`, with a kink at the origin that the Hessian cannot see.
+struct Kink;
+
+impl Law for Kink {
+    fn potential<T: Real>(&self, q: &[T], _mass: &[f64]) -> T {
+        q[0] * q[0].value().signum()
+    }
+}
+
+#[test]
+fn test_stalled_line_search_keeps_the_best_iterate() {
+    // The Newton step jumps across the kink, and so does every halving of it.
+    let mut state = PhaseSpace::new(1);
+    state.q[0] = 1e-4;
+    let mut laws = LawRegistry::new();
+    laws.add(Kink);
+
+    let mut integrator = ImplicitEuler::default();
+    integrator.step(&mut state, &laws, &[], 1.0);
+    let failure = &integrator.solver.failures[0];
+    assert_eq!(failure.iterations, 1);
+    assert_eq!(failure.residual, 1.0);
+    assert_eq!(state.v[0], 0.0);
+    assert_eq!(state.q[0], 1e-4);
+}
@@ -0,0 +1,235 @@
+use glam::{DMat3, DQuat, DVec2, DVec3};
+use moo::core::geometry::{LieGroup, Manifold, Pose, Pose2, SE2, SE3, SO2, SO3, Twist, Twist2};
+
+/// Flattens tangent vectors and tangent maps so one checker serves every group.
+trait Coords: LieGroup {
+    fn to_vec(v: Self::Tangent) -> Vec<f64>;
+    fn from_vec(v: &[f64]) -> Self::Tangent;
+    fn matrix(m: Self::Matrix) -> Vec<Vec<f64>>;
+}
+
+impl Coords for SO3 {
+    fn to_vec(v: DVec3) -> Vec<f64> {
+        v.to_array().to_vec()
+    }
+    fn from_vec(v: &[f64]) -> DVec3 {
+        DVec3::from_slice(v)
+    }
+    fn matrix(m: DMat3) -> Vec<Vec<f64>> {
+        mat3_rows(m)
+    }
+}
+
+impl Coords for SE2 {
+    fn to_vec(v: Twist2) -> Vec<f64> {
+        vec![v.omega, v.v.x, v.v.y]
+    }
+    fn from_vec(v: &[f64]) -> Twist2 {
+        Twist2::new(v[0], DVec2::new(v[1], v[2]))
+    }
+    fn matrix(m: DMat3) -> Vec<Vec<f64>> {
+        mat3_rows(m)
+    }
+}
+
+impl Coords for SE3 {
+    fn to_vec(v: Twist) -> Vec<f64> {
+        v.to_array().to_vec()
+    }
+    fn from_vec(v: &[f64]) -> Twist {
+        Twist::from_array(v.try_into().unwrap())
+    }
+    fn matrix(m: [[f64; 6]; 6]) -> Vec<Vec<f64>> {
+        m.iter().map(|row| row.to_vec()).collect()
+    }
+}
+
+fn mat3_rows(m: DMat3) -> Vec<Vec<f64>> {
+    (0..3)
+        .map(|r| (0..3).map(|c| m.col(c)[r]).collect())
+        .collect()
+}
+
+fn assert_matrix_eq(a: &[Vec<f64>], b: &[Vec<f64>], tol: f64, what: &str) {
+    for (r, (ra, rb)) in a.iter().zip(b).enumerate() {
+        for (c, (x, y)) in ra.iter().zip(rb).enumerate() {
+            assert!((x - y).abs() < tol, "{what}[{r}][{c}]: {x} vs {y}");
+        }
+    }
+}
+
+fn identity(n: usize) -> Vec<Vec<f64>> {
+    (0..n)
+        .map(|r| (0..n).map(|c| if r == c { 1.0 } else { 0.0 }).collect())
+        .collect()
+}
+
+fn mat_mul(a: &[Vec<f64>], b: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    let n = a.len();
+    (0..n)
+        .map(|r| {
+            (0..n)
+                .map(|c| (0..n).map(|k| a[r][k] * b[k][c]).sum())
+                .collect()
+        })
+        .collect()
+}
+
+/// Central differences of `exp(v + δ)` expressed as body and spatial increments.
+fn check_jacobians<G: Coords>(v: G::Tangent) {
+    let n = G::dim();
+    let h = 1e-6;
+    let base = G::exp(v);
+    let coords = G::to_vec(v);
+
+    let mut right_fd = vec![vec![0.0; n]; n];
+    let mut left_fd = vec![vec![0.0; n]; n];
+    for col in 0..n {
+        let mut plus = coords.clone();
+        let mut minus = coords.clone();
+        plus[col] += h;
+        minus[col] -= h;
+        let (p, m) = (G::exp(G::from_vec(&plus)), G::exp(G::from_vec(&minus)));
+
+        let right_p = G::to_vec(G::log(G::compose(G::inverse(base), p)));
+        let right_m = G::to_vec(G::log(G::compose(G::inverse(base), m)));
+        let left_p = G::to_vec(G::log(G::compose(p, G::inverse(base))));
+        let left_m = G::to_vec(G::log(G::compose(m, G::inverse(base))));
+        for row in 0..n {
+            right_fd[row][col] = (right_p[row] - right_m[row]) / (2.0 * h);
+            left_fd[row][col] = (left_p[row] - left_m[row]) / (2.0 * h);
+        }
+    }
+
+    let jr = G::matrix(G::right_jacobian(v));
+    let jl = G::matrix(G::left_jacobian(v));
+    assert_matrix_eq(&jr, &right_fd, 1e-6, "J_r vs FD");
+    assert_matrix_eq(&jl, &left_fd, 1e-6, "J_l vs FD");
+
+    let jr_inv = G::matrix(G::right_jacobian_inv(v));
+    let jl_inv = G::matrix(G::left_jacobian_inv(v));
+    assert_matrix_eq(&mat_mul(&jr, &jr_inv), &identity(n), 1e-10, "J_r J_r⁻¹");
+    assert_matrix_eq(&mat_mul(&jl, &jl_inv), &identity(n), 1e-10, "J_l J_l⁻¹");
+
+    // J_l(v) = Ad_{exp(v)} J_r(v)
+    let ad = G::matrix(G::adjoint_matrix(base));
+    assert_matrix_eq(&mat_mul(&ad, &jr), &jl, 1e-10, "Ad J_r");
+}
+
+fn check_adjoint<G: Coords>(p: G::Point, v: G::Tangent) {
+    let lhs = G::compose(G::compose(p, G::exp(v)), G::inverse(p));
+    let rhs = G::exp(G::adjoint(p, v));
+    let diff = G::to_vec(G::local(lhs, rhs));
+    assert!(
+        diff.iter().all(|d| d.abs() < 1e-10),
+        "adjoint mismatch {diff:?}"
+    );
+
+    let ad = G::matrix(G::adjoint_matrix(p));
+    let coords = G::to_vec(v);
+    let expected = G::to_vec(G::adjoint(p, v));
+    for (row, e) in ad.iter().zip(expected) {
+        let x: f64 = row.iter().zip(&coords).map(|(a, b)| a * b).sum();
+        assert!((x - e).abs() < 1e-12, "adjoint matrix {x} vs {e}");
+    }
+}
+
+fn check_hat_vee<G: Coords>(v: G::Tangent) {
+    let back = G::to_vec(G::vee(G::hat(v)));
+    for (a, b) in back.iter().zip(G::to_vec(v)) {
+        assert_eq!(*a, b);
+    }
+}
+
+#[test]
+fn test_so3_retract_convention() {
+    // `v` is the full rotation vector: a quarter turn about z maps X onto Y.
+    let q = SO3::retract(
+        SO3::identity(),
+        DVec3::new(0.0, 0.0, std::f64::consts::FRAC_PI_2),
+    );
+    assert!((q * DVec3::X - DVec3::Y).length() < 1e-12);
+
+    // Body-frame increment: retract(p, v) = p * exp(v), not exp(v) * p.
+    let p = DQuat::from_rotation_x(0.7);
+    let v = DVec3::new(0.0, 0.3, 0.0);
+    let expected = p * DQuat::from_rotation_y(0.3);
+    assert!(SO3::retract(p, v).dot(expected).abs() > 1.0 - 1e-12);
+}
+
+#[test]
+fn test_so3_log_takes_shortest_path() {
+    let q = -DQuat::from_rotation_z(0.5); // Same rotation, other hemisphere
+    assert!((SO3::log(q) - DVec3::new(0.0, 0.0, 0.5)).length() < 1e-12);
+    let p = DQuat::from_rotation_y(1.0);
+    assert!((SO3::local(p, -p * q) - DVec3::new(0.0, 0.0, 0.5)).length() < 1e-12);
+}
+
+#[test]
+fn test_so3_hat_is_cross_product() {
+    let a = DVec3::new(0.3, -1.2, 2.0);
+    let b = DVec3::new(-0.7, 0.4, 1.5);
+    assert!((SO3::hat(a) * b - a.cross(b)).length() < 1e-12);
+    check_hat_vee::<SO3>(a);
+}
+
+#[test]
+fn test_so3_jacobians() {
+    for v in [
+        DVec3::new(0.3, -1.2, 0.7),
+        DVec3::new(2.5, 0.4, -0.9),
+        DVec3::new(1e-6, -2e-6, 5e-7), // Taylor branch
+        DVec3::ZERO,
+    ] {
+        check_jacobians::<SO3>(v);
+    }
+}
+
+#[test]
+fn test_so3_jacobian_series_is_continuous() {
+    let axis = DVec3::new(1.0, 2.0, -0.5).normalize();
+    let below = SO3::right_jacobian_inv(axis * 0.99e-4);
+    let above = SO3::right_jacobian_inv(axis * 1.01e-4);
+    assert!((below - above).abs_diff_eq(DMat3::ZERO, 1e-5));
+    let below = SO3::right_jacobian(axis * 0.99e-4);
+    let above = SO3::right_jacobian(axis * 1.01e-4);
+    assert!((below - above).abs_diff_eq(DMat3::ZERO, 1e-5));
+}
+
+#[test]
+fn test_so3_adjoint() {
+    check_adjoint::<SO3>(
+        DQuat::from_euler(glam::EulerRot::XYZ, 0.3, -0.8, 1.4),
+        DVec3::new(0.2, 0.5, -0.1),
+    );
+}
+
+#[test]
+fn test_se2_lie_group() {
+    let pose = Pose2::new(0.9, DVec2::new(1.0, -2.0));
+    let xi = Twist2::new(0.6, DVec2::new(0.3, -0.4));
+    check_adjoint::<SE2>(pose, xi);
+    check_hat_vee::<SE2>(xi);
+    check_jacobians::<SE2>(xi);
+    check_jacobians::<SE2>(Twist2::new(1e-7, DVec2::new(1.0, 2.0)));
+}
+
+#[test]
+fn test_se3_lie_group() {
+    let pose = Pose::new(DQuat::from_rotation_y(0.8), DVec3::new(1.0, -2.0, 0.5));
+    let xi = Twist::new(DVec3::new(0.4, -0.9, 0.3), DVec3::new(1.0, 0.2, -0.7));
+    check_adjoint::<SE3>(pose, xi);
+    check_hat_vee::<SE3>(xi);
+    check_jacobians::<SE3>(xi);
+    check_jacobians::<SE3>(Twist::new(
+        DVec3::new(1e-6, 0.0, -1e-6),
+        DVec3::new(0.5, 1.0, 2.0),
+    ));
+}
+
+#[test]
+fn test_so2_lie_group() {
+    assert_eq!(SO2::compose(3.0, SO2::inverse(3.0)), 0.0);
+    assert_eq!(SO2::vee(SO2::hat(0.4)), 0.4);
+    assert_eq!(SO2::right_jacobian(1.3), 1.0);
+}
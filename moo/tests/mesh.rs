@@ -0,0 +1,214 @@
+mod common;
+
+use common::UniformGravity;
+use glam::{DQuat, DVec3};
+use moo::core::geometry::Pose;
+use moo::core::geometry::shapes::{Aabb, Bvh, TriMesh};
+use moo::core::solve::constraints::{Constraint, MeshConstraint};
+use moo::core::solve::{Integrator, VelocityVerlet};
+use moo::core::state::PhaseSpace;
+use moo::laws::registry::LawRegistry;
+
+const TETRAHEDRON: [[DVec3; 3]; 4] = [
+    [DVec3::ZERO, DVec3::Y, DVec3::X],
+    [DVec3::ZERO, DVec3::X, DVec3::Z],
+    [DVec3::ZERO, DVec3::Z, DVec3::Y],
+    [DVec3::X, DVec3::Y, DVec3::Z],
+];
+
+/// Open-top box `[-1, 1] x [0, 2] x [-1, 1]`: floor plus four walls, as quads.
+const CONTAINER_OBJ: &str = "\
+# container
+v -1 0 -1
+v  1 0 -1
+v  1 0  1
+v -1 0  1
+v -1 2 -1
+v  1 2 -1
+v  1 2  1
+v -1 2  1
+f 1 2 3 4
+f 1/1 2/1 6/1 5/1
+f 2//1 3//1 7//1 6//1
+f -6 -5 -1 -2
+f 4 1 5 8
+";
+
+fn binary_stl(facets: &[[DVec3; 3]]) -> Vec<u8> {
+    let mut bytes = vec![0u8; 80];
+    bytes.extend((facets.len() as u32).to_le_bytes());
+    for facet in facets {
+        bytes.extend([0u8; 12]);
+        for v in facet {
+            for c in v.to_array() {
+                bytes.extend((c as f32).to_le_bytes());
+            }
+        }
+        bytes.extend([0u8; 2]);
+    }
+    bytes
+}
+
+fn ascii_stl(facets: &[[DVec3; 3]]) -> String {
+    let mut text = String::from("solid tetra\n");
+    for facet in facets {
+        text += "  facet normal 0 0 0\n    outer loop\n";
+        for v in facet {
+            text += &format!("      vertex {} {} {}\n", v.x, v.y, v.z);
+        }
+        text += "    endloop\n  endfacet\n";
+    }
+    text + "endsolid tetra\n"
+}
+
+#[test]
+fn test_obj_faces_are_fan_triangulated() {
+    let mesh = TriMesh::from_obj(CONTAINER_OBJ).unwrap();
+    assert_eq!(mesh.vertices.len(), 8);
+    assert_eq!(mesh.triangles.len(), 10);
+    // Relative indices count back from the last vertex: "-6" is vertex 3.
+    assert_eq!(mesh.triangles[6], [2, 3, 7]);
+
+    let bounds = mesh.bounds().unwrap();
+    assert_eq!(bounds.min, DVec3::new(-1.0, 0.0, -1.0));
+    assert_eq!(bounds.max, DVec3::new(1.0, 2.0, 1.0));
+
+    assert!(TriMesh::from_obj("v 0 0 0\nf 1 2 3\n").is_err());
+    assert!(TriMesh::from_obj("v 0 zero 0\n").is_err());
+}
+
+#[test]
+fn test_stl_ascii_and_binary_agree() {
+    let ascii = TriMesh::from_stl(ascii_stl(&TETRAHEDRON).as_bytes()).unwrap();
+    let binary = TriMesh::from_stl(&binary_stl(&TETRAHEDRON)).unwrap();
+
+    // Shared corners are welded.
+    assert_eq!(ascii.vertices.len(), 4);
+    assert_eq!(ascii.triangles.len(), 4);
+    assert_eq!(ascii, binary);
+}
+
+#[test]
+fn test_load_by_extension() {
+    let dir = std::env::temp_dir().join(format!("moo-mesh-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let obj = dir.join("container.OBJ");
+    std::fs::write(&obj, CONTAINER_OBJ).unwrap();
+    assert_eq!(TriMesh::load(&obj).unwrap().triangles.len(), 10);
+
+    let stl = dir.join("tetra.stl");
+    std::fs::write(&stl, binary_stl(&TETRAHEDRON)).unwrap();
+    assert_eq!(TriMesh::load(&stl).unwrap().triangles.len(), 4);
+
+    assert!(TriMesh::load(dir.join("mesh.ply")).is_err());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_bvh_query_matches_brute_force() {
+    let boxes: Vec<Aabb> = (0..200)
+        .map(|i| {
+            let t = i as f64;
+            let c = DVec3::new((t * 0.37).sin() * 10.0, (t * 0.91).cos() * 10.0, t * 0.05);
+            Aabb::around(c, 0.2 + (t * 0.13).sin().abs())
+        })
+        .collect();
+    let bvh = Bvh::build(&boxes);
+
+    for query in [
+        Aabb::around(DVec3::ZERO, 3.0),
+        Aabb::around(DVec3::new(5.0, -5.0, 4.0), 1.0),
+        Aabb::new(DVec3::splat(-100.0), DVec3::splat(100.0)),
+        Aabb::around(DVec3::splat(50.0), 1.0),
+    ] {
+        let mut found = Vec::new();
+        bvh.query(&query, |i| found.push(i));
+        found.sort();
+        let expected: Vec<usize> = (0..boxes.len())
+            .filter(|&i| boxes[i].intersects(&query))
+            .collect();
+        assert_eq!(found, expected);
+    }
+}
+
+#[test]
+fn test_sphere_contact_is_two_sided() {
+    let ground = TriMesh::new(
+        vec![
+            DVec3::new(-1.0, 0.0, -1.0),
+            DVec3::new(1.0, 0.0, -1.0),
+            DVec3::new(1.0, 0.0, 1.0),
+            DVec3::new(-1.0, 0.0, 1.0),
+        ],
+        vec![[0, 2, 1], [0, 3, 2]],
+    );
+    let lifted = Pose::new(DQuat::IDENTITY, DVec3::new(0.0, 1.0, 0.0));
+
+    let above = ground
+        .sphere_contact(lifted, DVec3::new(0.2, 1.3, 0.1), 0.5)
+        .unwrap();
+    assert!((above.depth - 0.2).abs() < 1e-12);
+    assert!((above.normal - DVec3::Y).length() < 1e-12);
+    assert!((above.point_a - above.point_b - above.normal * above.depth).length() < 1e-12);
+
+    let below = ground
+        .sphere_contact(lifted, DVec3::new(0.2, 0.9, 0.1), 0.5)
+        .unwrap();
+    assert!((below.normal + DVec3::Y).length() < 1e-12);
+
+    // Past the edge the closest feature is the rim, so the normal tilts outwards.
+    let rim = ground
+        .sphere_contact(lifted, DVec3::new(1.3, 1.3, 0.0), 0.5)
+        .unwrap();
+    assert!((rim.normal - DVec3::new(1.0, 1.0, 0.0).normalize()).length() < 1e-12);
+
+    assert!(
+        ground
+            .sphere_contact(lifted, DVec3::new(0.0, 1.6, 0.0), 0.5)
+            .is_none()
+    );
+}
+
+#[test]
+fn test_particles_stay_in_mesh_container() {
+    let container = TriMesh::from_obj(CONTAINER_OBJ).unwrap();
+    let constraints: Vec<Box<dyn Constraint>> = vec![Box::new(MeshConstraint::new(
+        container,
+        Pose::IDENTITY,
+        0.5,
+        0.1,
+    ))];
+
+    let n = 8;
+    let mut state = PhaseSpace::new(n * 3);
+    for i in 0..n {
+        let t = i as f64;
+        state.radius[i] = 0.1;
+        state.set_position(
+            i,
+            DVec3::new(0.5 * (t * 1.3).sin(), 1.5, 0.5 * (t * 0.7).cos()),
+        );
+        state.set_velocity(
+            i,
+            DVec3::new(4.0 * (t * 2.1).cos(), 0.0, 4.0 * (t * 1.7).sin()),
+        );
+    }
+
+    let mut laws = LawRegistry::new();
+    laws.add(UniformGravity(9.81));
+    for _ in 0..2000 {
+        VelocityVerlet.step(&mut state, &laws, &constraints, 0.002);
+        for i in 0..n {
+            let p = state.position(i);
+            assert!(
+                p.y > 0.0 && p.x.abs() < 1.0 && p.z.abs() < 1.0,
+                "escaped: {p}"
+            );
+        }
+    }
+    // Settled on the floor.
+    for i in 0..n {
+        assert!((state.position(i).y - 0.1).abs() < 1e-2);
+    }
+}
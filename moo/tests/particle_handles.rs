@@ -0,0 +1,185 @@
+use glam::{DQuat, DVec3};
+use moo::core::state::{ParticleRegistry, PhaseSpace};
+use moo::laws::classical::Spring;
+use moo::laws::registry::LawRegistry;
+
+fn line_of_particles(n: usize) -> (PhaseSpace, ParticleRegistry) {
+    let mut state = PhaseSpace::new(n * 3);
+    for i in 0..n {
+        state.set_position(i, DVec3::new(i as f64, 0.0, 0.0));
+        state.radius[i] = 0.1 * (i + 1) as f64;
+    }
+    let registry = ParticleRegistry::new(&state);
+    (state, registry)
+}
+
+#[test]
+fn test_handles_follow_particles() {
+    let (mut state, mut registry) = line_of_particles(5);
+    let handles: Vec<_> = (0..5).map(|i| registry.handle(i).unwrap()).collect();
+
+    let reindex = registry.remove(&mut state, handles[1]).unwrap();
+    assert_eq!(state.particle_count(), 4);
+    assert_eq!(registry.len(), 4);
+    assert_eq!(reindex.get(0), Some(0));
+    assert_eq!(reindex.get(1), None);
+    assert_eq!(reindex.get(4), Some(3));
+
+    // Everyone else kept their data and their handle.
+    assert!(!registry.contains(handles[1]));
+    for &k in &[0, 2, 3, 4] {
+        let i = registry.index(handles[k]).unwrap();
+        assert_eq!(state.position(i).x, k as f64);
+        assert_eq!(state.radius[i], 0.1 * (k + 1) as f64);
+        assert_eq!(registry.handle(i), Some(handles[k]));
+    }
+
+    // The freed slot is reused, but the stale handle does not resolve to the newcomer.
+    let fresh = registry.insert(&mut state, DVec3::new(9.0, 1.0, 0.0), DVec3::Y);
+    assert_eq!(registry.index(fresh), Some(4));
+    assert_ne!(fresh, handles[1]);
+    assert_eq!(registry.index(handles[1]), None);
+    assert!(registry.remove(&mut state, handles[1]).is_none());
+    assert_eq!(state.velocity(4), DVec3::Y);
+    assert_eq!(state.mass[12..15], [1.0; 3]);
+}
+
+#[test]
+fn test_springs_survive_removal() {
+    let (mut state, mut registry) = line_of_particles(4);
+    let handles: Vec<_> = (0..4).map(|i| registry.handle(i).unwrap()).collect();
+    state.set_position(3, DVec3::new(2.0, 1.5, 0.0));
+
+    let mut laws = LawRegistry::new();
+    laws.add(Spring::new(10.0, 0.5, 0, 1));
+    laws.add(Spring::new(10.0, 0.5, 2, 3));
+    let before = laws.potential(&state.q, &state.mass);
+
+    // Removing particle 1 breaks the first spring and shifts the second one down.
+    let reindex = registry.remove(&mut state, handles[1]).unwrap();
+    laws.reindex(&reindex);
+    assert_eq!(laws.len(), 1);
+
+    // The surviving spring still connects the same two particles.
+    let stretch = 1.5 - 0.5;
+    let expected = 0.5 * 10.0 * stretch * stretch;
+    assert!((laws.potential(&state.q, &state.mass) - expected).abs() < 1e-12);
+    assert!(before > expected);
+
+    let mut grad = vec![0.0; state.dof];
+    laws.gradient(&state.q, &state.mass, &mut grad);
+    let i3 = registry.index(handles[3]).unwrap();
+    assert!((grad[i3 * 3 + 1] - 10.0 * stretch).abs() < 1e-12);
+}
+
+#[test]
+fn test_rigid_bodies_compact_with_particles() {
+    let (mut state, mut registry) = line_of_particles(4);
+    state.resize_rigid(3);
+    for i in 0..3 {
+        state.rot[i] = DQuat::from_rotation_z(i as f64);
+    }
+    let second = registry.handle(1).unwrap();
+    let third = registry.handle(2).unwrap();
+    let loose = registry.handle(3).unwrap();
+
+    registry.remove_many(&mut state, &[second, loose]);
+    assert_eq!(state.particle_count(), 2);
+    assert_eq!(state.rot.len(), 2);
+    let i = registry.index(third).unwrap();
+    assert_eq!(i, 1);
+    assert_eq!(state.rigid_pose(i).rot, DQuat::from_rotation_z(2.0));
+    assert_eq!(state.rigid_pose(i).trans, DVec3::new(2.0, 0.0, 0.0));
+}
+
+#[test]
+fn test_emitter_and_sink() {
+    let mut state = PhaseSpace::planar(0);
+    let mut registry = ParticleRegistry::new(&state);
+    let mut emitted = Vec::new();
+
+    for step in 0..50 {
+        emitted.push(registry.insert(&mut state, DVec3::new(step as f64, 10.0, 0.0), -DVec3::Y));
+
+        // Drift, then drain everything that fell below the sink.
+        for i in 0..state.particle_count() {
+            let p = state.position(i) + state.velocity(i);
+            state.set_position(i, p);
+        }
+        let sunk: Vec<_> = (0..state.particle_count())
+            .filter(|&i| state.position(i).y < 0.0)
+            .map(|i| registry.handle(i).unwrap())
+            .collect();
+        let reindex = registry.remove_many(&mut state, &sunk);
+        assert_eq!(reindex.is_identity(), sunk.is_empty());
+
+        assert_eq!(registry.len(), state.particle_count());
+        assert_eq!(state.blocks.len(), usize::from(!registry.is_empty()));
+    }
+
+    // Each particle survives 10 drifts, identified by its emission x.
+    assert_eq!(registry.len(), 10);
+    for (step, handle) in emitted.iter().enumerate() {
+        match registry.index(*handle) {
+            Some(i) => {
+                assert!(step >= 40);
+                assert_eq!(state.position(i).x, step as f64);
+            }
+            None => assert!(step < 40),
+        }
+    }
+}
+
+#[test]
+fn test_remove_with_untracked_particles() {
+    let (mut state, mut registry) = line_of_particles(3);
+    let first = registry.handle(0).unwrap();
+    // Two particles the registry never saw.
+    state.resize(state.dof + 6);
+    state.set_position(4, DVec3::new(4.0, 0.0, 0.0));
+
+    registry.remove(&mut state, first).unwrap();
+    assert_eq!(state.particle_count(), 4);
+    assert_eq!(registry.len(), 2);
+    assert_eq!(state.position(3).x, 4.0);
+    assert_eq!(state.position(0).x, 1.0);
+}
+
+#[test]
+fn test_insert_after_untracked_particles() {
+    let (mut state, mut registry) = line_of_particles(1);
+    let first = registry.handle(0).unwrap();
+    // Particle 1 is added behind the registry's back, particle 2 through it.
+    state.resize(state.dof + 3);
+    let emitted = registry.insert(&mut state, DVec3::new(2.0, 0.0, 0.0), DVec3::ZERO);
+
+    assert_eq!(registry.len(), 2);
+    assert_eq!(registry.handle(1), None);
+    assert_eq!(registry.handle(2), Some(emitted));
+    assert_eq!(registry.index(emitted), Some(2));
+
+    registry.remove(&mut state, first).unwrap();
+    assert!(!registry.contains(first));
+    assert_eq!(registry.handle(0), None);
+    assert_eq!(registry.index(emitted), Some(1));
+    assert_eq!(state.position(1).x, 2.0);
+
+    // The freed slot comes back with a new generation, at the right index.
+    let again = registry.insert(&mut state, DVec3::new(3.0, 0.0, 0.0), DVec3::ZERO);
+    assert_ne!(again, first);
+    assert_eq!(registry.index(again), Some(2));
+    assert_eq!(registry.handle(2), Some(again));
+    assert_eq!(registry.len(), 2);
+}
+
+#[test]
+fn test_handles_of_truncated_particles_stop_resolving() {
+    let (mut state, mut registry) = line_of_particles(3);
+    let last = registry.handle(2).unwrap();
+    state.resize(state.dof - 3);
+
+    let emitted = registry.insert(&mut state, DVec3::new(5.0, 0.0, 0.0), DVec3::ZERO);
+    assert!(!registry.contains(last));
+    assert_eq!(registry.index(emitted), Some(2));
+    assert_eq!(registry.len(), 3);
+}
@@ -0,0 +1,165 @@
+use glam::{DVec2, DVec3};
+use moo::core::geometry::{Manifold, Pose2, SE2, SE3, SO2, Twist, Twist2};
+use moo::core::solve::constraints::{Constraint, SphereConstraint};
+use moo::core::solve::{Integrator, VelocityVerlet};
+use moo::core::state::PhaseSpace;
+use moo::investigation::verify::GradientCheck;
+use moo::laws::classical::{Gravity, Spring};
+use moo::laws::continuum::SPH;
+use moo::laws::registry::LawRegistry;
+use std::f64::consts::PI;
+
+#[test]
+fn test_so2_wraps_across_pi() {
+    let a = SO2::retract(3.0, 0.5);
+    assert!(a < 0.0 && (a - (3.5 - 2.0 * PI)).abs() < 1e-12);
+
+    // The shortest path from just below π to just above -π is a small positive step.
+    let d = SO2::local(PI - 0.1, -PI + 0.1);
+    assert!((d - 0.2).abs() < 1e-12, "local = {d}");
+}
+
+#[test]
+fn test_se2_exp_log_roundtrip() {
+    let twists = [
+        Twist2::new(0.7, DVec2::new(1.0, -2.0)),
+        Twist2::new(1e-7, DVec2::new(0.3, 0.4)),
+        Twist2::new(0.0, DVec2::new(-4.0, 1.0)),
+        Twist2::new(-2.5, DVec2::new(0.1, 0.2)),
+    ];
+    for xi in twists {
+        let back = SE2::log(SE2::exp(xi));
+        assert!((back.omega - xi.omega).abs() < 1e-10, "{back:?} vs {xi:?}");
+        assert!((back.v - xi.v).length() < 1e-10, "{back:?} vs {xi:?}");
+    }
+}
+
+#[test]
+fn test_se2_matches_se3_about_z() {
+    let xi = Twist2::new(1.1, DVec2::new(0.5, -0.3));
+    let planar = SE2::exp(xi);
+    let spatial = SE3::exp(Twist::new(DVec3::new(0.0, 0.0, xi.omega), xi.v.extend(0.0)));
+
+    assert!((planar.trans.extend(0.0) - spatial.trans).length() < 1e-12);
+    let p = DVec2::new(2.0, 1.0);
+    let expected = spatial.transform_point(p.extend(0.0)).truncate();
+    assert!((planar.transform_point(p) - expected).length() < 1e-12);
+}
+
+#[test]
+fn test_se2_group_and_adjoint() {
+    let a = Pose2::new(0.4, DVec2::new(1.0, 2.0));
+    let b = Pose2::new(-1.3, DVec2::new(-0.5, 0.7));
+
+    let id = a * a.inverse();
+    assert!(id.angle.abs() < 1e-12 && id.trans.length() < 1e-12);
+
+    // retract / local are inverse to each other.
+    let xi = SE2::local(a, b);
+    let b2 = SE2::retract(a, xi);
+    assert!((b2.angle - b.angle).abs() < 1e-12 && (b2.trans - b.trans).length() < 1e-12);
+
+    // T exp(ξ) T⁻¹ = exp(Ad_T ξ)
+    let xi = Twist2::new(0.3, DVec2::new(0.2, -0.1));
+    let lhs = a * SE2::exp(xi) * a.inverse();
+    let rhs = SE2::exp(SE2::adjoint(a, xi));
+    assert!((lhs.angle - rhs.angle).abs() < 1e-12);
+    assert!((lhs.trans - rhs.trans).length() < 1e-12);
+}
+
+#[test]
+fn test_planar_state_layout() {
+    let mut state = PhaseSpace::planar(6);
+    assert_eq!(state.dim, 2);
+    assert_eq!(state.particle_count(), 3);
+    assert_eq!(state.radius.len(), 3);
+
+    state.set_particle_mass(1, 4.0);
+    assert_eq!(state.mass, vec![1.0, 1.0, 4.0, 4.0, 1.0, 1.0]);
+
+    state.set_position(2, DVec3::new(5.0, 6.0, 7.0));
+    assert_eq!(&state.q[4..6], &[5.0, 6.0]);
+    assert_eq!(state.position(2), DVec3::new(5.0, 6.0, 0.0));
+
+    state.resize_rigid(3);
+    let pose = Pose2::new(2.0, DVec2::new(5.0, 6.0));
+    state.set_planar_pose(2, pose);
+    let back = state.planar_pose(2);
+    assert!((back.angle - pose.angle).abs() < 1e-12);
+    assert_eq!(back.trans, pose.trans);
+}
+
+#[test]
+fn test_planar_circular_orbit() {
+    let mut state = PhaseSpace::planar(4); // 2 Bodies, 2 DOF each
+
+    let (m1, m2, dist, g) = (1000.0_f64, 10.0_f64, 100.0_f64, 1.0_f64);
+    let v_rel = (g * (m1 + m2) / dist).sqrt();
+    let (frac1, frac2) = (m1 / (m1 + m2), m2 / (m1 + m2));
+
+    state.set_particle_mass(0, m1);
+    state.set_particle_mass(1, m2);
+    state.set_position(0, DVec3::new(-dist * frac2, 0.0, 0.0));
+    state.set_position(1, DVec3::new(dist * frac1, 0.0, 0.0));
+    state.set_velocity(0, DVec3::new(0.0, -v_rel * frac2, 0.0));
+    state.set_velocity(1, DVec3::new(0.0, v_rel * frac1, 0.0));
+
+    let mut registry = LawRegistry::new();
+    registry.add(Gravity::new(g).with_dim(2));
+
+    let mut solver = VelocityVerlet;
+    for _ in 0..10000 {
+        solver.step(&mut state, &registry, &[], 0.001);
+    }
+
+    let final_dist = (state.position(0) - state.position(1)).length();
+    assert!(
+        (final_dist - dist).abs() < 1e-1,
+        "Planar orbit should remain circular, got distance {final_dist}"
+    );
+}
+
+#[test]
+fn test_planar_laws_gradient_check() {
+    let mut state = PhaseSpace::planar(6);
+    state.q = vec![0.0, 0.0, 1.1, 0.2, 0.3, 0.9];
+    state.set_particle_mass(2, 2.0);
+
+    let check = GradientCheck::default();
+    let gravity = Gravity::new(1.5).with_dim(2);
+    let spring = Spring::new(10.0, 0.8, 0, 2).with_dim(2);
+    let sph = SPH::new(2.0, 1.0, 5.0).with_dim(2);
+
+    for report in [
+        check.check_law(&gravity, &state),
+        check.check_law(&spring, &state),
+        check.check_law(&sph, &state),
+    ] {
+        assert!(report.passed(), "{report}");
+    }
+}
+
+#[test]
+fn test_planar_sphere_constraint() {
+    let mut state = PhaseSpace::planar(4);
+    state.radius = vec![1.0, 1.0];
+    state.set_position(1, DVec3::new(1.5, 0.0, 0.0));
+    state.set_velocity(0, DVec3::new(1.0, 0.0, 0.0));
+
+    SphereConstraint::new(1.0).project(&mut state);
+
+    let sep = (state.position(1) - state.position(0)).length();
+    assert!((sep - 2.0).abs() < 1e-12, "separation {sep}");
+    // Equal masses, elastic: velocities swap.
+    assert!((state.v[0]).abs() < 1e-12 && (state.v[2] - 1.0).abs() < 1e-12);
+}
+
+#[test]
+#[should_panic(expected = "expects 3D particles, the state is 2D")]
+fn test_law_dimension_must_match_state() {
+    let mut state = PhaseSpace::planar(4);
+    state.set_position(1, DVec3::X);
+    let mut registry = LawRegistry::new();
+    registry.add(Gravity::new(1.0)); // forgot `.with_dim(2)`
+    VelocityVerlet.step(&mut state, &registry, &[], 0.01);
+}
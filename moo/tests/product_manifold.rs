@@ -0,0 +1,148 @@
+use glam::{DQuat, DVec3};
+use moo::core::geometry::{Euclidean3, LieGroup, Manifold, Product, ProductTangent, SO2, SO3};
+use moo::core::solve::{Integrator, SymplecticEuler, VelocityVerlet};
+use moo::core::state::{Block, PhaseSpace};
+use moo::laws::registry::LawRegistry;
+
+type RigidBody = Product<Euclidean3, SO3>;
+
+#[test]
+fn test_product_retract_local_roundtrip() {
+    assert_eq!(RigidBody::dim(), 6);
+
+    let p = (DVec3::new(1.0, 2.0, 3.0), DQuat::from_rotation_x(0.4));
+    let v = ProductTangent(DVec3::new(-0.5, 0.1, 0.2), DVec3::new(0.3, -0.2, 0.9));
+    let q = RigidBody::retract(p, v);
+    let back = RigidBody::local(p, q);
+
+    assert!((back.0 - v.0).length() < 1e-12);
+    assert!((back.1 - v.1).length() < 1e-12);
+}
+
+#[test]
+fn test_product_lie_group() {
+    type Torus = Product<SO2, SO2>;
+    let a = (3.0, -1.0);
+    let id = Torus::compose(a, Torus::inverse(a));
+    assert!(id.0.abs() < 1e-12 && id.1.abs() < 1e-12);
+
+    let p = (DVec3::new(1.0, 0.0, 0.0), DQuat::from_rotation_z(0.5));
+    let v = ProductTangent(DVec3::Y, DVec3::new(0.1, 0.2, 0.3));
+    let (ad_t, ad_r) = RigidBody::adjoint_matrix(p);
+    let ad = RigidBody::adjoint(p, v);
+    assert!((ad_t * v.0 - ad.0).length() < 1e-12);
+    assert!((ad_r * v.1 - ad.1).length() < 1e-12);
+}
+
+#[test]
+fn test_default_layout() {
+    let mut state = PhaseSpace::new(6);
+    assert_eq!(
+        state.blocks,
+        vec![Block::Euclidean {
+            dim: 3,
+            start: 0,
+            count: 2
+        }]
+    );
+
+    state.resize_rigid(2);
+    assert_eq!(state.blocks[1], Block::Rotation { start: 0, count: 2 });
+    assert_eq!(state.tangent_dim(), 12);
+
+    // A flat configuration that is not made of particles is plain R^n.
+    let state = PhaseSpace::new(1);
+    assert_eq!(
+        state.blocks,
+        vec![Block::Euclidean {
+            dim: 1,
+            start: 0,
+            count: 1
+        }]
+    );
+}
+
+#[test]
+fn test_drift_matches_product_retract() {
+    let mut state = PhaseSpace::new(3);
+    state.resize_rigid(1);
+
+    let p0 = (DVec3::new(1.0, -2.0, 0.5), DQuat::from_rotation_y(0.3));
+    let v = DVec3::new(0.4, 0.0, -1.0);
+    let omega = DVec3::new(0.2, 1.5, -0.7);
+    state.q.copy_from_slice(&p0.0.to_array());
+    state.rot[0] = p0.1;
+    state.v.copy_from_slice(&v.to_array());
+    state.ang_v[0] = omega;
+
+    let dt = 0.1;
+    state.drift(dt);
+
+    let (p, r) = RigidBody::retract(p0, ProductTangent(v * dt, omega * dt));
+    assert!((state.position(0) - p).length() < 1e-12);
+    assert!(state.rot[0].dot(r).abs() > 1.0 - 1e-12);
+}
+
+#[test]
+fn test_custom_blocks_are_integrated() {
+    // Two blocks over the same `q`: a 3D particle followed by a 1D coordinate.
+    let mut state = PhaseSpace::new(4);
+    state.blocks = vec![
+        Block::Euclidean {
+            dim: 3,
+            start: 0,
+            count: 1,
+        },
+        Block::Euclidean {
+            dim: 1,
+            start: 3,
+            count: 1,
+        },
+    ];
+    state.v = vec![1.0, 2.0, 3.0, -4.0];
+
+    let laws = LawRegistry::new();
+    SymplecticEuler.step(&mut state, &laws, &[], 0.5);
+    assert_eq!(state.q, vec![0.5, 1.0, 1.5, -2.0]);
+}
+
+#[test]
+fn test_symplectic_euler_rotates_rigid_bodies() {
+    // SymplecticEuler used to leave orientations untouched; it now drifts them like
+    // VelocityVerlet. Spin about a principal axis is a steady rotation for both.
+    let omega = 2.0;
+    let steps = 100;
+    let dt = 0.01;
+
+    let mut a = PhaseSpace::new(0);
+    a.resize_rigid(1);
+    a.inertia[0] = DVec3::new(1.0, 2.0, 3.0);
+    a.ang_v[0] = DVec3::new(0.0, omega, 0.0);
+    let mut b = a.clone();
+
+    let laws = LawRegistry::new();
+    for _ in 0..steps {
+        SymplecticEuler.step(&mut a, &laws, &[], dt);
+        VelocityVerlet.step(&mut b, &laws, &[], dt);
+    }
+
+    let expected = DQuat::from_rotation_y(omega * dt * steps as f64);
+    assert!(a.rot[0].dot(expected).abs() > 1.0 - 1e-10);
+    assert!(b.rot[0].dot(expected).abs() > 1.0 - 1e-10);
+}
+
+#[test]
+fn test_symplectic_euler_moves_directors() {
+    // A free director follows its great circle at constant speed.
+    let mut state = PhaseSpace::new(0);
+    state.resize_directors(1);
+    state.dir[0] = DVec3::X;
+    state.dir_v[0] = DVec3::Z * 0.5;
+
+    let laws = LawRegistry::new();
+    for _ in 0..100 {
+        SymplecticEuler.step(&mut state, &laws, &[], 0.01);
+    }
+    let expected = DVec3::new(0.5f64.cos(), 0.0, 0.5f64.sin());
+    assert!((state.dir[0] - expected).length() < 1e-12);
+}
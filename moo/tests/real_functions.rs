@@ -0,0 +1,85 @@
+use moo::core::math::ad::Dual;
+use moo::core::math::real::Real;
+
+/// Central finite difference of a scalar function.
+fn numeric_derivative(f: impl Fn(f64) -> f64, x: f64) -> f64 {
+    let h = 1e-6;
+    (f(x + h) - f(x - h)) / (2.0 * h)
+}
+
+fn check(name: &str, f: impl Fn(Dual) -> Dual, g: impl Fn(f64) -> f64, x: f64) {
+    let ad = f(Dual::variable(x));
+    let fd = numeric_derivative(&g, x);
+
+    assert!(
+        (ad.val - g(x)).abs() < 1e-12,
+        "{name}: value mismatch {} vs {}",
+        ad.val,
+        g(x)
+    );
+    assert!(
+        (ad.der - fd).abs() < 1e-6 * (1.0 + fd.abs()),
+        "{name}: derivative mismatch AD={} FD={}",
+        ad.der,
+        fd
+    );
+}
+
+#[test]
+fn test_dual_elementary_functions() {
+    let x = 0.7;
+    check("sqrt", |d| d.sqrt(), |v| v.sqrt(), x);
+    check("powi", |d| d.powi(5), |v| v.powi(5), x);
+    check("powi_neg", |d| d.powi(-3), |v| v.powi(-3), x);
+    check("powf", |d| d.powf(2.5), |v| v.powf(2.5), x);
+    check("exp", |d| d.exp(), |v| v.exp(), x);
+    check("ln", |d| d.ln(), |v| v.ln(), x);
+    check("sin", |d| d.sin(), |v| v.sin(), x);
+    check("cos", |d| d.cos(), |v| v.cos(), x);
+    check("tan", |d| d.tan(), |v| v.tan(), x);
+    check("tanh", |d| d.tanh(), |v| v.tanh(), x);
+    check("recip", |d| d.recip(), |v| v.recip(), x);
+    check("abs_neg", |d| d.abs(), |v| v.abs(), -x);
+    check(
+        "atan2",
+        |d| d.atan2(Dual::constant(-0.3)),
+        |v| v.atan2(-0.3),
+        x,
+    );
+    check(
+        "atan2_x",
+        |d| Dual::constant(0.4).atan2(d),
+        |v| 0.4_f64.atan2(v),
+        x,
+    );
+}
+
+#[test]
+fn test_dual_morse_potential() {
+    // Morse: V(r) = D (1 - exp(-a (r - r0)))^2
+    let (d, a, r0) = (2.0, 1.5, 1.0);
+    let morse = |r: Dual| {
+        let s = Dual::constant(1.0) - (-(r - r0) * a).exp();
+        s * s * d
+    };
+    let morse_f64 = |r: f64| {
+        let s = 1.0 - (-(r - r0) * a).exp();
+        s * s * d
+    };
+    check("morse", morse, morse_f64, 1.3);
+}
+
+#[test]
+fn test_dual_subgradients_at_kinks() {
+    let zero = Dual::variable(0.0);
+    assert_eq!(zero.abs().der, 0.0);
+
+    let a = Dual::new(1.0, 2.0);
+    let b = Dual::new(1.0, 4.0);
+    assert_eq!(a.max(b).der, 3.0);
+    assert_eq!(a.min(b).der, 3.0);
+
+    let c = Dual::new(2.0, -1.0);
+    assert_eq!(a.max(c), c);
+    assert_eq!(a.min(c), a);
+}
@@ -0,0 +1,107 @@
+use moo::core::math::ad::Dual;
+use moo::core::math::multi::ChunkDual;
+use moo::core::math::real::Real;
+use moo::core::math::tape::{Tape, Var};
+use moo::core::state::PhaseSpace;
+use moo::laws::classical::{Gravity, Spring};
+use moo::laws::continuum::SPH;
+use moo::laws::registry::{GradientMode, Law, LawRegistry};
+
+fn scattered_state(n: usize) -> PhaseSpace {
+    let mut state = PhaseSpace::new(n * 3);
+    for i in 0..n {
+        let t = i as f64;
+        state.q[i * 3] = (1.1 * t).sin() * (1.0 + 0.3 * t);
+        state.q[i * 3 + 1] = (0.9 * t).cos() * (1.0 + 0.2 * t);
+        state.q[i * 3 + 2] = 0.1 * t * t;
+        state.set_particle_mass(i, 2.0 + t);
+    }
+    state
+}
+
+#[test]
+fn test_tape_records_elementary_functions() {
+    let tape = Tape::new();
+    let x = tape.var(0.8);
+    let y = tape.var(-1.7);
+
+    // f = sin(x) * exp(y) + atan2(y, x) / x
+    let f = x.sin() * y.exp() + y.atan2(x) / x;
+    let adj = f.backward();
+
+    let dfdx = 0.8_f64.cos() * (-1.7_f64).exp() + (-(-1.7) / (0.8 * 0.8 + 1.7 * 1.7)) / 0.8
+        - (-1.7_f64).atan2(0.8) / (0.8 * 0.8);
+    let dfdy = 0.8_f64.sin() * (-1.7_f64).exp() + (0.8 / (0.8 * 0.8 + 1.7 * 1.7)) / 0.8;
+
+    assert!((adj.wrt(x) - dfdx).abs() < 1e-12);
+    assert!((adj.wrt(y) - dfdy).abs() < 1e-12);
+    assert_eq!(adj.wrt(Var::constant(3.0)), 0.0);
+}
+
+#[test]
+fn test_reverse_gradient_matches_forward() {
+    let state = scattered_state(9);
+
+    let mut registry = LawRegistry::new();
+    registry.add(Gravity::new(2.0));
+    registry.add(Spring::new(3.0, 0.7, 1, 4));
+    registry.add(SPH::new(2.0, 1.0, 5.0));
+
+    let mut forward = vec![0.0; state.dof];
+    let mut reverse = vec![0.0; state.dof];
+
+    registry.set_gradient_mode(GradientMode::Forward);
+    registry.gradient(&state.q, &state.mass, &mut forward);
+    registry.set_gradient_mode(GradientMode::Reverse);
+    registry.gradient(&state.q, &state.mass, &mut reverse);
+
+    for (i, (f, r)) in forward.iter().zip(&reverse).enumerate() {
+        assert!(
+            (f - r).abs() < 1e-10 * (1.0 + f.abs()),
+            "DOF {i}: forward {f} vs reverse {r}"
+        );
+    }
+}
+
+/// A user law written once against `Real`, evaluated with every scalar type.
+struct Anharmonic;
+
+impl Law for Anharmonic {
+    fn potential<T: Real>(&self, q: &[T], _mass: &[f64]) -> T {
+        q.iter()
+            .fold(T::constant(0.0), |acc, &x| acc + x.powi(4) * 0.25)
+    }
+}
+
+#[test]
+fn test_law_instantiated_for_each_scalar() {
+    let q: Vec<f64> = (0..11).map(|i| 0.3 * i as f64 - 1.0).collect();
+    let mass = vec![1.0; q.len()];
+    let expected_v: f64 = q.iter().map(|x| 0.25 * x.powi(4)).sum();
+
+    let mut registry = LawRegistry::new();
+    registry.add(Anharmonic);
+
+    // Energy only
+    assert!((registry.potential(&q, &mass) - expected_v).abs() < 1e-12);
+
+    // Forward, single tangent
+    let mut dual: Vec<Dual> = q.iter().map(|&x| Dual::constant(x)).collect();
+    dual[2].der = 1.0;
+    assert!((registry.potential(&dual, &mass).der - q[2].powi(3)).abs() < 1e-12);
+
+    // Forward, chunked
+    let chunk: Vec<ChunkDual> = q.iter().map(|&x| ChunkDual::constant(x)).collect();
+    assert!((Anharmonic.potential(&chunk, &mass).val - expected_v).abs() < 1e-12);
+
+    // Reverse
+    let tape = Tape::new();
+    let inputs: Vec<Var> = q.iter().map(|&x| tape.var(x)).collect();
+    let v = registry.potential(&inputs, &mass);
+    let adj = v.backward();
+
+    assert!((v.val - expected_v).abs() < 1e-12);
+    for (x, &xv) in inputs.iter().zip(&q) {
+        assert!((adj.wrt(*x) - xv.powi(3)).abs() < 1e-12);
+    }
+}
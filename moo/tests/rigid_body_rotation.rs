@@ -1,7 +1,40 @@
-use glam::DVec3;
-use moo::core::solve::{Integrator, VelocityVerlet};
+use glam::{DQuat, DVec3};
+use moo::core::math::Real;
+use moo::core::solve::{
+    Composition, Integrator, LieGroupVariational, RungeKutta4, SymplecticEuler, VelocityVerlet,
+};
 use moo::core::state::PhaseSpace;
-use moo::laws::registry::LawRegistry;
+use moo::investigation::probe::{EnergyProbe, Probe};
+use moo::laws::registry::{Law, LawRegistry};
+
+/// Uniform gravity `-g ẑ` on a body of mass `m` whose centre of mass sits at `offset`
+/// (body frame) from a fixed pivot: `V = m g ẑ · R offset`. A heavy top.
+struct Pivot {
+    m: f64,
+    g: f64,
+    offset: DVec3,
+}
+
+impl Law for Pivot {
+    fn potential<T: Real>(&self, _q: &[T], _mass: &[f64]) -> T {
+        T::constant(0.0)
+    }
+
+    fn orientation_potential<T: Real>(&self, rot: &[[T; 9]]) -> T {
+        // Third row of R times the offset.
+        let r = &rot[0];
+        (r[6] * self.offset.x + r[7] * self.offset.y + r[8] * self.offset.z) * (self.m * self.g)
+    }
+}
+
+fn rotational_energy(s: &PhaseSpace) -> f64 {
+    0.5 * s.ang_v[0].dot(s.ang_v[0] * s.inertia[0])
+}
+
+/// `R I ω`.
+fn spatial_momentum(s: &PhaseSpace) -> DVec3 {
+    s.rot[0] * (s.ang_v[0] * s.inertia[0])
+}
 
 #[test]
 fn test_rigid_body_energy_conservation() {
@@ -56,3 +89,117 @@ fn test_rigid_body_energy_conservation() {
         "Rotational energy drift too high! Stability issues?"
     );
 }
+
+#[test]
+fn test_lie_group_variational_dzhanibekov() {
+    // Same tumbling brick, with a step ten times larger than above.
+    let mut state = PhaseSpace::new(0);
+    state.resize_rigid(1);
+    state.inertia[0] = DVec3::new(1.0, 2.0, 3.0);
+    state.ang_v[0] = DVec3::new(0.1, 5.0, 0.1);
+    let registry = LawRegistry::new();
+    let (e0, l0) = (rotational_energy(&state), spatial_momentum(&state));
+
+    let mut solver = LieGroupVariational::default();
+    let mut flipped = false;
+    for _ in 0..5000 {
+        solver.step(&mut state, &registry, &[], 0.01);
+        assert!(solver.converged);
+        flipped |= state.ang_v[0].y < -4.5;
+        assert!((spatial_momentum(&state) - l0).length() < 1e-12 * l0.length());
+    }
+    assert!(flipped, "the spin axis should tumble");
+    assert!((state.rot[0].length() - 1.0).abs() < 1e-14);
+    let error = (rotational_energy(&state) - e0).abs() / e0;
+    assert!(error < 1e-12, "energy error {error}");
+}
+
+#[test]
+fn test_orientation_potential_torque() {
+    let top = Pivot {
+        m: 2.0,
+        g: 9.81,
+        offset: DVec3::new(0.1, 0.2, 0.5),
+    };
+    let rot = DQuat::from_scaled_axis(DVec3::new(0.3, -0.7, 0.2));
+    let mut laws = LawRegistry::new();
+    laws.add(top);
+    let mut torque = [DVec3::ZERO];
+    laws.torques(&[rot], &mut torque);
+
+    // τ = m g (Rᵀ ẑ) × offset, the moment of the weight about the pivot in the body frame.
+    let expected = 2.0 * 9.81 * (rot.inverse() * DVec3::Z).cross(DVec3::new(0.1, 0.2, 0.5));
+    assert!((torque[0] - expected).length() < 1e-12, "{torque:?}");
+    let v = laws.orientation_potential(&[rot]);
+    assert!((v - 2.0 * 9.81 * (rot * DVec3::new(0.1, 0.2, 0.5)).z).abs() < 1e-12);
+}
+
+#[test]
+fn test_lie_group_variational_heavy_top() {
+    let mut state = PhaseSpace::new(0);
+    state.resize_rigid(1);
+    state.inertia[0] = DVec3::new(1.0, 1.5, 0.8);
+    state.rot[0] = DQuat::from_rotation_x(0.4);
+    state.ang_v[0] = DVec3::new(0.3, -0.5, 8.0);
+    let mut laws = LawRegistry::new();
+    laws.add(Pivot {
+        m: 1.0,
+        g: 9.81,
+        offset: DVec3::new(0.05, 0.0, 0.3),
+    });
+    let e0 = EnergyProbe.measure(&state, &laws);
+    let lz0 = spatial_momentum(&state).z;
+
+    let mut solver = LieGroupVariational::default();
+    let mut worst = 0.0f64;
+    for _ in 0..20000 {
+        solver.step(&mut state, &laws, &[], 0.005);
+        assert!(solver.converged);
+        // Gravity is symmetric about the vertical: L_z is a momentum map.
+        assert!((spatial_momentum(&state).z - lz0).abs() < 1e-10);
+        worst = worst.max((EnergyProbe.measure(&state, &laws) - e0).abs());
+    }
+    assert!(worst < 1e-3 * e0.abs(), "energy error {worst}");
+}
+
+#[test]
+fn test_integrators_apply_orientation_torques() {
+    let top = || {
+        let mut state = PhaseSpace::new(0);
+        state.resize_rigid(1);
+        state.inertia[0] = DVec3::new(1.0, 1.5, 0.8);
+        state.rot[0] = DQuat::from_rotation_x(0.4);
+        state.ang_v[0] = DVec3::new(0.3, -0.5, 2.0);
+        let mut laws = LawRegistry::new();
+        laws.add(Pivot {
+            m: 1.0,
+            g: 9.81,
+            offset: DVec3::new(0.05, 0.0, 0.3),
+        });
+        (state, laws)
+    };
+    let run = |solver: &mut dyn Integrator, dt: f64| {
+        let (mut state, laws) = top();
+        for _ in 0..(1.0 / dt).round() as usize {
+            solver.step(&mut state, &laws, &[], dt);
+        }
+        state
+    };
+
+    // Without the torques the orientations would be off by O(1) after one second.
+    let reference = run(&mut LieGroupVariational::default(), 1e-4);
+    let solvers: [(&str, Box<dyn Integrator>, f64); 4] = [
+        ("symplectic euler", Box::new(SymplecticEuler), 1e-2),
+        ("velocity verlet", Box::new(VelocityVerlet), 1e-2),
+        ("yoshida4", Box::new(Composition::yoshida4()), 1e-2),
+        ("rk4", Box::new(RungeKutta4), 1e-5),
+    ];
+    for (name, mut solver, tolerance) in solvers {
+        let state = run(solver.as_mut(), 1e-3);
+        let angle = (state.rot[0].inverse() * reference.rot[0])
+            .to_scaled_axis()
+            .length();
+        let dw = (state.ang_v[0] - reference.ang_v[0]).length();
+        assert!(angle < tolerance && dw < tolerance, "{name}: {angle}, {dw}");
+    }
+}
@@ -0,0 +1,110 @@
+mod common;
+
+use common::{Harmonic, energy_error, kepler};
+use glam::DVec3;
+use moo::core::solve::{DormandPrince, Integrator, RungeKutta4, VelocityVerlet};
+use moo::core::state::PhaseSpace;
+use moo::laws::registry::LawRegistry;
+use std::f64::consts::PI;
+
+#[test]
+fn test_rk4_is_fourth_order() {
+    let error = |steps: usize| {
+        let mut state = PhaseSpace::new(1);
+        state.q[0] = 1.0;
+        let mut laws = LawRegistry::new();
+        laws.add(Harmonic(1.0));
+        let dt = 2.0 / steps as f64;
+        for _ in 0..steps {
+            RungeKutta4.step(&mut state, &laws, &[], dt);
+        }
+        assert!((state.t - 2.0).abs() < 1e-12);
+        (state.q[0] - 2f64.cos()).abs()
+    };
+    let ratio = error(20) / error(40);
+    assert!((14.0..18.0).contains(&ratio), "ratio {ratio}");
+}
+
+#[test]
+fn test_rk4_energy_drifts_where_verlet_does_not() {
+    const ORBITS: usize = 400;
+    let rk4 = energy_error(&mut RungeKutta4, ORBITS, 200);
+    let verlet = energy_error(&mut VelocityVerlet, ORBITS, 200);
+    // RK4 loses energy every orbit; Verlet's worst error per orbit stays put.
+    assert!(rk4.windows(2).all(|w| w[1] > w[0]));
+    assert!(verlet.iter().all(|&e| e < 1.1 * verlet[0]), "{verlet:?}");
+    // RK4 starts out far more accurate, and still falls behind on a long run.
+    assert!(rk4[0] < verlet[0] / 100.0);
+    assert!(rk4[ORBITS - 1] > verlet[ORBITS - 1]);
+}
+
+#[test]
+fn test_dormand_prince_meets_tolerance() {
+    let period = 2.0 * PI;
+    let run = |rtol: f64| {
+        let (mut state, laws) = kepler(0.9);
+        let start = state.position(0);
+        // One call per orbit: the integrator picks its own substeps.
+        let mut solver = DormandPrince::new(rtol, rtol * 1e-3);
+        solver.step(&mut state, &laws, &[], period);
+        assert_eq!(state.t, period);
+        ((state.position(0) - start).length(), solver)
+    };
+
+    let (coarse_error, coarse) = run(1e-6);
+    let (fine_error, fine) = run(1e-10);
+    assert!(fine_error < 1e-6, "{fine_error}");
+    assert!(fine_error < coarse_error);
+    assert!(fine.accepted > coarse.accepted);
+    // The first attempt spans the whole orbit and must be thrown away.
+    assert!(coarse.rejected > 0);
+    assert!(coarse.h.unwrap() < period);
+}
+
+#[test]
+fn test_dormand_prince_rigid_body() {
+    // Torque-free spin about the unstable middle axis.
+    let mut state = PhaseSpace::new(3);
+    state.resize_rigid(1);
+    state.inertia[0] = DVec3::new(1.0, 2.0, 3.0);
+    state.ang_v[0] = DVec3::new(1e-3, 1.0, 1e-3);
+    let kinetic = |s: &PhaseSpace| 0.5 * s.ang_v[0].dot(s.ang_v[0] * s.inertia[0]);
+    let spatial = |s: &PhaseSpace| s.rot[0] * (s.ang_v[0] * s.inertia[0]);
+    let momentum = |s: &PhaseSpace| spatial(s).length();
+    let (t0, l0, spatial0) = (kinetic(&state), momentum(&state), spatial(&state));
+
+    let laws = LawRegistry::new();
+    let mut solver = DormandPrince::new(1e-10, 1e-12);
+    let mut flipped = false;
+    for _ in 0..200 {
+        solver.step(&mut state, &laws, &[], 0.1);
+        flipped |= state.ang_v[0].y < -0.9;
+    }
+    assert!(flipped, "the spin axis should tumble");
+    assert!((kinetic(&state) - t0).abs() < 1e-8);
+    assert!((momentum(&state) - l0).abs() < 1e-6);
+    // The direction of R I ω depends on the orientation as well.
+    assert!((spatial(&state) - spatial0).length() < 1e-6);
+}
+
+#[test]
+fn test_rk4_orientation_is_fourth_order() {
+    // Torque-free tumbling brick: the orientation error must converge like the rest.
+    let run = |steps: usize| {
+        let mut state = PhaseSpace::new(0);
+        state.resize_rigid(1);
+        state.inertia[0] = DVec3::new(1.0, 2.0, 3.0);
+        state.ang_v[0] = DVec3::new(0.4, 1.0, 0.3);
+        let laws = LawRegistry::new();
+        let dt = 2.0 / steps as f64;
+        for _ in 0..steps {
+            RungeKutta4.step(&mut state, &laws, &[], dt);
+        }
+        state.rot[0]
+    };
+    let reference = run(2000);
+    // Twice the vector part of the relative rotation: its angle, without acos round-off.
+    let error = |steps| 2.0 * (run(steps).inverse() * reference).xyz().length();
+    let ratio = error(20) / error(40);
+    assert!((14.0..18.0).contains(&ratio), "ratio {ratio}");
+}
@@ -0,0 +1,82 @@
+use glam::DVec3;
+use moo::core::geometry::{Manifold, S2};
+use moo::core::solve::{Integrator, SymplecticEuler};
+use moo::core::state::{Block, PhaseSpace};
+use moo::laws::registry::LawRegistry;
+use std::f64::consts::PI;
+
+#[test]
+fn test_retract_stays_on_sphere() {
+    let p = DVec3::new(1.0, 2.0, -0.5).normalize();
+    for v in [
+        DVec3::new(0.3, -0.1, 0.4),
+        DVec3::new(10.0, 3.0, -7.0), // Several times around
+        DVec3::new(1e-12, 0.0, 0.0),
+        p * 5.0, // Purely normal: no motion
+    ] {
+        let q = S2::retract(p, v);
+        assert!((q.length() - 1.0).abs() < 1e-14, "|q| = {}", q.length());
+    }
+    assert!((S2::retract(p, p * 5.0) - p).length() < 1e-14);
+}
+
+#[test]
+fn test_project_removes_normal_component() {
+    let p = DVec3::new(0.0, 0.6, 0.8);
+    let v = S2::project(p, DVec3::new(1.0, 2.0, 3.0));
+    assert!(v.dot(p).abs() < 1e-14);
+    assert!((v.x - 1.0).abs() < 1e-14);
+}
+
+#[test]
+fn test_log_inverts_retract() {
+    let p = DVec3::new(-0.3, 0.2, 0.9).normalize();
+    let v = S2::project(p, DVec3::new(0.7, 1.1, 0.0));
+    assert!(v.length() < PI);
+
+    let q = S2::retract(p, v);
+    assert!((S2::local(p, q) - v).length() < 1e-12);
+    assert!((S2::distance(p, q) - v.length()).abs() < 1e-12);
+
+    assert!(S2::local(p, p).length() < 1e-15);
+    let antipodal = S2::local(p, -p);
+    assert!((antipodal.length() - PI).abs() < 1e-12 && antipodal.dot(p).abs() < 1e-12);
+}
+
+#[test]
+fn test_transport_follows_geodesic_velocity() {
+    let p = DVec3::X;
+    let v = DVec3::new(0.0, 0.5, 0.0);
+
+    // The great circle in the xy-plane: after angle 0.5 the velocity is (-sin, cos) * 0.5.
+    let w = S2::transport(p, v, v);
+    assert!((w - DVec3::new(-0.5f64.sin(), 0.5f64.cos(), 0.0) * 0.5).length() < 1e-14);
+
+    // A vector normal to the plane of motion is unchanged.
+    assert_eq!(S2::transport(p, v, DVec3::Z), DVec3::Z);
+}
+
+#[test]
+fn test_directors_integrate_on_great_circle() {
+    let mut state = PhaseSpace::new(0);
+    state.resize_directors(1);
+    assert_eq!(state.blocks, vec![Block::Director { start: 0, count: 1 }]);
+
+    let speed = 2.0;
+    state.dir[0] = DVec3::X;
+    state.dir_v[0] = DVec3::Y * speed;
+
+    let laws = LawRegistry::new();
+    let dt = 0.01;
+    let steps = 1000;
+    for _ in 0..steps {
+        SymplecticEuler.step(&mut state, &laws, &[], dt);
+        assert!((state.dir[0].length() - 1.0).abs() < 1e-12);
+        assert!(state.dir[0].dot(state.dir_v[0]).abs() < 1e-10);
+    }
+
+    let angle = speed * dt * steps as f64;
+    let expected = DVec3::new(angle.cos(), angle.sin(), 0.0);
+    assert!((state.dir[0] - expected).length() < 1e-9);
+    assert!((state.dir_v[0].length() - speed).abs() < 1e-9);
+}
@@ -0,0 +1,187 @@
+mod common;
+
+use common::UniformGravity;
+use glam::{DQuat, DVec3};
+use moo::core::geometry::Pose;
+use moo::core::geometry::shapes::{Aabb, Sdf, TriMesh, VoxelSdf};
+use moo::core::solve::constraints::{Constraint, SdfConstraint};
+use moo::core::solve::{Integrator, VelocityVerlet};
+use moo::core::state::PhaseSpace;
+use moo::laws::registry::LawRegistry;
+
+/// Closed cube `[-1, 1]³`, outward-wound.
+const CUBE_OBJ: &str = "\
+v -1 -1 -1
+v  1 -1 -1
+v  1  1 -1
+v -1  1 -1
+v -1 -1  1
+v  1 -1  1
+v  1  1  1
+v -1  1  1
+f 1 4 3 2
+f 5 6 7 8
+f 1 2 6 5
+f 2 3 7 6
+f 3 4 8 7
+f 4 1 5 8
+";
+
+#[test]
+fn test_primitive_distances() {
+    let p = DVec3::new(3.0, 0.0, 0.0);
+    assert_eq!(Sdf::sphere(1.0).distance(p), 2.0);
+    assert_eq!(
+        Sdf::cuboid(DVec3::ONE).distance(DVec3::new(3.0, 3.0, 0.0)),
+        8f64.sqrt()
+    );
+    assert_eq!(
+        Sdf::cuboid(DVec3::ONE).distance(DVec3::new(0.5, 0.0, 0.0)),
+        -0.5
+    );
+    assert_eq!(
+        Sdf::capsule(1.0, 0.5).distance(DVec3::new(0.0, 3.0, 0.0)),
+        1.5
+    );
+    assert_eq!(
+        Sdf::cylinder(1.0, 0.5).distance(DVec3::new(0.0, 0.0, 2.0)),
+        1.5
+    );
+    assert_eq!(
+        Sdf::half_space(DVec3::Y * 2.0, -1.0).distance(DVec3::ZERO),
+        1.0
+    );
+}
+
+#[test]
+fn test_csg_and_transforms() {
+    let a = Sdf::sphere(1.0);
+    let b = Sdf::sphere(1.0).transformed(Pose::new(DQuat::IDENTITY, DVec3::X * 1.5));
+    let p = DVec3::new(0.75, 0.0, 0.0);
+
+    assert_eq!(a.clone().union(b.clone()).distance(p), -0.25);
+    assert_eq!(a.clone().intersection(b.clone()).distance(p), -0.25);
+    assert_eq!(a.clone().difference(b.clone()).distance(p), 0.25);
+    assert_eq!(a.clone().inverted().distance(DVec3::ZERO), 1.0);
+    // Blending only ever adds material.
+    assert!(a.clone().smooth_union(b.clone(), 0.5).distance(p) < -0.25);
+    assert_eq!(
+        a.clone().smooth_union(b, 0.5).distance(DVec3::X * -3.0),
+        2.0
+    );
+
+    let rotated = Sdf::cuboid(DVec3::new(2.0, 0.5, 0.5)).transformed(Pose::new(
+        DQuat::from_rotation_z(std::f64::consts::FRAC_PI_2),
+        DVec3::ZERO,
+    ));
+    assert!((rotated.distance(DVec3::new(0.0, 3.0, 0.0)) - 1.0).abs() < 1e-12);
+
+    let n = rotated.gradient(DVec3::new(0.0, 3.0, 0.0));
+    assert!((n - DVec3::Y).length() < 1e-6);
+}
+
+#[test]
+fn test_voxel_bake_interpolates() {
+    // A plane is linear, so trilinear interpolation reproduces it inside the grid.
+    let ramp = Sdf::half_space(DVec3::new(1.0, 2.0, 0.5), 0.3);
+    let bounds = Aabb::new(DVec3::splat(-2.0), DVec3::splat(2.0));
+    let grid = VoxelSdf::bake(&ramp, bounds, 0.25);
+    assert_eq!(grid.dims, [17, 17, 17]);
+    for p in [DVec3::new(0.1, -0.7, 1.3), DVec3::new(-1.9, 1.99, 0.0)] {
+        assert!((grid.distance(p) - ramp.distance(p)).abs() < 1e-5);
+    }
+    // Outside the grid the box distance is added on.
+    let outside = DVec3::new(0.0, 0.0, 5.0);
+    let edge = DVec3::new(0.0, 0.0, 2.0);
+    assert!((grid.distance(outside) - grid.distance(edge) - 3.0).abs() < 1e-12);
+
+    let ball = Sdf::sphere(1.0);
+    let coarse = Sdf::Grid(VoxelSdf::bake(&ball, bounds, 0.1));
+    let p = DVec3::new(0.3, 0.9, -0.2);
+    assert!((coarse.distance(p) - ball.distance(p)).abs() < 1e-2);
+    assert!((coarse.gradient(p) - p.normalize()).length() < 0.1);
+}
+
+#[test]
+fn test_voxel_floor_extends_past_the_grid() {
+    // The GPU's default boundary: a floor baked over a finite box.
+    let floor = Sdf::half_space(DVec3::Y, -200.0);
+    let bounds = Aabb::new(
+        DVec3::new(-1000.0, -400.0, -25.0),
+        DVec3::new(1000.0, 1200.0, 25.0),
+    );
+    let grid = VoxelSdf::bake(&floor, bounds, 25.0);
+    // Below the floor the plane is extrapolated exactly, however far out.
+    for p in [
+        DVec3::new(1100.0, -250.0, 0.0),
+        DVec3::new(0.0, -500.0, 0.0),
+        DVec3::new(-3000.0, -210.0, 60.0),
+    ] {
+        assert!((grid.distance(p) - floor.distance(p)).abs() < 1e-3, "{p}");
+    }
+    // Above it the box distance is added on, which keeps the sign.
+    assert!(grid.distance(DVec3::new(1100.0, -150.0, 0.0)) >= 50.0 - 1e-3);
+}
+
+#[test]
+fn test_mesh_bake_has_sign() {
+    let cube = TriMesh::from_obj(CUBE_OBJ).unwrap();
+    let grid = VoxelSdf::from_mesh(&cube, 0.25, 0.5);
+    let exact = Sdf::cuboid(DVec3::ONE);
+
+    assert!(grid.bounds().min.abs_diff_eq(DVec3::splat(-1.5), 1e-12));
+    for p in [
+        DVec3::ZERO,
+        DVec3::new(0.5, 0.5, -0.25),
+        DVec3::new(1.25, 0.0, 0.0),
+        DVec3::new(1.25, 1.25, 1.25),
+    ] {
+        assert!(
+            (grid.distance(p) - exact.distance(p)).abs() < 0.05,
+            "{p}: {} vs {}",
+            grid.distance(p),
+            exact.distance(p)
+        );
+    }
+
+    // Flipping the winding keeps the sign.
+    let mut flipped = cube.clone();
+    flipped.triangles.iter_mut().for_each(|t| t.swap(1, 2));
+    let flipped = TriMesh::new(flipped.vertices, flipped.triangles);
+    assert_eq!(VoxelSdf::from_mesh(&flipped, 0.25, 0.5), grid);
+}
+
+#[test]
+fn test_particles_stay_in_sdf_bowl() {
+    // A hemispherical bowl: the solid around a ball, below y = 0.
+    let bowl = Sdf::sphere(2.0)
+        .inverted()
+        .intersection(Sdf::half_space(DVec3::Y, 0.0));
+    let constraints: Vec<Box<dyn Constraint>> = vec![Box::new(SdfConstraint::new(bowl, 0.3, 0.05))];
+
+    let n = 6;
+    let mut state = PhaseSpace::new(n * 3);
+    for i in 0..n {
+        let t = i as f64;
+        state.radius[i] = 0.1;
+        state.set_position(i, DVec3::new((t * 0.9).sin(), -0.5, (t * 1.7).cos()));
+        state.set_velocity(
+            i,
+            DVec3::new(2.0 * (t * 2.3).cos(), 0.0, 2.0 * (t * 0.4).sin()),
+        );
+    }
+
+    let mut laws = LawRegistry::new();
+    laws.add(UniformGravity(9.81));
+    for _ in 0..3000 {
+        VelocityVerlet.step(&mut state, &laws, &constraints, 0.002);
+        for i in 0..n {
+            let p = state.position(i);
+            assert!(p.length() < 1.9 + 1e-6, "left the bowl: {p}");
+        }
+    }
+    // Friction drains the sloshing: everything gathers near the bottom.
+    for i in 0..n {
+        assert!(state.position(i).y < -1.5);
+    }
+}
@@ -0,0 +1,99 @@
+use glam::{DQuat, DVec3};
+use moo::core::geometry::{Manifold, Pose, SE3, Twist};
+use moo::core::state::PhaseSpace;
+
+fn assert_pose_eq(a: Pose, b: Pose, tol: f64) {
+    let same_rot = a.rot.dot(b.rot).abs() > 1.0 - tol;
+    assert!(same_rot, "rotations differ: {:?} vs {:?}", a.rot, b.rot);
+    assert!(
+        (a.trans - b.trans).length() < tol,
+        "translations differ: {:?} vs {:?}",
+        a.trans,
+        b.trans
+    );
+}
+
+fn assert_twist_eq(a: Twist, b: Twist, tol: f64) {
+    assert!((a.omega - b.omega).length() < tol, "{a:?} vs {b:?}");
+    assert!((a.v - b.v).length() < tol, "{a:?} vs {b:?}");
+}
+
+#[test]
+fn test_exp_log_roundtrip() {
+    let twists = [
+        Twist::new(DVec3::new(0.3, -1.2, 0.7), DVec3::new(1.0, 2.0, -0.5)),
+        Twist::new(DVec3::new(1e-7, 2e-7, -1e-7), DVec3::new(0.2, 0.0, 3.0)),
+        Twist::new(DVec3::ZERO, DVec3::new(-4.0, 1.0, 0.5)),
+        Twist::new(DVec3::new(0.0, 0.0, 3.0), DVec3::new(0.1, 0.2, 0.3)),
+    ];
+    for xi in twists {
+        assert_twist_eq(SE3::log(SE3::exp(xi)), xi, 1e-10);
+    }
+}
+
+#[test]
+fn test_exp_screw_motion() {
+    // Quarter turn about z while moving along x: the V matrix bends the path into an arc.
+    let theta = std::f64::consts::FRAC_PI_2;
+    let pose = SE3::exp(Twist::new(DVec3::new(0.0, 0.0, theta), DVec3::X));
+
+    let expected = DVec3::new(theta.sin() / theta, (1.0 - theta.cos()) / theta, 0.0);
+    assert!((pose.trans - expected).length() < 1e-12);
+    assert!((pose.rot * DVec3::X - DVec3::Y).length() < 1e-12);
+}
+
+#[test]
+fn test_retract_local_and_composition() {
+    let p = Pose::new(
+        DQuat::from_scaled_axis(DVec3::new(0.4, 0.1, -0.9)),
+        DVec3::new(1.0, -2.0, 0.5),
+    );
+    let xi = Twist::new(DVec3::new(-0.2, 0.5, 0.3), DVec3::new(0.7, 0.1, -1.1));
+
+    let q = SE3::retract(p, xi);
+    assert_twist_eq(SE3::local(p, q), xi, 1e-10);
+
+    assert_pose_eq(p.compose(p.inverse()), Pose::IDENTITY, 1e-12);
+
+    let x = DVec3::new(0.3, 0.6, -0.2);
+    let composed = (p * q).transform_point(x);
+    assert!((composed - p.transform_point(q.transform_point(x))).length() < 1e-12);
+}
+
+#[test]
+fn test_adjoint_conjugation() {
+    let t = Pose::new(
+        DQuat::from_scaled_axis(DVec3::new(1.1, -0.3, 0.4)),
+        DVec3::new(-0.5, 2.0, 1.5),
+    );
+    let xi = Twist::new(DVec3::new(0.2, 0.1, -0.6), DVec3::new(0.9, -0.4, 0.3));
+
+    // T exp(ξ) T⁻¹ = exp(Ad_T ξ)
+    let lhs = t * SE3::exp(xi) * t.inverse();
+    let rhs = SE3::exp(SE3::adjoint(t, xi));
+    assert_pose_eq(lhs, rhs, 1e-10);
+
+    // Matrix form agrees with the action.
+    let m = SE3::adjoint_matrix(t);
+    let a = xi.to_array();
+    let mut out = [0.0; 6];
+    for (row, o) in out.iter_mut().enumerate() {
+        *o = (0..6).map(|col| m[row][col] * a[col]).sum();
+    }
+    assert_twist_eq(Twist::from_array(out), SE3::adjoint(t, xi), 1e-12);
+}
+
+#[test]
+fn test_phase_space_pose_roundtrip() {
+    let mut state = PhaseSpace::new(6);
+    state.resize_rigid(2);
+
+    let pose = SE3::exp(Twist::new(
+        DVec3::new(0.1, 0.2, 0.3),
+        DVec3::new(4.0, 5.0, 6.0),
+    ));
+    state.set_rigid_pose(1, pose);
+
+    assert_eq!(state.rigid_pose(1), pose);
+    assert_eq!(state.rigid_pose(0), Pose::IDENTITY);
+}
@@ -0,0 +1,165 @@
+mod common;
+
+use common::UniformGravity;
+use glam::{DQuat, DVec3};
+use moo::core::geometry::Pose;
+use moo::core::geometry::shapes::{
+    Capsule, ConvexHull, Cuboid, Cylinder, Plane, Shape, Sphere, closest_points, contact,
+};
+use moo::core::solve::constraints::{ColliderConstraint, Constraint};
+use moo::core::solve::{Integrator, VelocityVerlet};
+use moo::core::state::PhaseSpace;
+use moo::laws::registry::LawRegistry;
+
+fn at(x: f64, y: f64, z: f64) -> Pose {
+    Pose::new(DQuat::IDENTITY, DVec3::new(x, y, z))
+}
+
+fn unit_cube_hull() -> ConvexHull {
+    let mut points = Vec::new();
+    for x in [-1.0, 1.0] {
+        for y in [-1.0, 1.0] {
+            for z in [-1.0, 1.0] {
+                points.push(DVec3::new(x, y, z));
+            }
+        }
+    }
+    ConvexHull::new(points)
+}
+
+#[test]
+fn test_sphere_sphere() {
+    let a = Shape::Sphere(Sphere::new(1.0));
+    let b = Shape::Sphere(Sphere::new(0.5));
+
+    assert!(contact(&a, at(0.0, 0.0, 0.0), &b, at(2.0, 0.0, 0.0)).is_none());
+
+    let c = contact(&a, at(0.0, 0.0, 0.0), &b, at(0.0, 1.2, 0.0)).unwrap();
+    assert!((c.depth - 0.3).abs() < 1e-12);
+    assert!((c.normal - DVec3::Y).length() < 1e-12);
+    assert!((c.point_a - DVec3::new(0.0, 1.0, 0.0)).length() < 1e-12);
+    assert!((c.point_b - DVec3::new(0.0, 0.7, 0.0)).length() < 1e-12);
+}
+
+#[test]
+fn test_closest_points_box_sphere() {
+    let cube = Cuboid::new(DVec3::ONE);
+    let sphere = Sphere::new(0.5);
+    let prox = closest_points(&cube, at(0.0, 0.0, 0.0), &sphere, at(3.0, 3.0, 0.0));
+
+    let expected = (DVec3::new(2.0, 2.0, 0.0)).length() - 0.5;
+    assert!((prox.distance - expected).abs() < 1e-9, "{prox:?}");
+    assert!((prox.point_a - DVec3::new(1.0, 1.0, prox.point_a.z)).length() < 1e-9);
+}
+
+#[test]
+fn test_box_box_penetration() {
+    let cube = Shape::Cuboid(Cuboid::new(DVec3::ONE));
+    let c = contact(&cube, at(0.0, 0.0, 0.0), &cube, at(1.7, 0.2, -0.1)).unwrap();
+    assert!((c.depth - 0.3).abs() < 1e-9, "{c:?}");
+    assert!((c.normal - DVec3::X).length() < 1e-9);
+    assert!((c.point_a - c.point_b - c.normal * c.depth).length() < 1e-9);
+}
+
+#[test]
+fn test_rotated_box_corner() {
+    // A cube turned 45° about z pokes a corner out to x = √2.
+    let cube = Shape::Cuboid(Cuboid::new(DVec3::ONE));
+    let turned = Pose::new(
+        DQuat::from_rotation_z(std::f64::consts::FRAC_PI_4),
+        DVec3::ZERO,
+    );
+    let c = contact(&cube, turned, &cube, at(2.2, 0.0, 0.0)).unwrap();
+
+    assert!((c.depth - (2f64.sqrt() - 1.2)).abs() < 1e-9, "{c:?}");
+    assert!((c.normal - DVec3::X).length() < 1e-9);
+}
+
+#[test]
+fn test_hull_matches_cuboid() {
+    let hull = Shape::ConvexHull(unit_cube_hull());
+    let cube = Shape::Cuboid(Cuboid::new(DVec3::ONE));
+    let sphere = Shape::Sphere(Sphere::new(1.0));
+    let pose = at(0.4, 1.5, 0.3);
+
+    let a = contact(&hull, Pose::IDENTITY, &sphere, pose).unwrap();
+    let b = contact(&cube, Pose::IDENTITY, &sphere, pose).unwrap();
+    assert!((a.depth - 0.5).abs() < 1e-9 && (a.depth - b.depth).abs() < 1e-9);
+    assert!((a.normal - b.normal).length() < 1e-9);
+}
+
+#[test]
+fn test_deep_sphere_in_box() {
+    // The sphere center is inside the box, so GJK reports overlapping cores and EPA runs.
+    let cube = Shape::Cuboid(Cuboid::new(DVec3::ONE));
+    let sphere = Shape::Sphere(Sphere::new(0.5));
+    let c = contact(&cube, Pose::IDENTITY, &sphere, at(0.0, 0.0, 0.8)).unwrap();
+
+    // Sphere support is curved, so EPA converges to a polytope approximation.
+    assert!((c.depth - 0.7).abs() < 1e-3, "{c:?}");
+    assert!((c.normal - DVec3::Z).length() < 1e-2);
+}
+
+#[test]
+fn test_capsule_and_cylinder_on_plane() {
+    let ground = Shape::Plane(Plane::new(DVec3::Y, 0.0));
+
+    // Lying capsule (axis turned onto x) slightly sunk into the ground.
+    let capsule = Shape::Capsule(Capsule::new(2.0, 0.5));
+    let lying = Pose::new(
+        DQuat::from_rotation_z(std::f64::consts::FRAC_PI_2),
+        DVec3::new(0.0, 0.4, 0.0),
+    );
+    let c = contact(&ground, Pose::IDENTITY, &capsule, lying).unwrap();
+    assert!((c.depth - 0.1).abs() < 1e-12);
+    assert_eq!(c.normal, DVec3::Y);
+
+    // Same pair, other order: normal points from the shape into the plane.
+    let flipped = contact(&capsule, lying, &ground, Pose::IDENTITY).unwrap();
+    assert_eq!(flipped.normal, -DVec3::Y);
+
+    let cylinder = Shape::Cylinder(Cylinder::new(1.0, 0.5));
+    assert!(contact(&ground, Pose::IDENTITY, &cylinder, at(0.0, 1.01, 0.0)).is_none());
+    let c = contact(&ground, Pose::IDENTITY, &cylinder, at(0.0, 0.9, 0.0)).unwrap();
+    assert!((c.depth - 0.1).abs() < 1e-12);
+}
+
+#[test]
+fn test_capsule_cylinder_side_contact() {
+    let capsule = Shape::Capsule(Capsule::new(1.0, 0.25));
+    let cylinder = Shape::Cylinder(Cylinder::new(1.0, 0.5));
+    let c = contact(&cylinder, Pose::IDENTITY, &capsule, at(0.7, 0.0, 0.0)).unwrap();
+    assert!((c.depth - 0.05).abs() < 1e-9, "{c:?}");
+    assert!((c.normal - DVec3::X).length() < 1e-9);
+}
+
+#[test]
+fn test_collider_constraint_keeps_particles_out() {
+    // A particle dropped onto a tilted box slides off instead of tunnelling.
+    let mut state = PhaseSpace::new(3);
+    state.radius[0] = 0.1;
+    state.q.copy_from_slice(&[0.3, 2.0, 0.0]);
+
+    let tilt = Pose::new(DQuat::from_rotation_z(0.3), DVec3::ZERO);
+    let box_shape = Shape::Cuboid(Cuboid::new(DVec3::new(2.0, 0.5, 2.0)));
+    let constraints: Vec<Box<dyn Constraint>> = vec![Box::new(ColliderConstraint::new(
+        box_shape.clone(),
+        tilt,
+        0.0,
+        0.0,
+    ))];
+
+    let mut laws = LawRegistry::new();
+    laws.add(UniformGravity(9.81));
+    let mut solver = VelocityVerlet;
+    for _ in 0..300 {
+        solver.step(&mut state, &laws, &constraints, 0.005);
+        let particle = Shape::Sphere(Sphere::new(0.1));
+        let pose = at(state.q[0], state.q[1], state.q[2]);
+        if let Some(c) = contact(&box_shape, tilt, &particle, pose) {
+            assert!(c.depth < 1e-6, "particle sank {} into the box", c.depth);
+        }
+    }
+    // It slid downhill (towards -x for a counter-clockwise tilt).
+    assert!(state.q[0] < 0.3);
+}
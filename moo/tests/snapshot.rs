@@ -0,0 +1,204 @@
+use glam::{DQuat, DVec3, Vec4};
+use moo::core::solve::{Integrator, VelocityVerlet};
+use moo::core::state::attributes::names;
+use moo::core::state::{Block, PhaseSpace, SNAPSHOT_VERSION};
+use moo::laws::registry::LawRegistry;
+use std::io;
+
+fn busy_state() -> PhaseSpace {
+    let mut state = PhaseSpace::new(12);
+    for i in 0..4 {
+        let x = i as f64;
+        state.set_position(i, DVec3::new(x.sin(), 0.1 * x, -1.0 / 3.0));
+        state.set_velocity(i, DVec3::new(0.0, x, 1e-17));
+        state.radius[i] = 0.25 + x;
+    }
+    state.set_particle_mass(2, 7.5);
+    state.resize_rigid(2);
+    state.rot[1] = DQuat::from_rotation_y(0.3);
+    state.ang_v[1] = DVec3::new(0.1, -2.0, 3.0);
+    state.inertia[0] = DVec3::new(1.0, 2.0, 3.0);
+    state.resize_directors(1);
+    state.dir[0] = DVec3::new(0.6, 0.8, 0.0);
+    state.add_attribute(names::CHARGE, -1.0);
+    state.add_attribute(names::MATERIAL, 3u32);
+    state.add_attribute(names::COLOR, Vec4::new(1.0, 0.5, 0.25, 1.0));
+    state.t = 12.345678901234567;
+    state
+}
+
+fn assert_same(a: &PhaseSpace, b: &PhaseSpace) {
+    assert_eq!((a.dof, a.dim, a.t), (b.dof, b.dim, b.t));
+    assert_eq!(a.q, b.q);
+    assert_eq!(a.v, b.v);
+    assert_eq!(a.mass, b.mass);
+    assert_eq!(a.radius, b.radius);
+    assert_eq!(
+        (&a.rot, &a.ang_v, &a.inertia),
+        (&b.rot, &b.ang_v, &b.inertia)
+    );
+    assert_eq!((&a.dir, &a.dir_v), (&b.dir, &b.dir_v));
+    assert_eq!(a.attributes, b.attributes);
+    assert_eq!(a.blocks, b.blocks);
+}
+
+fn invalid_data<T: std::fmt::Debug>(result: io::Result<T>) -> String {
+    let err = result.unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    err.to_string()
+}
+
+#[test]
+fn test_ron_round_trip() {
+    let state = busy_state();
+    let text = state.to_ron().unwrap();
+    assert!(text.contains(&format!("version: {SNAPSHOT_VERSION}")));
+    assert!(text.contains("charge"));
+    // Bit-exact, including values that do not print finitely.
+    assert_same(&PhaseSpace::from_ron(&text).unwrap(), &state);
+}
+
+#[test]
+fn test_binary_round_trip() {
+    let state = busy_state();
+    let bytes = state.to_bytes().unwrap();
+    assert_eq!(&bytes[4..8], &SNAPSHOT_VERSION.to_le_bytes());
+    assert!(bytes.len() < state.to_ron().unwrap().len());
+    assert_same(&PhaseSpace::from_bytes(&bytes).unwrap(), &state);
+
+    assert!(invalid_data(PhaseSpace::from_bytes(b"nonsense")).contains("not a moo snapshot"));
+    assert!(PhaseSpace::from_bytes(&bytes[..bytes.len() - 5]).is_err());
+}
+
+#[test]
+fn test_version_header() {
+    let mut bytes = busy_state().to_bytes().unwrap();
+    bytes[4..8].copy_from_slice(&(SNAPSHOT_VERSION + 1).to_le_bytes());
+    assert!(invalid_data(PhaseSpace::from_bytes(&bytes)).contains("version"));
+
+    let text = busy_state().to_ron().unwrap().replacen(
+        &format!("version: {SNAPSHOT_VERSION}"),
+        "version: 99",
+        1,
+    );
+    assert!(invalid_data(PhaseSpace::from_ron(&text)).contains("version 99"));
+}
+
+#[test]
+fn test_old_checkpoint_fills_defaults() {
+    // A minimal hand-written checkpoint from before attributes and directors existed.
+    let text = "(
+        version: 1,
+        state: (
+            dof: 6,
+            dim: 3,
+            q: [0, 0, 0, 1, 2, 3],
+            v: [0, 0, 0, 0, 0, 0],
+            mass: [1, 1, 1, 2, 2, 2],
+            radius: [0.5, 0.5],
+            t: 4.0,
+        ),
+    )";
+    let state = PhaseSpace::from_ron(text).unwrap();
+    assert_eq!(state.position(1), DVec3::new(1.0, 2.0, 3.0));
+    assert_eq!(state.t, 4.0);
+    assert!(state.dir.is_empty());
+    assert_eq!(state.attributes.names().count(), 0);
+    assert_eq!(
+        state.blocks,
+        [Block::Euclidean {
+            dim: 3,
+            start: 0,
+            count: 2
+        }]
+    );
+
+    // Inconsistent arrays are rejected rather than loaded.
+    let broken = text.replace("radius: [0.5, 0.5]", "radius: [0.5]");
+    assert!(invalid_data(PhaseSpace::from_ron(&broken)).contains("radius"));
+}
+
+#[test]
+fn test_checkpoint_without_blocks_still_moves() {
+    let text = "(
+        version: 1,
+        state: (dof: 3, dim: 3, q: [0, 0, 0], v: [1, 0, 0], mass: [1, 1, 1], radius: [0.5]),
+    )";
+    let mut state = PhaseSpace::from_ron(text).unwrap();
+    VelocityVerlet.step(&mut state, &LawRegistry::new(), &[], 0.5);
+    assert_eq!(state.position(0), DVec3::new(0.5, 0.0, 0.0));
+}
+
+#[test]
+fn test_save_and_load_files() {
+    let dir = std::env::temp_dir().join(format!("moo-snapshot-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let state = busy_state();
+
+    for name in ["state.ron", "state.bin"] {
+        let path = dir.join(name);
+        state.save(&path).unwrap();
+        assert_same(&PhaseSpace::load(&path).unwrap(), &state);
+    }
+    assert!(
+        std::fs::read_to_string(dir.join("state.ron"))
+            .unwrap()
+            .starts_with('(')
+    );
+    assert!(
+        std::fs::read(dir.join("state.bin"))
+            .unwrap()
+            .starts_with(b"MOO")
+    );
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_reads_version_1_binary_fixture() {
+    // Written by the first binary format; must keep loading as PhaseSpace grows.
+    let bytes = include_bytes!("fixtures/snapshot_v1.bin");
+    assert_eq!(&bytes[4..8], &1u32.to_le_bytes());
+    let state = PhaseSpace::from_bytes(bytes).unwrap();
+
+    assert_eq!((state.dof, state.dim, state.t), (6, 3, 0.125));
+    assert_eq!(state.position(0), DVec3::new(1.0, 2.0, 3.0));
+    assert_eq!(state.velocity(1), DVec3::new(-0.5, 0.0, 0.25));
+    assert_eq!(state.mass, vec![1.0, 1.0, 1.0, 4.0, 4.0, 4.0]);
+    assert_eq!(state.radius[1], 0.75);
+    assert_eq!(state.rot, vec![DQuat::from_rotation_z(0.5)]);
+    assert_eq!(state.ang_v, vec![DVec3::new(0.0, 0.0, 2.0)]);
+    assert_eq!(state.dir, vec![DVec3::Y]);
+    assert_eq!(
+        state.attribute::<f64>(names::CHARGE),
+        Some(&[1.5, -1.5][..])
+    );
+    assert_eq!(state.blocks.len(), 3);
+}
+
+#[test]
+fn test_blocks_must_fit_their_storage() {
+    for block in [
+        Block::Euclidean {
+            dim: 3,
+            start: 3,
+            count: 4,
+        },
+        Block::Euclidean {
+            dim: 0,
+            start: 0,
+            count: 1,
+        },
+        Block::Rotation {
+            start: 1,
+            count: usize::MAX,
+        },
+        Block::Director { start: 1, count: 1 },
+    ] {
+        let mut state = busy_state();
+        state.blocks.push(block);
+        let bytes = state.to_bytes().unwrap();
+        assert!(invalid_data(PhaseSpace::from_bytes(&bytes)).contains("block"));
+        let text = state.to_ron().unwrap();
+        assert!(invalid_data(PhaseSpace::from_ron(&text)).contains("block"));
+    }
+}
@@ -0,0 +1,161 @@
+use glam::DVec3;
+use moo::core::solve::{Integrator, VelocityVerlet};
+use moo::core::state::attributes::names;
+use moo::core::state::{ParticleRegistry, PhaseSpace};
+use moo::investigation::probe::{EnergyProbe, Probe};
+use moo::laws::classical::Spring;
+use moo::laws::registry::LawRegistry;
+use moo::platform::storage::{
+    Compression, Field, Frame, TrajectoryMeta, TrajectoryReader, TrajectoryWriter,
+};
+use std::io::{self, Cursor};
+
+fn oscillator() -> (PhaseSpace, LawRegistry) {
+    let mut state = PhaseSpace::new(6);
+    state.set_position(1, DVec3::new(1.5, 0.0, 0.0));
+    state.set_velocity(0, DVec3::new(0.0, 0.3, 0.0));
+    let mut laws = LawRegistry::new();
+    laws.add(Spring::new(4.0, 1.0, 0, 1));
+    (state, laws)
+}
+
+#[test]
+fn test_record_and_seek() {
+    let (mut state, laws) = oscillator();
+    let dt = 0.01;
+    let probes: [&dyn Probe; 1] = [&EnergyProbe];
+    let meta = TrajectoryMeta::new("VelocityVerlet", &laws, dt)
+        .with_probes(&probes)
+        .with_note("units", "SI");
+    let mut writer = TrajectoryWriter::new(Vec::new(), meta.clone())
+        .unwrap()
+        .with_chunk_frames(7);
+
+    let mut expected = Vec::new();
+    for _ in 0..100 {
+        writer.record(&state, &laws, &probes).unwrap();
+        expected.push((state.t, state.q.clone(), state.v.clone()));
+        VelocityVerlet.step(&mut state, &laws, &[], dt);
+    }
+    assert_eq!(writer.len(), 100);
+    let bytes = writer.finish().unwrap();
+
+    let mut reader = TrajectoryReader::new(Cursor::new(bytes)).unwrap();
+    assert_eq!(reader.meta(), &meta);
+    assert_eq!(reader.meta().integrator, "VelocityVerlet");
+    assert!(reader.meta().laws[0].starts_with("Spring { k: 4.0, rest_length: 1.0"));
+    assert_eq!(reader.len(), 100);
+
+    // Out of order, across chunk boundaries.
+    for i in [99, 0, 23, 6, 7, 50] {
+        let frame = reader.frame(i).unwrap();
+        let (t, q, v) = &expected[i];
+        assert_eq!(frame.t, *t);
+        assert!((frame.t - i as f64 * dt).abs() < 1e-12);
+        assert_eq!(&frame.fields[0], q);
+        assert_eq!(&frame.fields[1], v);
+    }
+    assert_eq!(
+        reader.frame(100).unwrap_err().kind(),
+        io::ErrorKind::InvalidInput
+    );
+
+    let energy = reader.probe_series(EnergyProbe.name()).unwrap().unwrap();
+    assert_eq!(energy.len(), 100);
+    assert!(energy.iter().all(|&(_, e)| (e - energy[0].1).abs() < 1e-3));
+    assert!(reader.probe_series("missing").unwrap().is_none());
+}
+
+#[test]
+fn test_compression() {
+    let (state, laws) = oscillator();
+    let write = |compression| {
+        let meta = TrajectoryMeta::new("VelocityVerlet", &laws, 0.01);
+        let mut writer = TrajectoryWriter::new(Vec::new(), meta)
+            .unwrap()
+            .with_compression(compression);
+        for _ in 0..200 {
+            writer.record(&state, &laws, &[]).unwrap();
+        }
+        writer.finish().unwrap()
+    };
+
+    let raw = write(Compression::None);
+    let packed = write(Compression::Best);
+    assert!(
+        packed.len() * 5 < raw.len(),
+        "{} vs {}",
+        packed.len(),
+        raw.len()
+    );
+    let mut a = TrajectoryReader::new(Cursor::new(raw)).unwrap();
+    let mut b = TrajectoryReader::new(Cursor::new(packed)).unwrap();
+    assert_eq!(a.frame(150).unwrap(), b.frame(150).unwrap());
+}
+
+#[test]
+fn test_unfinished_file_recovers_complete_chunks() {
+    let (state, laws) = oscillator();
+    let meta = TrajectoryMeta::new("VelocityVerlet", &laws, 0.01);
+    let mut bytes = Vec::new();
+    let mut writer = TrajectoryWriter::new(&mut bytes, meta)
+        .unwrap()
+        .with_chunk_frames(10);
+    for _ in 0..35 {
+        writer.record(&state, &laws, &[]).unwrap();
+    }
+    // A crash: the pending frames and the index never reach the file...
+    std::mem::forget(writer);
+    // ...and the last chunk is torn mid-write.
+    bytes.extend_from_slice(&[1, 10, 0, 0, 0, 0xff, 0xff, 0, 0, 0, 0, 0, 0, 7, 7]);
+
+    let mut reader = TrajectoryReader::new(Cursor::new(bytes)).unwrap();
+    assert_eq!(reader.len(), 30);
+    assert_eq!(reader.frame(29).unwrap().fields[0], state.q);
+
+    let err = TrajectoryReader::new(Cursor::new(b"not a trajectory".to_vec())).err();
+    assert_eq!(err.unwrap().kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn test_fields_follow_particle_count() {
+    let mut state = PhaseSpace::new(3);
+    state.add_attribute(names::TEMPERATURE, 300.0);
+    let mut registry = ParticleRegistry::new(&state);
+    let laws = LawRegistry::new();
+    let meta = TrajectoryMeta::new("VelocityVerlet", &laws, 1.0).with_fields([
+        Field::Radii,
+        Field::Attribute(names::TEMPERATURE.to_string()),
+        Field::Attribute(names::CHARGE.to_string()),
+    ]);
+
+    let dir = std::env::temp_dir().join(format!("moo-trajectory-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("run.mtj");
+    {
+        let mut writer = TrajectoryWriter::create(&path, meta).unwrap();
+        for step in 0..4 {
+            state.t = step as f64;
+            writer.record(&state, &laws, &[]).unwrap();
+            registry.insert(&mut state, DVec3::ZERO, DVec3::ZERO);
+        }
+        // Frames must match the metadata.
+        let err = writer.push(Frame::default()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        // Dropping the writer finishes the file.
+    }
+
+    let mut reader = TrajectoryReader::open(&path).unwrap();
+    assert_eq!(reader.len(), 4);
+    let temperature = reader
+        .meta()
+        .field_index(&Field::Attribute(names::TEMPERATURE.to_string()));
+    for step in 0..4 {
+        let frame = reader.frame(step).unwrap();
+        assert_eq!(frame.t, step as f64);
+        assert_eq!(frame.fields[0].len(), step + 1);
+        assert_eq!(frame.fields[temperature.unwrap()], vec![300.0; step + 1]);
+        assert!(frame.fields[2].is_empty());
+    }
+    std::fs::remove_dir_all(&dir).unwrap();
+}
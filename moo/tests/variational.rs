@@ -0,0 +1,118 @@
+mod common;
+
+use common::{Harmonic, Sun};
+use glam::DVec3;
+use moo::core::math::Real;
+use moo::core::solve::{
+    Integrator, Kinetic, Quadratic, Quadrature, VariationalIntegrator, VelocityVerlet,
+};
+use moo::core::state::PhaseSpace;
+use moo::laws::registry::LawRegistry;
+
+/// `T = (1 + |q|²) |v|² / 2`: a configuration-dependent mass, rotation invariant.
+struct Stiffening;
+
+impl Kinetic for Stiffening {
+    fn kinetic<T: Real>(&self, q: &[T], v: &[T], _mass: &[f64]) -> T {
+        let (mut q2, mut v2) = (T::constant(0.0), T::constant(0.0));
+        for (&q, &v) in q.iter().zip(v) {
+            q2 = q2 + q * q;
+            v2 = v2 + v * v;
+        }
+        (q2 + 1.0) * v2 * 0.5
+    }
+}
+
+fn stiffening_oscillator() -> (PhaseSpace, LawRegistry) {
+    let mut state = PhaseSpace::new(3);
+    state.set_position(0, DVec3::new(1.0, 0.0, 0.2));
+    state.set_velocity(0, DVec3::new(0.0, 0.8, 0.1));
+    let mut laws = LawRegistry::new();
+    laws.add(Harmonic(1.0));
+    (state, laws)
+}
+
+/// `p = (1 + |q|²) v`, `L = q × p`, `H = T + V`.
+fn momentum(state: &PhaseSpace) -> DVec3 {
+    let q = state.position(0);
+    (1.0 + q.length_squared()) * state.velocity(0)
+}
+
+fn angular_momentum(state: &PhaseSpace) -> DVec3 {
+    state.position(0).cross(momentum(state))
+}
+
+fn hamiltonian(state: &PhaseSpace) -> f64 {
+    let q = state.position(0);
+    0.5 * (1.0 + q.length_squared()) * state.velocity(0).length_squared() + 0.5 * q.length_squared()
+}
+
+#[test]
+fn test_trapezoidal_quadrature_is_velocity_verlet() {
+    let mut a = PhaseSpace::new(3);
+    a.set_position(0, DVec3::new(1.5, 0.0, 0.0));
+    a.set_velocity(0, DVec3::new(0.0, 0.5, 0.1));
+    let mut b = a.clone();
+    let mut laws = LawRegistry::new();
+    laws.add(Sun);
+
+    let mut integrator = VariationalIntegrator::new(Quadratic, Quadrature::Trapezoidal);
+    for _ in 0..200 {
+        integrator.step(&mut a, &laws, &[], 0.05);
+        VelocityVerlet.step(&mut b, &laws, &[], 0.05);
+        assert!(integrator.converged);
+    }
+    assert!((a.t - b.t).abs() < 1e-12);
+    for (x, y) in a.q.iter().chain(&a.v).zip(b.q.iter().chain(&b.v)) {
+        assert!((x - y).abs() < 1e-9, "{x} vs {y}");
+    }
+}
+
+#[test]
+fn test_midpoint_is_second_order_for_non_separable_systems() {
+    let run = |steps: usize| {
+        let (mut state, laws) = stiffening_oscillator();
+        let mut integrator = VariationalIntegrator::new(Stiffening, Quadrature::Midpoint);
+        let dt = 2.0 / steps as f64;
+        for _ in 0..steps {
+            integrator.step(&mut state, &laws, &[], dt);
+        }
+        state.position(0)
+    };
+    let reference = run(4000);
+    let error = |steps| (run(steps) - reference).length();
+    let ratio = error(50) / error(100);
+    assert!((3.6..4.4).contains(&ratio), "ratio {ratio}");
+}
+
+#[test]
+fn test_non_separable_system_keeps_its_invariants() {
+    let (mut state, laws) = stiffening_oscillator();
+    let (l0, e0) = (angular_momentum(&state), hamiltonian(&state));
+    let mut integrator = VariationalIntegrator::new(Stiffening, Quadrature::Midpoint);
+    let mut worst = 0.0f64;
+    for _ in 0..2000 {
+        integrator.step(&mut state, &laws, &[], 0.05);
+        assert!(integrator.converged);
+        assert!((angular_momentum(&state) - l0).length() < 1e-10);
+        worst = worst.max((hamiltonian(&state) - e0).abs());
+    }
+    // Not exact, but bounded: no drift over ~16 periods.
+    assert!(worst < 1e-2 * e0, "{worst}");
+    assert!((state.t - 100.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_reports_newton_failure() {
+    let (mut state, laws) = stiffening_oscillator();
+    let mut integrator = VariationalIntegrator::new(Stiffening, Quadrature::Midpoint);
+    integrator.step(&mut state, &laws, &[], 0.05);
+    assert!(integrator.converged);
+    assert!(integrator.iterations <= 6, "{}", integrator.iterations);
+
+    integrator.max_iterations = 1;
+    integrator.step(&mut state, &laws, &[], 0.05);
+    assert!(!integrator.converged);
+    assert_eq!(integrator.iterations, 1);
+    assert!(state.q.iter().chain(&state.v).all(|x| x.is_finite()));
+}
@@ -0,0 +1,41 @@
+use moo::core::math::ad::Dual;
+use moo::core::state::PhaseSpace;
+use moo::laws::classical::{Gravity, Spring};
+use moo::laws::continuum::SPH;
+use moo::laws::registry::LawRegistry;
+
+#[test]
+fn test_chunked_gradient_matches_scalar_sweep() {
+    // 7 particles -> 21 DOF, deliberately not a multiple of the chunk width.
+    let n = 7;
+    let mut state = PhaseSpace::new(n * 3);
+    for i in 0..n {
+        let t = i as f64;
+        state.q[i * 3] = (1.3 * t).cos() * (1.0 + 0.2 * t);
+        state.q[i * 3 + 1] = (0.7 * t).sin() * (1.0 + 0.1 * t);
+        state.q[i * 3 + 2] = 0.15 * t;
+        state.set_particle_mass(i, 1.0 + 0.5 * t);
+    }
+
+    let mut registry = LawRegistry::new();
+    registry.add(Gravity::new(1.0));
+    registry.add(Spring::new(5.0, 0.5, 0, 3));
+    registry.add(SPH::new(1.5, 1.0, 10.0));
+
+    let mut grad = vec![0.0; state.dof];
+    registry.gradient(&state.q, &state.mass, &mut grad);
+
+    let mut inputs: Vec<Dual> = state.q.iter().map(|&x| Dual::constant(x)).collect();
+    for i in 0..state.dof {
+        inputs[i].der = 1.0;
+        let expected = registry.potential(&inputs, &state.mass).der;
+        inputs[i].der = 0.0;
+
+        assert!(
+            (grad[i] - expected).abs() < 1e-10 * (1.0 + expected.abs()),
+            "DOF {i}: chunked {} vs scalar {}",
+            grad[i],
+            expected
+        );
+    }
+}